@@ -3,7 +3,7 @@
 use bytes::Bytes;
 use criterion::{criterion_group, criterion_main, Criterion};
 use futures::stream;
-use multigear::{MemoryStorage, Multer, MulterError};
+use multigear::{DiskStorage, MemoryStorage, Multer, MulterError};
 
 fn benchmark_upload_parse(c: &mut Criterion) {
     let runtime = tokio::runtime::Builder::new_current_thread()
@@ -27,6 +27,97 @@ fn benchmark_upload_parse(c: &mut Criterion) {
     });
 }
 
+fn benchmark_disk_small_chunks(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("tokio runtime");
+    let root = std::env::temp_dir().join(format!("multigear-bench-{}", std::process::id()));
+    let chunks = build_chunked_body(1000, 64);
+
+    let mut group = c.benchmark_group("disk_store_1000_small_chunks");
+    for (name, use_copy_buf) in [("copy_buf", true), ("write_all", false)] {
+        let storage = DiskStorage::builder()
+            .destination(root.join(name))
+            .use_copy_buf(use_copy_buf)
+            .build()
+            .expect("disk storage");
+        let multer = Multer::new(storage);
+
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter(|| async {
+                let input = stream::iter(chunks.clone().into_iter().map(Ok::<Bytes, MulterError>));
+                let output = multer
+                    .parse_and_store("BOUND", input)
+                    .await
+                    .expect("pipeline should succeed");
+                let path = output.stored_files[0].path.as_ref().expect("stored path");
+                tokio::fs::remove_file(path)
+                    .await
+                    .expect("remove stored file");
+            });
+        });
+    }
+    group.finish();
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+fn benchmark_field_count_hint(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("tokio runtime");
+    let body = Bytes::from(build_text_fields_body(64));
+
+    let mut group = c.benchmark_group("parse_and_store_64_text_fields");
+    for (name, hint) in [("no_hint", None), ("field_count_hint", Some(64))] {
+        let mut builder = Multer::builder().storage(MemoryStorage::new());
+        if let Some(hint) = hint {
+            builder = builder.field_count_hint(hint);
+        }
+        let multer = builder.build().expect("multer config");
+
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter(|| async {
+                let output = multer
+                    .parse_and_store(
+                        "BOUND",
+                        stream::iter([Ok::<Bytes, MulterError>(body.clone())]),
+                    )
+                    .await
+                    .expect("pipeline should succeed");
+                assert_eq!(output.text_fields.len(), 64);
+            });
+        });
+    }
+    group.finish();
+}
+
+fn build_text_fields_body(field_count: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    for index in 0..field_count {
+        out.extend_from_slice(
+            format!(
+                "--BOUND\r\nContent-Disposition: form-data; name=\"field{index}\"\r\n\r\nvalue{index}\r\n"
+            )
+            .as_bytes(),
+        );
+    }
+    out.extend_from_slice(b"--BOUND--\r\n");
+    out
+}
+
+fn build_chunked_body(chunk_count: usize, chunk_size: usize) -> Vec<Bytes> {
+    let mut chunks = Vec::with_capacity(chunk_count + 2);
+    chunks.push(Bytes::from_static(
+        b"--BOUND\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"bench.bin\"\r\n\r\n",
+    ));
+    chunks.extend((0..chunk_count).map(|_| Bytes::from(vec![b'x'; chunk_size])));
+    chunks.push(Bytes::from_static(b"\r\n--BOUND--\r\n"));
+    chunks
+}
+
 fn build_body(size: usize) -> Vec<u8> {
     let mut out = Vec::with_capacity(size + 256);
     out.extend_from_slice(
@@ -37,5 +128,10 @@ fn build_body(size: usize) -> Vec<u8> {
     out
 }
 
-criterion_group!(benches, benchmark_upload_parse);
+criterion_group!(
+    benches,
+    benchmark_upload_parse,
+    benchmark_disk_small_chunks,
+    benchmark_field_count_hint
+);
 criterion_main!(benches);
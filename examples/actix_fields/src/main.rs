@@ -35,6 +35,9 @@ async fn upload(
             }
         }
     }
+    if let Err(err) = multipart.validate_required_fields() {
+        return HttpResponse::BadRequest().body(err.to_string());
+    }
 
     let mut body = format!(
         "stored {} file(s), parsed {} text field(s)\n",
@@ -76,7 +79,7 @@ const INDEX_HTML: &str = r#"<!doctype html>
 </head>
 <body>
   <h1>Actix Fields Example</h1>
-  <p>Allowed fields: <code>metadata</code> (text), <code>thumbnail</code> (jpeg/png), <code>gallery</code> (image/*).</p>
+  <p>Allowed fields: <code>metadata</code> (text), <code>thumbnail</code> (required, jpeg/png), <code>gallery</code> (image/*).</p>
   <form action="/products" method="post" enctype="multipart/form-data">
     <label>Metadata:</label><br />
     <textarea name="metadata" rows="4" cols="60">{"name":"sample product"}</textarea><br /><br />
@@ -102,7 +105,7 @@ async fn main() -> io::Result<()> {
         .fields([
             Field::text("metadata").max_size(16 * 1024),
             Field::file("thumbnail")
-                .max_count(1)
+                .required()
                 .allowed_mime_types(["image/jpeg", "image/png"]),
             Field::file("gallery")
                 .max_count(8)
@@ -29,6 +29,7 @@ async fn upload(
             text_fields.push((field_name, text));
         }
     }
+    multipart.validate_required_fields().map_err(err)?;
 
     let mut body = format!(
         "stored {} file(s), parsed {} text field(s)\n",
@@ -72,7 +73,7 @@ const INDEX_HTML: &str = r#"<!doctype html>
 </head>
 <body>
   <h1>Axum Fields Example</h1>
-  <p>Allowed fields: <code>metadata</code> (text), <code>thumbnail</code> (jpeg/png), <code>gallery</code> (image/*).</p>
+  <p>Allowed fields: <code>metadata</code> (text), <code>thumbnail</code> (required, jpeg/png), <code>gallery</code> (image/*).</p>
   <form action="/products" method="post" enctype="multipart/form-data">
     <label>Metadata:</label><br />
     <textarea name="metadata" rows="4" cols="60">{"name":"sample product"}</textarea><br /><br />
@@ -99,7 +100,7 @@ async fn main() -> io::Result<()> {
             .fields([
                 Field::text("metadata").max_size(16 * 1024),
                 Field::file("thumbnail")
-                    .max_count(1)
+                    .required()
                     .allowed_mime_types(["image/jpeg", "image/png"]),
                 Field::file("gallery")
                     .max_count(8)
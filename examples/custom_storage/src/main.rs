@@ -1,11 +1,14 @@
 #![allow(missing_docs)]
 
-use std::{collections::HashMap, io, sync::Arc};
+use std::{collections::HashMap, env, io, sync::Arc};
 
 use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use bytes::Bytes;
 use futures::StreamExt;
-use multigear::{BoxStream, Multer, MulterError, StorageEngine, StorageError};
+use multigear::{
+    BoxStream, BoxedStorage, DynStorageEngine, MemoryStorage, Multer, MulterError, StorageEngine,
+    StorageError, StoredFile,
+};
 use tokio::sync::RwLock;
 
 #[derive(Debug, Clone, Default)]
@@ -13,25 +16,16 @@ struct HashMapStorage {
     files: Arc<RwLock<HashMap<String, Bytes>>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct HashMapKey(String);
-
-impl HashMapStorage {
-    async fn len(&self) -> usize {
-        self.files.read().await.len()
-    }
-}
-
 #[async_trait::async_trait]
 impl StorageEngine for HashMapStorage {
-    type Output = HashMapKey;
+    type Output = StoredFile;
     type Error = StorageError;
 
     async fn store(
         &self,
         field_name: &str,
-        _file_name: Option<&str>,
-        _content_type: &str,
+        file_name: Option<&str>,
+        content_type: &str,
         mut stream: BoxStream<'_, Result<Bytes, MulterError>>,
     ) -> Result<Self::Output, Self::Error> {
         let key = format!("{field_name}-{}", self.files.read().await.len());
@@ -41,16 +35,27 @@ impl StorageEngine for HashMapStorage {
             content.extend_from_slice(&chunk);
         }
 
+        let size = content.len() as u64;
         self.files
             .write()
             .await
             .insert(key.clone(), Bytes::from(content));
-        Ok(HashMapKey(key))
+        Ok(StoredFile {
+            storage_key: key,
+            field_name: field_name.to_owned(),
+            file_name: file_name.map(str::to_owned),
+            content_type: content_type
+                .parse()
+                .unwrap_or(mime::APPLICATION_OCTET_STREAM),
+            size,
+            path: None,
+            digest: None,
+        })
     }
 }
 
 async fn upload(
-    data: web::Data<Multer<HashMapStorage>>,
+    data: web::Data<Multer<BoxedStorage>>,
     request: HttpRequest,
     payload: web::Payload,
 ) -> impl Responder {
@@ -66,7 +71,7 @@ async fn upload(
     } {
         if part.file_name().is_some() {
             match data.store(part).await {
-                Ok(key) => stored_keys.push(key.0),
+                Ok(file) => stored_keys.push(file.storage_key),
                 Err(err) => return HttpResponse::BadRequest().body(err.to_string()),
             };
         } else if let Err(err) = part.text().await {
@@ -74,12 +79,7 @@ async fn upload(
         }
     }
 
-    let total = data.storage().len().await;
-    let mut body = format!(
-        "stored {} file(s) in this request; custom storage now has {} item(s)\n",
-        stored_keys.len(),
-        total
-    );
+    let mut body = format!("stored {} file(s) in this request\n", stored_keys.len());
     for key in stored_keys {
         body.push_str(&format!("- key={key}\n"));
     }
@@ -113,10 +113,18 @@ const INDEX_HTML: &str = r#"<!doctype html>
 
 #[actix_web::main]
 async fn main() -> io::Result<()> {
-    let storage = HashMapStorage::default();
+    // Backends sharing the `StoredFile` output are routed by name at startup.
+    let mut backends: HashMap<&str, Box<dyn DynStorageEngine>> = HashMap::new();
+    backends.insert("hashmap", HashMapStorage::default().into_boxed());
+    backends.insert("memory", MemoryStorage::new().into_boxed());
+    let backend = env::var("STORAGE_BACKEND").unwrap_or_else(|_| "hashmap".to_owned());
+    let storage = backends
+        .remove(backend.as_str())
+        .expect("STORAGE_BACKEND should be `hashmap` or `memory`");
+
     let multer = Multer::builder()
         .array("upload", 10)
-        .storage(storage)
+        .storage(BoxedStorage::from(storage))
         .build()
         .expect("multer should build");
 
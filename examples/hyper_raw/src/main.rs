@@ -1,15 +1,12 @@
 #![allow(missing_docs)]
 
-use std::{convert::Infallible, io, net::SocketAddr, sync::Arc};
+use std::{collections::HashMap, convert::Infallible, io, net::SocketAddr, sync::Arc};
 
 use bytes::Bytes;
 use http_body_util::{BodyExt, Full};
 use hyper::{
-    body::Incoming,
-    header::CONTENT_TYPE,
-    server::conn::http1,
-    service::service_fn,
-    Method, Request, Response, StatusCode,
+    body::Incoming, header::CONTENT_TYPE, server::conn::http1, service::service_fn, Method,
+    Request, Response, StatusCode,
 };
 use hyper_util::rt::TokioIo;
 use multigear::{DiskStorage, FilenameStrategy, Multer};
@@ -53,6 +50,13 @@ async fn upload(
         }
     };
 
+    // Forwarded to the storage engine alongside each file.
+    let session = request
+        .headers()
+        .get("x-upload-session")
+        .and_then(|value| value.to_str().ok())
+        .map(ToOwned::to_owned);
+
     let stream = request.into_body().into_data_stream();
     let mut multipart = match multer.parse_stream(stream, boundary).await {
         Ok(value) => value,
@@ -72,7 +76,11 @@ async fn upload(
         }
     } {
         if part.file_name().is_some() {
-            match multer.store(part).await {
+            let mut extra = HashMap::new();
+            if let Some(session) = &session {
+                extra.insert("upload_session".to_owned(), session.clone());
+            }
+            match multer.store_with_meta(part, extra).await {
                 Ok(file) => stored.push(file),
                 Err(err) => {
                     return text_response(
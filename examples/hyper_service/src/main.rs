@@ -12,7 +12,7 @@ use hyper::{
     Method, Request, Response, StatusCode,
 };
 use hyper_util::rt::TokioIo;
-use multigear::{hyper::MulterService, DiskStorage, FilenameStrategy, Multer, StoredFile};
+use multigear::{hyper::MulterService, DiskStorage, FilenameStrategy, Multer, ProcessedMultipart};
 use tokio::net::TcpListener;
 
 async fn handle(
@@ -22,8 +22,8 @@ async fn handle(
     match (request.method(), request.uri().path()) {
         (&Method::GET, "/") => Ok(html_response(INDEX_HTML)),
         (&Method::POST, "/upload") => {
-            let service = MulterService::new(multer, |saved_files: Vec<StoredFile>| async move {
-                Ok::<_, io::Error>(upload_response(saved_files))
+            let service = MulterService::new(multer, |processed: ProcessedMultipart| async move {
+                Ok::<_, io::Error>(upload_response(processed))
             });
 
             match service.call(request).await {
@@ -38,9 +38,9 @@ async fn handle(
     }
 }
 
-fn upload_response(saved_files: Vec<StoredFile>) -> Response<Full<Bytes>> {
-    let mut body = format!("stored {} file(s)\n", saved_files.len());
-    for file in saved_files {
+fn upload_response(processed: ProcessedMultipart) -> Response<Full<Bytes>> {
+    let mut body = format!("stored {} file(s)\n", processed.stored_files.len());
+    for file in processed.stored_files {
         let original_name = file.file_name.as_deref().unwrap_or("<none>");
         let path = file
             .path
@@ -52,6 +52,9 @@ fn upload_response(saved_files: Vec<StoredFile>) -> Response<Full<Bytes>> {
             file.field_name, original_name, file.size, path
         ));
     }
+    for (name, value) in processed.text_fields {
+        body.push_str(&format!("- text {name}={value}\n"));
+    }
 
     text_response(StatusCode::OK, body)
 }
@@ -83,6 +86,7 @@ const INDEX_HTML: &str = r#"<!doctype html>
   <h1>Hyper MulterService Example</h1>
   <p>Field name: <code>files</code> (multiple allowed)</p>
   <form action="/upload" method="post" enctype="multipart/form-data">
+    <input type="text" name="caption" placeholder="Caption" />
     <input type="file" name="files" multiple />
     <button type="submit">Upload</button>
   </form>
@@ -0,0 +1,70 @@
+#![allow(missing_docs)]
+
+use std::sync::Arc;
+
+use multigear::{
+    rocket::{MulterFairing, MulterForm},
+    MemoryStorage, Multer,
+};
+use rocket::{get, post, response::content::RawHtml, routes};
+
+#[post("/upload", data = "<form>")]
+fn upload(form: MulterForm<MemoryStorage>) -> String {
+    let form = form.into_inner();
+    let mut body = format!(
+        "received {} text field(s) and {} file(s)\n",
+        form.text_fields.len(),
+        form.file_parts.len()
+    );
+    for (name, value) in &form.text_fields {
+        body.push_str(&format!("- text {name}={value}\n"));
+    }
+    for part in &form.file_parts {
+        body.push_str(&format!(
+            "- file field={} original={} bytes={}\n",
+            part.field_name(),
+            part.file_name().unwrap_or("<none>"),
+            part.body.len()
+        ));
+    }
+    body
+}
+
+const INDEX_HTML: &str = r#"<!doctype html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <meta name="viewport" content="width=device-width, initial-scale=1" />
+  <title>multigear rocket upload</title>
+</head>
+<body>
+  <h1>Rocket Upload Example</h1>
+  <p>Field name: <code>documents</code> (up to 8 files)</p>
+  <form action="/upload" method="post" enctype="multipart/form-data">
+    <input type="text" name="title" placeholder="title" />
+    <input type="file" name="documents" multiple />
+    <button type="submit">Upload</button>
+  </form>
+</body>
+</html>
+"#;
+
+#[get("/")]
+fn index() -> RawHtml<&'static str> {
+    RawHtml(INDEX_HTML)
+}
+
+#[rocket::launch]
+fn rocket() -> _ {
+    let multer = Multer::builder()
+        .array("documents", 8)
+        .max_file_size(16 * 1024 * 1024)
+        .max_body_size(64 * 1024 * 1024)
+        .storage(MemoryStorage::new())
+        .build()
+        .expect("multer should build");
+
+    rocket::build()
+        .attach(MulterFairing::new(Arc::new(multer)))
+        .mount("/", routes![index, upload])
+}
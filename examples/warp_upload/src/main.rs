@@ -0,0 +1,110 @@
+#![allow(missing_docs)]
+
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+use multigear::{
+    warp::{warp_filter, WarpMulterRejection, WarpMultipart},
+    DiskStorage, FilenameStrategy, Multer, MulterError,
+};
+use warp::{http::StatusCode, Filter, Rejection, Reply};
+
+async fn upload(
+    multer: Arc<Multer<DiskStorage>>,
+    mut multipart: WarpMultipart,
+) -> Result<String, Rejection> {
+    let mut stored = Vec::new();
+
+    while let Some(part) = multipart.next_part().await.map_err(reject)? {
+        if part.file_name().is_some() {
+            stored.push(multer.store(part).await.map_err(reject)?);
+        }
+    }
+
+    let mut body = format!("stored {} file(s)\n", stored.len());
+    for file in stored {
+        let original_name = file.file_name.as_deref().unwrap_or("<none>");
+        let path = file
+            .path
+            .as_ref()
+            .map(|value| value.display().to_string())
+            .unwrap_or_else(|| "<none>".to_owned());
+        body.push_str(&format!(
+            "- field={} original={} bytes={} path={}\n",
+            file.field_name, original_name, file.size, path
+        ));
+    }
+
+    Ok(body)
+}
+
+fn reject(e: MulterError) -> Rejection {
+    warp::reject::custom(WarpMulterRejection(e))
+}
+
+async fn recover(rejection: Rejection) -> Result<impl Reply, Infallible> {
+    let (status, message) = match rejection.find::<WarpMulterRejection>() {
+        Some(WarpMulterRejection(err)) => (
+            StatusCode::from_u16(err.http_status()).unwrap_or(StatusCode::BAD_REQUEST),
+            err.to_string(),
+        ),
+        None if rejection.is_not_found() => (StatusCode::NOT_FOUND, "not found".to_owned()),
+        None => (StatusCode::BAD_REQUEST, format!("{rejection:?}")),
+    };
+    Ok(warp::reply::with_status(message, status))
+}
+
+const INDEX_HTML: &str = r#"<!doctype html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <meta name="viewport" content="width=device-width, initial-scale=1" />
+  <title>multigear warp upload</title>
+</head>
+<body>
+  <h1>Warp Upload Example</h1>
+  <p>Field name: <code>documents</code> (up to 8 files)</p>
+  <form action="/upload" method="post" enctype="multipart/form-data">
+    <input type="file" name="documents" multiple />
+    <button type="submit">Upload</button>
+  </form>
+</body>
+</html>
+"#;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let storage = DiskStorage::builder()
+        .destination(std::env::temp_dir().join("multigear-warp-upload"))
+        .filename(FilenameStrategy::Random)
+        .build()
+        .expect("disk storage should build");
+
+    let multer = Arc::new(
+        Multer::builder()
+            .array("documents", 8)
+            .max_file_size(16 * 1024 * 1024)
+            .storage(storage)
+            .build()
+            .expect("multer should build"),
+    );
+
+    let index = warp::path::end()
+        .and(warp::get())
+        .map(|| warp::reply::html(INDEX_HTML));
+    let with_multer = {
+        let multer = Arc::clone(&multer);
+        warp::any().map(move || Arc::clone(&multer))
+    };
+    let upload = warp::path("upload")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(with_multer)
+        .and(warp_filter(multer))
+        .and_then(upload);
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], 8089));
+    println!("warp-upload-example running at http://{addr}");
+    warp::serve(index.or(upload).recover(recover))
+        .run(addr)
+        .await;
+}
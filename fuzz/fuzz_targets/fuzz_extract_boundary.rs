@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use multigear::parser::boundary::extract_multipart_boundary;
+
+fuzz_target!(|data: &[u8]| fuzz_extract_boundary(data));
+
+/// Feeds arbitrary input to boundary extraction and checks it never panics.
+fn fuzz_extract_boundary(input: &[u8]) {
+    let Ok(content_type) = std::str::from_utf8(input) else {
+        return;
+    };
+
+    if let Ok(boundary) = extract_multipart_boundary(content_type) {
+        assert!(!boundary.is_empty());
+        assert!(boundary.len() <= 70);
+    }
+}
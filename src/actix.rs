@@ -9,14 +9,17 @@ use std::{
 use actix_web::{
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
     error::PayloadError,
-    http::header,
+    http::{header, StatusCode},
     rt,
     web::{self, Bytes},
-    FromRequest, HttpRequest,
+    FromRequest, HttpRequest, ResponseError,
 };
 use futures::{channel::mpsc, Stream, StreamExt};
 
-use crate::{Multer, MulterError, Multipart, ParseError, StorageEngine};
+use crate::{
+    parser::headers::parse_content_length, ConfigError, Multer, MulterError, Multipart, ParseError,
+    StorageEngine,
+};
 
 /// Actix body stream mapped into `multigear` chunk errors.
 pub type ActixMappedBodyStream<S> =
@@ -24,6 +27,20 @@ pub type ActixMappedBodyStream<S> =
 /// Actix payload stream converted into a `Send` stream for multipart parsing.
 pub type ActixBodyStream = mpsc::UnboundedReceiver<Result<Bytes, MulterError>>;
 
+/// Answers with [`MulterError::http_status`] and the error message as body.
+impl ResponseError for MulterError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.http_status()).unwrap_or(StatusCode::BAD_REQUEST)
+    }
+}
+
+/// Configuration errors are server-side failures and answer with `500`.
+impl ResponseError for ConfigError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
 /// Extracts the raw `Content-Type` header from an Actix request.
 pub fn content_type_from_request(request: &HttpRequest) -> Result<&str, MulterError> {
     let value = request
@@ -66,7 +83,14 @@ where
     S: StorageEngine,
 {
     let content_type = content_type_from_request(request)?;
-    multer.multipart_from_content_type(content_type, payload_to_send_stream(payload))
+    let content_length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_content_length);
+    Ok(multer
+        .multipart_from_content_type(content_type, payload_to_send_stream(payload))?
+        .with_request_content_length(content_length))
 }
 
 /// Helper that extracts multipart from an Actix request and payload.
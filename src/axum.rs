@@ -1,16 +1,22 @@
 //! Axum integration helpers.
 
 use axum::{
-    body::Bytes,
-    extract::FromRequest,
+    body::{Body, Bytes},
+    extract::{FromRequest, Request},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
 };
-use futures::{stream, Stream, StreamExt};
+use futures::{future::BoxFuture, stream, Stream, StreamExt};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
 
-use crate::{Multer, MulterError, Multipart, ParseError, StorageEngine};
+use crate::{
+    parser::{extract_multipart_boundary, headers::content_length},
+    ConfigError, Multer, MulterError, Multipart, ParseError, StorageEngine,
+};
 
 /// Axum body stream mapped into `multigear` chunk errors.
 pub type AxumBodyStream<S> =
@@ -29,7 +35,15 @@ pub struct AxumMulterRejection(pub MulterError);
 
 impl IntoResponse for AxumMulterRejection {
     fn into_response(self) -> Response {
-        (StatusCode::BAD_REQUEST, self.0.to_string()).into_response()
+        let status = StatusCode::from_u16(self.0.http_status()).unwrap_or(StatusCode::BAD_REQUEST);
+        (status, self.0.to_string()).into_response()
+    }
+}
+
+/// Configuration errors are server-side failures and answer with `500`.
+impl IntoResponse for ConfigError {
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
     }
 }
 
@@ -87,10 +101,7 @@ where
 {
     type Rejection = AxumMulterRejection;
 
-    async fn from_request(
-        request: axum::extract::Request,
-        state: &AppState,
-    ) -> Result<Self, Self::Rejection> {
+    async fn from_request(request: Request, state: &AppState) -> Result<Self, Self::Rejection> {
         let (parts, body) = request.into_parts();
         let content_type =
             content_type_from_headers(&parts.headers).map_err(AxumMulterRejection)?;
@@ -99,12 +110,165 @@ where
 
         let multipart = state
             .build_multipart(content_type, body_stream)
-            .map_err(AxumMulterRejection)?;
+            .map_err(AxumMulterRejection)?
+            .with_request_content_length(content_length(&parts.headers));
 
         Ok(Self(multipart))
     }
 }
 
+/// Options controlling [`MulterLayer`] behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MulterLayerConfig {
+    /// Forwards requests without a `multipart/form-data` content type untouched
+    /// instead of rejecting them.
+    pub skip_on_non_multipart: bool,
+}
+
+/// `tower::Layer` that parses and stores multipart bodies before the handler runs.
+///
+/// The resulting [`crate::ProcessedMultipart`] is inserted as a request
+/// extension, so handlers can read it with
+/// `Extension<ProcessedMultipart<StoredFile>>`. Parse and storage failures are
+/// answered with [`AxumMulterRejection`] without calling the inner service.
+pub struct MulterLayer<S> {
+    multer: Arc<Multer<S>>,
+    config: MulterLayerConfig,
+}
+
+impl<S> MulterLayer<S> {
+    /// Creates a layer backed by a shared `Multer` instance.
+    pub fn new(multer: Arc<Multer<S>>) -> Self {
+        Self {
+            multer,
+            config: MulterLayerConfig::default(),
+        }
+    }
+
+    /// Replaces the layer options.
+    pub fn with_config(mut self, config: MulterLayerConfig) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+impl<S> Clone for MulterLayer<S> {
+    fn clone(&self) -> Self {
+        Self {
+            multer: Arc::clone(&self.multer),
+            config: self.config,
+        }
+    }
+}
+
+impl<S> std::fmt::Debug for MulterLayer<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MulterLayer")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, Inner> Layer<Inner> for MulterLayer<S> {
+    type Service = MulterMiddleware<Inner, S>;
+
+    fn layer(&self, inner: Inner) -> Self::Service {
+        MulterMiddleware {
+            inner,
+            multer: Arc::clone(&self.multer),
+            config: self.config,
+        }
+    }
+}
+
+/// Service produced by [`MulterLayer`].
+pub struct MulterMiddleware<Inner, S> {
+    inner: Inner,
+    multer: Arc<Multer<S>>,
+    config: MulterLayerConfig,
+}
+
+impl<Inner: Clone, S> Clone for MulterMiddleware<Inner, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            multer: Arc::clone(&self.multer),
+            config: self.config,
+        }
+    }
+}
+
+impl<Inner, S> std::fmt::Debug for MulterMiddleware<Inner, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MulterMiddleware")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Inner, S> Service<Request> for MulterMiddleware<Inner, S>
+where
+    Inner: Service<Request, Response = Response> + Clone + Send + 'static,
+    Inner::Future: Send + 'static,
+    S: StorageEngine,
+    S::Output: Clone + Sync + 'static,
+{
+    type Response = Response;
+    type Error = Inner::Error;
+    type Future = BoxFuture<'static, Result<Response, Inner::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Take the service that was driven to readiness and leave a fresh clone behind.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let multer = Arc::clone(&self.multer);
+        let config = self.config;
+
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+            if config.skip_on_non_multipart && !is_multipart(&parts.headers) {
+                return inner.call(Request::from_parts(parts, body)).await;
+            }
+
+            let processed = match parse_request_body(&multer, &parts.headers, body).await {
+                Ok(processed) => processed,
+                Err(err) => return Ok(AxumMulterRejection(err).into_response()),
+            };
+            parts.extensions.insert(processed);
+            inner.call(Request::from_parts(parts, Body::empty())).await
+        })
+    }
+}
+
+async fn parse_request_body<S>(
+    multer: &Multer<S>,
+    headers: &HeaderMap,
+    body: Body,
+) -> Result<crate::ProcessedMultipart<S::Output>, MulterError>
+where
+    S: StorageEngine,
+{
+    let boundary = extract_multipart_boundary(content_type_from_headers(headers)?)?;
+    let body_stream = Box::pin(map_body_stream(body.into_data_stream())) as AxumBodyBoxStream;
+    multer.parse_and_store(boundary, body_stream).await
+}
+
+fn is_multipart(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|media_type| {
+            media_type
+                .trim()
+                .eq_ignore_ascii_case("multipart/form-data")
+        })
+}
+
 /// Extracts the raw `Content-Type` header from Axum request headers.
 pub fn content_type_from_headers(headers: &HeaderMap) -> Result<&str, MulterError> {
     let value = headers
@@ -134,7 +298,9 @@ where
     B: Stream<Item = Result<Bytes, axum::Error>> + Unpin,
 {
     let content_type = content_type_from_headers(headers)?;
-    multer.multipart_from_content_type(content_type, map_body_stream(body))
+    Ok(multer
+        .multipart_from_content_type(content_type, map_body_stream(body))?
+        .with_request_content_length(content_length(headers)))
 }
 
 fn axum_item_to_multer(item: Result<Bytes, axum::Error>) -> Result<Bytes, MulterError> {
@@ -1,16 +1,25 @@
+use futures::future::BoxFuture;
+
 use crate::{
-    config::{MulterConfig, Selector, UnknownFieldPolicy},
+    config::{AsyncCompletionHook, CompletionHook, MulterConfig, Selector, UnknownFieldPolicy},
     error::ConfigError,
     limits::Limits,
-    storage::NoopStorage,
-    Multer,
+    storage::{BoxedStorage, DynStorageEngine, NoopStorage, StorageEngine},
+    Multer, ProcessedMultipart,
 };
 
+mod multipart_body;
+
+pub use multipart_body::MultipartBodyBuilder;
+
 /// Builder for configuring a `Multer` instance.
 #[derive(Debug, Clone)]
 pub struct MulterBuilder<S = NoopStorage> {
     config: MulterConfig,
     storage: S,
+    on_complete: Option<CompletionHook<S>>,
+    on_complete_async: Option<AsyncCompletionHook<S>>,
+    hooks_discarded: bool,
 }
 
 impl Default for MulterBuilder<NoopStorage> {
@@ -18,6 +27,9 @@ impl Default for MulterBuilder<NoopStorage> {
         Self {
             config: MulterConfig::default(),
             storage: NoopStorage,
+            on_complete: None,
+            on_complete_async: None,
+            hooks_discarded: false,
         }
     }
 }
@@ -27,6 +39,63 @@ impl MulterBuilder<NoopStorage> {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Creates a builder with limits read from `MULTER_*` environment variables.
+    ///
+    /// Reads `MULTER_MAX_FILE_SIZE`, `MULTER_MAX_FILES`, `MULTER_MAX_FIELD_SIZE`,
+    /// `MULTER_MAX_FIELDS`, `MULTER_MAX_BODY_SIZE` and the comma-separated
+    /// `MULTER_ALLOWED_MIME_TYPES`. Unset variables keep the builder defaults;
+    /// a value that does not parse returns [`ConfigError::InvalidLimitValue`]
+    /// naming the variable.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut builder = Self::new();
+        let limits = &mut builder.config.limits;
+        if let Some(value) = env_limit("MULTER_MAX_FILE_SIZE")? {
+            limits.max_file_size = Some(value);
+        }
+        if let Some(value) = env_limit("MULTER_MAX_FILES")? {
+            limits.max_files = Some(value);
+        }
+        if let Some(value) = env_limit("MULTER_MAX_FIELD_SIZE")? {
+            limits.max_field_size = Some(value);
+        }
+        if let Some(value) = env_limit("MULTER_MAX_FIELDS")? {
+            limits.max_fields = Some(value);
+        }
+        if let Some(value) = env_limit("MULTER_MAX_BODY_SIZE")? {
+            limits.max_body_size = Some(value);
+        }
+        if let Some(value) = env_var("MULTER_ALLOWED_MIME_TYPES")? {
+            limits.allowed_mime_types = value
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(str::to_owned)
+                .collect();
+        }
+        Ok(builder)
+    }
+}
+
+fn env_var(name: &'static str) -> Result<Option<String>, ConfigError> {
+    match std::env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err(ConfigError::InvalidLimitValue { limit: name })
+        }
+    }
+}
+
+fn env_limit<T: std::str::FromStr>(name: &'static str) -> Result<Option<T>, ConfigError> {
+    env_var(name)?
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| ConfigError::InvalidLimitValue { limit: name })
+        })
+        .transpose()
 }
 
 impl<S> MulterBuilder<S> {
@@ -36,13 +105,43 @@ impl<S> MulterBuilder<S> {
     }
 
     /// Replaces the storage backend used by the built `Multer`.
+    ///
+    /// Completion hooks are typed by the backend output, so hooks registered
+    /// before this call make [`MulterBuilder::build`] fail with
+    /// [`ConfigError::CompletionHooksDiscarded`].
     pub fn storage<T>(self, storage: T) -> MulterBuilder<T> {
         MulterBuilder {
             config: self.config,
             storage,
+            on_complete: None,
+            on_complete_async: None,
+            hooks_discarded: self.hooks_discarded
+                || self.on_complete.is_some()
+                || self.on_complete_async.is_some(),
         }
     }
 
+    /// Replaces the storage backend with a type-erased [`BoxedStorage`].
+    ///
+    /// Use this when the backend is chosen at runtime but the `Multer` type
+    /// must stay the same.
+    pub fn boxed_storage<T>(self, storage: T) -> MulterBuilder<BoxedStorage>
+    where
+        T: DynStorageEngine,
+    {
+        self.storage(BoxedStorage::new(storage))
+    }
+
+    /// Seeds the builder with an existing base configuration.
+    ///
+    /// Builder calls made afterwards override the corresponding settings, so
+    /// a shared base can be specialised per endpoint. To layer a whole
+    /// override config instead, use [`MulterConfig::merge`].
+    pub fn base_config(mut self, config: MulterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Replaces the full builder configuration.
     pub fn with_config(mut self, config: MulterConfig) -> Self {
         self.config = config;
@@ -68,6 +167,26 @@ impl<S> MulterBuilder<S> {
     }
 
     /// Selects multiple named fields.
+    ///
+    /// Accepts [`SelectedField`](crate::SelectedField)s or the [`Field`](crate::Field)
+    /// models; `required()` and `optional()` express how often a field may appear.
+    ///
+    /// ```rust
+    /// use multigear::{Field, Multer, UnknownFieldPolicy};
+    ///
+    /// let multer = Multer::builder()
+    ///     .fields([
+    ///         Field::file("avatar")
+    ///             .required()
+    ///             .allowed_mime_types(["image/png", "image/jpeg"]),
+    ///         Field::file("attachment").optional(),
+    ///         Field::text("caption").max_size(1024),
+    ///     ])
+    ///     .on_unknown_field(UnknownFieldPolicy::Reject)
+    ///     .build()
+    ///     .expect("fields should validate");
+    /// # let _ = multer;
+    /// ```
     pub fn fields<F>(mut self, fields: impl IntoIterator<Item = F>) -> Self
     where
         F: Into<crate::config::SelectedField>,
@@ -129,12 +248,31 @@ impl<S> MulterBuilder<S> {
         self
     }
 
+    /// Sets the maximum number of parts, files and text combined.
+    pub fn max_parts(mut self, max_parts: usize) -> Self {
+        self.config.limits.max_parts = Some(max_parts);
+        self
+    }
+
     /// Sets the maximum accepted multipart request size in bytes.
     pub fn max_body_size(mut self, max_body_size: u64) -> Self {
         self.config.limits.max_body_size = Some(max_body_size);
         self
     }
 
+    /// Sets the maximum size in bytes of a single part's header block.
+    pub fn max_header_size(mut self, max_header_size: usize) -> Self {
+        self.config.limits.max_header_size = Some(max_header_size);
+        self
+    }
+
+    /// Sets how long `next_part` waits for the next part before failing with
+    /// [`MulterError::Timeout`](crate::MulterError::Timeout).
+    pub fn part_read_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.config.limits.part_read_timeout = Some(timeout);
+        self
+    }
+
     /// Sets the global list of allowed MIME patterns.
     pub fn allowed_mime_types<I, M>(mut self, allowed_mime_types: I) -> Self
     where
@@ -146,6 +284,28 @@ impl<S> MulterBuilder<S> {
         self
     }
 
+    /// Rejects `parse_and_store` requests that do not contain any file parts.
+    pub fn require_file_parts(mut self) -> Self {
+        self.config.require_at_least_one_file = true;
+        self
+    }
+
+    /// Sets the expected number of parts so `parse_and_store` can pre-allocate its output.
+    ///
+    /// This is purely a performance hint and never limits the number of parts.
+    pub fn field_count_hint(mut self, field_count_hint: usize) -> Self {
+        self.config.field_count_hint = Some(field_count_hint);
+        self
+    }
+
+    /// Enables `multer.part`, `multer.store` and `multer.parse_and_store` tracing spans.
+    ///
+    /// Spans are off by default and require the `tracing` feature.
+    pub fn with_tracing_spans(mut self, enabled: bool) -> Self {
+        self.config.emit_spans = enabled;
+        self
+    }
+
     /// Validates builder configuration.
     pub fn validate(&self) -> Result<(), ConfigError> {
         self.config.validate()
@@ -159,6 +319,40 @@ impl<S> MulterBuilder<S> {
 
     /// Builds a fully configured `Multer` instance.
     pub fn build(self) -> Result<Multer<S>, ConfigError> {
-        Multer::with_config(self.storage, self.config)
+        if self.hooks_discarded {
+            return Err(ConfigError::CompletionHooksDiscarded);
+        }
+        let mut multer = Multer::with_config(self.storage, self.config)?;
+        multer.on_complete = self.on_complete;
+        multer.on_complete_async = self.on_complete_async;
+        Ok(multer)
+    }
+}
+
+impl<S> MulterBuilder<S>
+where
+    S: StorageEngine,
+{
+    /// Registers a hook invoked after `parse_and_store` successfully processes all parts.
+    ///
+    /// The hook runs synchronously on the async task, so it must not block.
+    /// Register it after choosing the storage backend.
+    pub fn on_complete<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ProcessedMultipart<S::Output>) + Send + Sync + 'static,
+    {
+        self.on_complete = Some(CompletionHook::new(hook));
+        self
+    }
+
+    /// Registers an async hook awaited after `parse_and_store` successfully processes all parts.
+    ///
+    /// Register it after choosing the storage backend.
+    pub fn on_complete_async<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ProcessedMultipart<S::Output>) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+    {
+        self.on_complete_async = Some(AsyncCompletionHook::new(hook));
+        self
     }
 }
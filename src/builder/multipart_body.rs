@@ -0,0 +1,138 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{stream, Stream};
+
+use crate::MulterError;
+
+/// Builds `multipart/form-data` bodies, mainly for tests and client code.
+///
+/// Field names, filenames and content types are written verbatim, so the
+/// builder can also produce unusual or hostile inputs on purpose.
+///
+/// ```rust
+/// use bytes::Bytes;
+/// use multigear::MultipartBodyBuilder;
+///
+/// let body = MultipartBodyBuilder::new()
+///     .part("title", Bytes::from_static(b"Holiday"))
+///     .file("photo", "a.png", "image/png", Bytes::from_static(b"PNG"))
+///     .build("BOUND");
+///
+/// assert!(body.starts_with(b"--BOUND\r\nContent-Disposition: form-data; name=\"title\""));
+/// assert!(body.ends_with(b"--BOUND--\r\n"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MultipartBodyBuilder {
+    parts: Vec<BodyPart>,
+}
+
+#[derive(Debug, Clone)]
+struct BodyPart {
+    field: String,
+    file_name: Option<String>,
+    content_type: Option<String>,
+    body: Bytes,
+}
+
+impl MultipartBodyBuilder {
+    /// Creates an empty body builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a text field.
+    pub fn part(mut self, field: &str, body: impl Into<Bytes>) -> Self {
+        self.parts.push(BodyPart {
+            field: field.to_owned(),
+            file_name: None,
+            content_type: None,
+            body: body.into(),
+        });
+        self
+    }
+
+    /// Appends a file field with a `Content-Type` header.
+    pub fn file(
+        mut self,
+        field: &str,
+        file_name: &str,
+        content_type: &str,
+        body: impl Into<Bytes>,
+    ) -> Self {
+        self.parts.push(BodyPart {
+            field: field.to_owned(),
+            file_name: Some(file_name.to_owned()),
+            content_type: Some(content_type.to_owned()),
+            body: body.into(),
+        });
+        self
+    }
+
+    /// Appends a file field without a `Content-Type` header.
+    pub fn file_without_content_type(
+        mut self,
+        field: &str,
+        file_name: &str,
+        body: impl Into<Bytes>,
+    ) -> Self {
+        self.parts.push(BodyPart {
+            field: field.to_owned(),
+            file_name: Some(file_name.to_owned()),
+            content_type: None,
+            body: body.into(),
+        });
+        self
+    }
+
+    /// Returns the complete body, terminal boundary included.
+    pub fn build(&self, boundary: &str) -> Bytes {
+        let mut out = BytesMut::new();
+        for part in &self.parts {
+            out.put(part.encode(boundary));
+        }
+        out.put(closing_delimiter(boundary));
+        out.freeze()
+    }
+
+    /// Returns the body as a stream yielding one chunk per part and a final
+    /// chunk for the terminal boundary.
+    pub fn stream(
+        &self,
+        boundary: &str,
+    ) -> impl Stream<Item = Result<Bytes, MulterError>> + Unpin + Send + 'static {
+        let chunks = self
+            .parts
+            .iter()
+            .map(|part| part.encode(boundary))
+            .chain([closing_delimiter(boundary)])
+            .map(Ok)
+            .collect::<Vec<_>>();
+        stream::iter(chunks)
+    }
+}
+
+impl BodyPart {
+    fn encode(&self, boundary: &str) -> Bytes {
+        let mut head = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"{}\"",
+            self.field
+        );
+        if let Some(file_name) = &self.file_name {
+            head.push_str(&format!("; filename=\"{file_name}\""));
+        }
+        head.push_str("\r\n");
+        if let Some(content_type) = &self.content_type {
+            head.push_str(&format!("Content-Type: {content_type}\r\n"));
+        }
+        head.push_str("\r\n");
+
+        let mut out = BytesMut::with_capacity(head.len() + self.body.len() + 2);
+        out.put(head.as_bytes());
+        out.put(self.body.clone());
+        out.put(&b"\r\n"[..]);
+        out.freeze()
+    }
+}
+
+fn closing_delimiter(boundary: &str) -> Bytes {
+    Bytes::from(format!("--{boundary}--\r\n"))
+}
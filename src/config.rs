@@ -1,6 +1,10 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt, sync::Arc};
 
-use crate::{error::ConfigError, limits::Limits};
+use futures::future::BoxFuture;
+
+use crate::{error::ConfigError, limits::Limits, storage::StorageEngine, ProcessedMultipart};
+
+type UnknownFieldFn = dyn Fn(&str) -> UnknownFieldAction + Send + Sync;
 
 /// Discriminates selected field handling between file and text parts.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -22,21 +26,40 @@ pub struct SelectedField {
     pub kind: SelectedFieldKind,
     /// Maximum file count accepted for this field.
     pub max_count: Option<usize>,
+    /// Minimum number of parts required for this field; `0` makes it optional.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_count: usize,
     /// Maximum size accepted for this field in bytes.
     pub max_size: Option<u64>,
     /// Allowed MIME patterns for this field (for example: `image/*`).
     pub allowed_mime_types: Vec<String>,
+    /// Allowed filename extensions for this field (for example: `pdf`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allowed_extensions: Vec<String>,
 }
 
 impl SelectedField {
     /// Creates a selected field with no explicit per-field max count.
+    ///
+    /// The field is always a file field; prefer the explicit
+    /// [`SelectedField::file`] or [`SelectedField::text`] constructors.
+    #[deprecated(
+        note = "use `SelectedField::file` or `SelectedField::text` to state the field kind"
+    )]
     pub fn new(name: impl Into<String>) -> Self {
+        Self::file(name)
+    }
+
+    /// Creates a selected file field.
+    pub fn file(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
             kind: SelectedFieldKind::File,
             max_count: None,
+            min_count: 0,
             max_size: None,
             allowed_mime_types: Vec::new(),
+            allowed_extensions: Vec::new(),
         }
     }
 
@@ -46,8 +69,10 @@ impl SelectedField {
             name: name.into(),
             kind: SelectedFieldKind::Text,
             max_count: None,
+            min_count: 0,
             max_size: None,
             allowed_mime_types: Vec::new(),
+            allowed_extensions: Vec::new(),
         }
     }
 
@@ -62,6 +87,38 @@ impl SelectedField {
         self.with_max_count(max_count)
     }
 
+    /// Sets the minimum number of parts `parse_and_store` requires for this field.
+    pub fn with_min_count(mut self, min_count: usize) -> Self {
+        self.min_count = min_count;
+        self
+    }
+
+    /// Alias for [`SelectedField::with_min_count`].
+    pub fn min_count(self, min_count: usize) -> Self {
+        self.with_min_count(min_count)
+    }
+
+    /// Requires the field to appear.
+    ///
+    /// Sets `min_count` and `max_count` to `1`; the minimum is checked by
+    /// [`Multipart::validate_required_fields`](crate::Multipart::validate_required_fields).
+    /// `max_count` only limits file fields, so a required text field must
+    /// appear at least once and may repeat.
+    pub fn required(mut self) -> Self {
+        self.min_count = 1;
+        self.max_count = Some(1);
+        self
+    }
+
+    /// Allows the field to appear at most once, or not at all.
+    ///
+    /// Sets `min_count` to `0` and `max_count` to `1`.
+    pub fn optional(mut self) -> Self {
+        self.min_count = 0;
+        self.max_count = Some(1);
+        self
+    }
+
     /// Sets the maximum size accepted for this field in bytes.
     pub fn with_max_size(mut self, max_size: u64) -> Self {
         self.max_size = Some(max_size);
@@ -92,6 +149,28 @@ impl SelectedField {
         self.with_allowed_mime_types(patterns)
     }
 
+    /// Sets filename extensions accepted for this field.
+    ///
+    /// Extensions are compared case-insensitively and may be given with or
+    /// without a leading dot.
+    pub fn with_allowed_extensions<I, E>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<String>,
+    {
+        self.allowed_extensions = extensions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Alias for [`SelectedField::with_allowed_extensions`].
+    pub fn allowed_extensions<I, E>(self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<String>,
+    {
+        self.with_allowed_extensions(extensions)
+    }
+
     /// Validates a single selected field configuration.
     pub fn validate(&self) -> Result<(), ConfigError> {
         if self.name.trim().is_empty() {
@@ -104,6 +183,16 @@ impl SelectedField {
             });
         }
 
+        if let Some(max_count) = self.max_count {
+            if self.min_count > max_count {
+                return Err(ConfigError::MinCountExceedsMaxCount {
+                    name: self.name.clone(),
+                    min_count: self.min_count,
+                    max_count,
+                });
+            }
+        }
+
         if matches!(self.max_size, Some(0)) {
             return Err(ConfigError::InvalidFieldMaxSize {
                 name: self.name.clone(),
@@ -118,6 +207,14 @@ impl SelectedField {
             }
         }
 
+        for extension in &self.allowed_extensions {
+            if extension.trim().trim_start_matches('.').is_empty() {
+                return Err(ConfigError::InvalidExtension {
+                    name: self.name.clone(),
+                });
+            }
+        }
+
         Ok(())
     }
 }
@@ -140,6 +237,17 @@ pub enum Selector {
     },
     /// Accept files for a set of named fields.
     Fields(Vec<SelectedField>),
+    /// Accept files for every field name matching a regular expression.
+    ///
+    /// The pattern must match the whole field name. Matching needs the
+    /// `regex` feature; without it the selector fails validation with
+    /// [`ConfigError::InvalidRegexPattern`].
+    Regex {
+        /// Field name pattern, e.g. `attachment_[0-9]+`.
+        pattern: String,
+        /// Maximum number of files accepted across all matching fields.
+        max_count: Option<usize>,
+    },
     /// Reject all file parts.
     None,
     /// Accept files for any field name.
@@ -166,6 +274,42 @@ impl Selector {
         Self::Fields(fields.into_iter().collect())
     }
 
+    /// Creates a `fields(...)` selector from a [`SelectorFormBuilder`] closure.
+    ///
+    /// ```rust
+    /// use multigear::Selector;
+    ///
+    /// let selector = Selector::for_form(|form| {
+    ///     form.text("title")
+    ///         .file_with_count("photos", 4)
+    ///         .file_with_mime("avatar", ["image/*"])
+    /// });
+    /// assert!(selector.validate().is_ok());
+    /// ```
+    pub fn for_form<F>(build: F) -> Self
+    where
+        F: FnOnce(SelectorFormBuilder) -> SelectorFormBuilder,
+    {
+        build(SelectorFormBuilder::new()).build()
+    }
+
+    /// Creates a selector that allows files for field names matching `pattern`.
+    ///
+    /// ```rust
+    /// use multigear::Selector;
+    ///
+    /// let selector = Selector::regex("attachment_[0-9]+");
+    /// assert!(selector.validate().is_ok());
+    /// assert!(Selector::regex("attachment_[").validate().is_err());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: impl Into<String>) -> Self {
+        Self::Regex {
+            pattern: pattern.into(),
+            max_count: None,
+        }
+    }
+
     /// Creates a selector that rejects all file uploads.
     pub fn none() -> Self {
         Self::None
@@ -203,6 +347,14 @@ impl Selector {
                     }
                 }
             }
+            Self::Regex { pattern, max_count } => {
+                validate_field_pattern(pattern)?;
+                if matches!(max_count, Some(0)) {
+                    return Err(ConfigError::InvalidFieldMaxCount {
+                        name: pattern.clone(),
+                    });
+                }
+            }
             Self::None | Self::Any => {}
         }
 
@@ -210,27 +362,336 @@ impl Selector {
     }
 }
 
+#[cfg(feature = "regex")]
+fn validate_field_pattern(pattern: &str) -> Result<(), ConfigError> {
+    compile_field_pattern(pattern).map(drop)
+}
+
+#[cfg(not(feature = "regex"))]
+fn validate_field_pattern(pattern: &str) -> Result<(), ConfigError> {
+    Err(ConfigError::InvalidRegexPattern {
+        pattern: pattern.to_owned(),
+        error: "the `regex` feature is not enabled".to_owned(),
+    })
+}
+
+/// Compiles a [`Selector::Regex`] pattern anchored to the whole field name.
+#[cfg(feature = "regex")]
+pub(crate) fn compile_field_pattern(pattern: &str) -> Result<regex::Regex, ConfigError> {
+    regex::Regex::new(&format!("^(?:{pattern})$")).map_err(|err| ConfigError::InvalidRegexPattern {
+        pattern: pattern.to_owned(),
+        error: err.to_string(),
+    })
+}
+
+/// Fluent builder for multi-field selectors used by [`Selector::for_form`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectorFormBuilder {
+    fields: Vec<SelectedField>,
+}
+
+impl SelectorFormBuilder {
+    /// Creates an empty form builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a text field.
+    pub fn text(self, name: impl Into<String>) -> Self {
+        self.field(SelectedField::text(name))
+    }
+
+    /// Adds a file field without a per-field count limit.
+    pub fn file(self, name: impl Into<String>) -> Self {
+        self.field(SelectedField::file(name))
+    }
+
+    /// Adds a file field accepting at most `max_count` files.
+    pub fn file_with_count(self, name: impl Into<String>, max_count: usize) -> Self {
+        self.field(SelectedField::file(name).with_max_count(max_count))
+    }
+
+    /// Adds a file field restricted to the given MIME patterns.
+    pub fn file_with_mime<I, M>(self, name: impl Into<String>, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = M>,
+        M: Into<String>,
+    {
+        self.field(SelectedField::file(name).with_allowed_mime_types(patterns))
+    }
+
+    /// Adds a fully configured field.
+    pub fn field(mut self, field: impl Into<SelectedField>) -> Self {
+        self.fields.push(field.into());
+        self
+    }
+
+    /// Builds a `fields(...)` selector from the collected fields.
+    pub fn build(self) -> Selector {
+        Selector::Fields(self.fields)
+    }
+}
+
 /// Policy for handling fields not described by the active selector.
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Default)]
 pub enum UnknownFieldPolicy {
     /// Reject unknown fields with an error.
     Reject,
     /// Ignore unknown fields.
     #[default]
     Ignore,
+    /// Rename unknown file fields to `to` and apply that field's selector rules.
+    ///
+    /// Unknown text fields are ignored.
+    Redirect {
+        /// Field name that unknown file parts are reported under.
+        to: String,
+    },
+    /// Decide per field by calling the closure with the unknown field name.
+    Callback(Arc<UnknownFieldFn>),
+}
+
+impl UnknownFieldPolicy {
+    /// Creates a [`UnknownFieldPolicy::Callback`] policy from a closure.
+    ///
+    /// ```rust
+    /// use multigear::{UnknownFieldAction, UnknownFieldPolicy};
+    ///
+    /// let policy = UnknownFieldPolicy::callback(|name| {
+    ///     if name.starts_with('_') {
+    ///         UnknownFieldAction::IgnoreAndLog
+    ///     } else {
+    ///         UnknownFieldAction::Reject
+    ///     }
+    /// });
+    /// # let _ = policy;
+    /// ```
+    pub fn callback<F>(decide: F) -> Self
+    where
+        F: Fn(&str) -> UnknownFieldAction + Send + Sync + 'static,
+    {
+        Self::Callback(Arc::new(decide))
+    }
+}
+
+impl fmt::Debug for UnknownFieldPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reject => f.write_str("Reject"),
+            Self::Ignore => f.write_str("Ignore"),
+            Self::Redirect { to } => f.debug_struct("Redirect").field("to", to).finish(),
+            Self::Callback(_) => f.write_str("Callback(<fn>)"),
+        }
+    }
+}
+
+/// Callback policies compare equal only when they share the same underlying closure.
+impl PartialEq for UnknownFieldPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Reject, Self::Reject) | (Self::Ignore, Self::Ignore) => true,
+            (Self::Redirect { to: left }, Self::Redirect { to: right }) => left == right,
+            (Self::Callback(left), Self::Callback(right)) => Arc::ptr_eq(left, right),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for UnknownFieldPolicy {}
+
+/// Serializes `Callback` as `"callback"`; the closure itself is not persisted.
+#[cfg(feature = "serde")]
+impl serde::Serialize for UnknownFieldPolicy {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Reject => UnknownFieldPolicyRepr::Reject,
+            Self::Ignore => UnknownFieldPolicyRepr::Ignore,
+            Self::Redirect { to } => UnknownFieldPolicyRepr::Redirect { to: to.clone() },
+            Self::Callback(_) => UnknownFieldPolicyRepr::Callback,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes `"callback"` as [`UnknownFieldPolicy::Ignore`], since no closure can be restored.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UnknownFieldPolicy {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match UnknownFieldPolicyRepr::deserialize(deserializer)? {
+            UnknownFieldPolicyRepr::Reject => Self::Reject,
+            UnknownFieldPolicyRepr::Ignore | UnknownFieldPolicyRepr::Callback => Self::Ignore,
+            UnknownFieldPolicyRepr::Redirect { to } => Self::Redirect { to },
+        })
+    }
+}
+
+/// Serde representation of [`UnknownFieldPolicy`] without the callback closure.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+enum UnknownFieldPolicyRepr {
+    Reject,
+    Ignore,
+    Redirect {
+        to: String,
+    },
+    #[serde(rename = "callback")]
+    Callback,
 }
 
+/// Action returned by an [`UnknownFieldPolicy::Callback`] for one unknown field.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownFieldAction {
+    /// Skip the field.
+    Ignore,
+    /// Fail with [`crate::MulterError::UnexpectedField`].
+    Reject,
+    /// Skip the field and emit a `tracing` warning when that feature is enabled.
+    IgnoreAndLog,
+}
+
+/// Synchronous hook invoked after [`crate::Multer::parse_and_store`] succeeds.
+///
+/// The hook receives the output of storage backend `S`, so it is registered
+/// through [`crate::MulterBuilder::on_complete`] once the backend is chosen.
+/// Hooks compare equal only when they share the same underlying closure.
+pub struct CompletionHook<S>(Arc<dyn CompletionFn<S>>);
+
+/// Object-safe form of a completion closure, keyed by storage type so hooks
+/// can be stored without bounding every `Multer<S>` on [`StorageEngine`].
+trait CompletionFn<S>: Send + Sync {
+    fn call(&self, output: &ProcessedMultipart<S::Output>)
+    where
+        S: StorageEngine;
+}
+
+impl<S, F> CompletionFn<S> for F
+where
+    S: StorageEngine,
+    F: Fn(&ProcessedMultipart<S::Output>) + Send + Sync,
+{
+    fn call(&self, output: &ProcessedMultipart<S::Output>) {
+        self(output)
+    }
+}
+
+impl<S: StorageEngine> CompletionHook<S> {
+    /// Wraps a completion callback.
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&ProcessedMultipart<S::Output>) + Send + Sync + 'static,
+    {
+        Self(Arc::new(hook))
+    }
+
+    /// Invokes the hook with processed multipart output.
+    pub fn call(&self, output: &ProcessedMultipart<S::Output>) {
+        self.0.call(output)
+    }
+}
+
+impl<S> Clone for CompletionHook<S> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<S> fmt::Debug for CompletionHook<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CompletionHook(<fn>)")
+    }
+}
+
+impl<S> PartialEq for CompletionHook<S> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<S> Eq for CompletionHook<S> {}
+
+/// Asynchronous hook awaited after [`crate::Multer::parse_and_store`] succeeds.
+///
+/// Like [`CompletionHook`], it is typed by the storage backend `S`.
+/// Hooks compare equal only when they share the same underlying closure.
+pub struct AsyncCompletionHook<S>(Arc<dyn AsyncCompletionFn<S>>);
+
+/// Object-safe form of an async completion closure; see [`CompletionFn`].
+trait AsyncCompletionFn<S>: Send + Sync {
+    fn call(&self, output: &ProcessedMultipart<S::Output>) -> BoxFuture<'static, ()>
+    where
+        S: StorageEngine;
+}
+
+impl<S, F> AsyncCompletionFn<S> for F
+where
+    S: StorageEngine,
+    F: Fn(&ProcessedMultipart<S::Output>) -> BoxFuture<'static, ()> + Send + Sync,
+{
+    fn call(&self, output: &ProcessedMultipart<S::Output>) -> BoxFuture<'static, ()> {
+        self(output)
+    }
+}
+
+impl<S: StorageEngine> AsyncCompletionHook<S> {
+    /// Wraps an async completion callback.
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&ProcessedMultipart<S::Output>) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+    {
+        Self(Arc::new(hook))
+    }
+
+    /// Invokes the hook and returns its completion future.
+    pub fn call(&self, output: &ProcessedMultipart<S::Output>) -> BoxFuture<'static, ()> {
+        self.0.call(output)
+    }
+}
+
+impl<S> Clone for AsyncCompletionHook<S> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<S> fmt::Debug for AsyncCompletionHook<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AsyncCompletionHook(<fn>)")
+    }
+}
+
+impl<S> PartialEq for AsyncCompletionHook<S> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<S> Eq for AsyncCompletionHook<S> {}
+
 /// Top-level multipart configuration model.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct MulterConfig {
     /// Selector strategy for file fields.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub selector: Selector,
     /// Behavior when an incoming field does not match the selector.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub unknown_field_policy: UnknownFieldPolicy,
     /// Global request limits.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub limits: Limits,
+    /// Rejects `parse_and_store` requests that contain no file parts.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub require_at_least_one_file: bool,
+    /// Expected number of parts, used to pre-allocate `parse_and_store` output.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub field_count_hint: Option<usize>,
+    /// Emits `multer.*` tracing spans around parsing and storage.
+    ///
+    /// Only takes effect with the `tracing` feature enabled.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub emit_spans: bool,
 }
 
 impl MulterConfig {
@@ -239,12 +700,220 @@ impl MulterConfig {
         Self::default()
     }
 
+    /// Deserializes a configuration from JSON.
+    ///
+    /// Omitted settings keep their defaults. The result is not validated.
+    ///
+    /// ```rust
+    /// use multigear::{MulterConfig, Selector};
+    ///
+    /// let config = MulterConfig::from_json(
+    ///     r#"{"selector": {"Array": {"name": "photos", "max_count": 4}}}"#,
+    /// )
+    /// .expect("valid JSON config");
+    /// assert_eq!(config.selector, Selector::array("photos", 4));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<MulterConfig, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Deserializes a configuration from TOML, with the same rules as
+    /// [`MulterConfig::from_json`].
+    ///
+    /// ```rust
+    /// use multigear::MulterConfig;
+    ///
+    /// let config = MulterConfig::from_toml(
+    ///     r#"
+    ///     [limits]
+    ///     max_file_size = 1048576
+    ///     allowed_mime_types = ["image/*"]
+    ///     "#,
+    /// )
+    /// .expect("valid TOML config");
+    /// assert_eq!(config.limits.max_file_size, Some(1_048_576));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_toml(toml: &str) -> Result<MulterConfig, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
     /// Validates selector and limit configuration.
     pub fn validate(&self) -> Result<(), ConfigError> {
         self.selector.validate()?;
+        if let UnknownFieldPolicy::Redirect { to } = &self.unknown_field_policy {
+            validate_field_name(to)?;
+        }
         validate_limits(&self.limits)?;
         Ok(())
     }
+
+    /// Layers endpoint-specific overrides in `other` on top of `self`.
+    ///
+    /// - A non-default selector in `other` replaces the selector in `self`.
+    /// - Limits merge field by field, see [`Limits::merge`].
+    /// - The unknown field policy from `other` always wins.
+    /// - Flags enabled in `other` stay enabled; `Some` options in `other`
+    ///   replace those in `self`.
+    ///
+    /// The result is not validated; call [`MulterConfig::validate`] on it.
+    pub fn merge(self, other: MulterConfig) -> MulterConfig {
+        MulterConfig {
+            selector: if other.selector == Selector::default() {
+                self.selector
+            } else {
+                other.selector
+            },
+            unknown_field_policy: other.unknown_field_policy,
+            limits: self.limits.merge(other.limits),
+            require_at_least_one_file: self.require_at_least_one_file
+                || other.require_at_least_one_file,
+            field_count_hint: other.field_count_hint.or(self.field_count_hint),
+            emit_spans: self.emit_spans || other.emit_spans,
+        }
+    }
+
+    /// Lists the settings that differ between `self` and `other`.
+    ///
+    /// Each entry reports the value in `self` as `from` and the value in
+    /// `other` as `to`.
+    pub fn diff(&self, other: &MulterConfig) -> Vec<ConfigDiff> {
+        let mut diffs = Vec::new();
+
+        if self.selector != other.selector {
+            diffs.push(ConfigDiff::SelectorChanged {
+                from: self.selector.clone(),
+                to: other.selector.clone(),
+            });
+        }
+        if self.unknown_field_policy != other.unknown_field_policy {
+            diffs.push(ConfigDiff::PolicyChanged {
+                from: self.unknown_field_policy.clone(),
+                to: other.unknown_field_policy.clone(),
+            });
+        }
+
+        let (from, to) = (&self.limits, &other.limits);
+        let limits = [
+            ("max_file_size", from.max_file_size, to.max_file_size),
+            ("max_files", as_u64(from.max_files), as_u64(to.max_files)),
+            ("max_field_size", from.max_field_size, to.max_field_size),
+            ("max_fields", as_u64(from.max_fields), as_u64(to.max_fields)),
+            ("max_parts", as_u64(from.max_parts), as_u64(to.max_parts)),
+            ("max_body_size", from.max_body_size, to.max_body_size),
+            (
+                "max_header_size",
+                as_u64(from.max_header_size),
+                as_u64(to.max_header_size),
+            ),
+            (
+                "part_read_timeout_ms",
+                as_millis(from.part_read_timeout),
+                as_millis(to.part_read_timeout),
+            ),
+        ];
+        for (limit, from, to) in limits {
+            if from != to {
+                diffs.push(ConfigDiff::LimitChanged { limit, from, to });
+            }
+        }
+        if from.allowed_mime_types != to.allowed_mime_types {
+            diffs.push(ConfigDiff::AllowedMimeTypesChanged {
+                from: from.allowed_mime_types.clone(),
+                to: to.allowed_mime_types.clone(),
+            });
+        }
+
+        let settings = [
+            (
+                "require_at_least_one_file",
+                self.require_at_least_one_file != other.require_at_least_one_file,
+            ),
+            (
+                "field_count_hint",
+                self.field_count_hint != other.field_count_hint,
+            ),
+            ("emit_spans", self.emit_spans != other.emit_spans),
+        ];
+        for (setting, changed) in settings {
+            if changed {
+                diffs.push(ConfigDiff::SettingChanged { setting });
+            }
+        }
+
+        diffs
+    }
+
+    /// Lists the settings that differ from [`MulterConfig::default`].
+    pub fn diff_from_default(&self) -> Vec<ConfigDiff> {
+        MulterConfig::default().diff(self)
+    }
+}
+
+/// A single difference reported by [`MulterConfig::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigDiff {
+    /// The file selector changed.
+    SelectorChanged {
+        /// Previous selector.
+        from: Selector,
+        /// New selector.
+        to: Selector,
+    },
+    /// The unknown-field policy changed.
+    PolicyChanged {
+        /// Previous policy.
+        from: UnknownFieldPolicy,
+        /// New policy.
+        to: UnknownFieldPolicy,
+    },
+    /// A numeric limit changed.
+    LimitChanged {
+        /// Name of the limit, matching the [`Limits`] field name.
+        limit: &'static str,
+        /// Previous value.
+        from: Option<u64>,
+        /// New value.
+        to: Option<u64>,
+    },
+    /// The global MIME allowlist changed.
+    AllowedMimeTypesChanged {
+        /// Previous allowlist.
+        from: Vec<String>,
+        /// New allowlist.
+        to: Vec<String>,
+    },
+    /// Another setting changed, such as a hook or pre-allocation hint.
+    SettingChanged {
+        /// Name of the changed [`MulterConfig`] field.
+        setting: &'static str,
+    },
+}
+
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SelectorChanged { from, to } => write!(f, "selector: {from:?} -> {to:?}"),
+            Self::PolicyChanged { from, to } => {
+                write!(f, "unknown_field_policy: {from:?} -> {to:?}")
+            }
+            Self::LimitChanged { limit, from, to } => write!(f, "{limit}: {from:?} -> {to:?}"),
+            Self::AllowedMimeTypesChanged { from, to } => {
+                write!(f, "allowed_mime_types: {from:?} -> {to:?}")
+            }
+            Self::SettingChanged { setting } => write!(f, "{setting} changed"),
+        }
+    }
+}
+
+fn as_u64(value: Option<usize>) -> Option<u64> {
+    value.map(|value| value as u64)
+}
+
+fn as_millis(value: Option<std::time::Duration>) -> Option<u64> {
+    value.map(|value| u64::try_from(value.as_millis()).unwrap_or(u64::MAX))
 }
 
 fn validate_field_name(name: &str) -> Result<(), ConfigError> {
@@ -260,7 +929,29 @@ fn validate_limits(limits: &Limits) -> Result<(), ConfigError> {
     validate_positive_usize("max_files", limits.max_files)?;
     validate_positive_u64("max_field_size", limits.max_field_size)?;
     validate_positive_usize("max_fields", limits.max_fields)?;
+    validate_positive_usize("max_parts", limits.max_parts)?;
     validate_positive_u64("max_body_size", limits.max_body_size)?;
+    validate_positive_usize("max_header_size", limits.max_header_size)?;
+    if limits
+        .part_read_timeout
+        .is_some_and(|timeout| timeout.is_zero())
+    {
+        return Err(ConfigError::InvalidLimitValue {
+            limit: "part_read_timeout",
+        });
+    }
+
+    if let (Some(max_parts), Some(max_files), Some(max_fields)) =
+        (limits.max_parts, limits.max_files, limits.max_fields)
+    {
+        if max_parts < max_files.saturating_add(max_fields) {
+            return Err(ConfigError::PartsLimitBelowCombined {
+                max_parts,
+                max_files,
+                max_fields,
+            });
+        }
+    }
 
     if let Some(max_body_size) = limits.max_body_size {
         if let Some(max_file_size) = limits.max_file_size {
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use thiserror::Error;
 
 /// Configuration-time validation errors.
@@ -56,6 +58,45 @@ pub enum ConfigError {
         /// The invalid pattern value.
         pattern: String,
     },
+    /// A field requires more parts than its `max_count` allows.
+    #[error("field `{name}` has min_count {min_count} greater than max_count {max_count}")]
+    MinCountExceedsMaxCount {
+        /// Name of the field with inconsistent counts.
+        name: String,
+        /// Configured minimum part count.
+        min_count: usize,
+        /// Configured maximum part count.
+        max_count: usize,
+    },
+    /// A field allowlist contains an empty filename extension.
+    #[error("field `{name}` has an empty allowed extension")]
+    InvalidExtension {
+        /// Name of the field with an invalid extension entry.
+        name: String,
+    },
+    /// `max_parts` is lower than `max_files` and `max_fields` combined.
+    #[error(
+        "limit `max_parts` ({max_parts}) cannot be lower than `max_files` ({max_files}) plus `max_fields` ({max_fields})"
+    )]
+    PartsLimitBelowCombined {
+        /// Configured `max_parts`.
+        max_parts: usize,
+        /// Configured `max_files`.
+        max_files: usize,
+        /// Configured `max_fields`.
+        max_fields: usize,
+    },
+    /// A `Selector::Regex` pattern failed to compile.
+    #[error("invalid field name pattern `{pattern}`: {error}")]
+    InvalidRegexPattern {
+        /// The pattern that failed to compile.
+        pattern: String,
+        /// Compiler error message.
+        error: String,
+    },
+    /// Completion hooks were registered before the storage backend was replaced.
+    #[error("completion hooks must be registered after the storage backend is chosen")]
+    CompletionHooksDiscarded,
 }
 
 /// Parser-level multipart failures.
@@ -87,6 +128,48 @@ pub enum StorageError {
         /// Storage failure message.
         message: String,
     },
+    /// A capacity-limited backend could not accept more bytes.
+    #[error("storage capacity of {max} bytes exceeded ({used} bytes in use)")]
+    CapacityExceeded {
+        /// Bytes held by the backend when the write was rejected.
+        used: u64,
+        /// Configured capacity in bytes.
+        max: u64,
+    },
+    /// The target filesystem has less free space than the file needs.
+    #[error("insufficient disk space: {required} bytes required, {available} bytes available")]
+    InsufficientDiskSpace {
+        /// Free bytes reported for the target filesystem.
+        available: u64,
+        /// Declared size of the file being stored.
+        required: u64,
+    },
+    /// Accepting a file would push stored payloads past a global cap.
+    #[error(
+        "global storage cap of {max} bytes exceeded ({stored} bytes stored, {incoming} bytes incoming)"
+    )]
+    GlobalCapacityExceeded {
+        /// Bytes held by stored payloads when the file was rejected.
+        stored: u64,
+        /// Size of the rejected file, or its size hint.
+        incoming: u64,
+        /// Configured global cap in bytes.
+        max: u64,
+    },
+    /// The output path was already taken and collisions are configured to fail.
+    #[error("file `{}` already exists", path.display())]
+    FileAlreadyExists {
+        /// Path of the existing file.
+        path: PathBuf,
+    },
+    /// Bytes read back after a write hashed differently from the bytes written.
+    #[error("write verification failed: expected digest {expected}, read back {actual}")]
+    WriteVerificationFailed {
+        /// Lowercase hex digest of the bytes written.
+        expected: String,
+        /// Lowercase hex digest of the bytes read back.
+        actual: String,
+    },
 }
 
 impl StorageError {
@@ -98,8 +181,14 @@ impl StorageError {
     }
 }
 
+impl From<std::io::Error> for StorageError {
+    fn from(err: std::io::Error) -> Self {
+        Self::new(err.to_string())
+    }
+}
+
 /// Runtime error type used by `multigear`.
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
 #[non_exhaustive]
 pub enum MulterError {
     /// Configuration error surfaced at runtime.
@@ -153,6 +242,12 @@ pub enum MulterError {
         /// Maximum allowed number of text parts.
         max_fields: usize,
     },
+    /// The total number of parts exceeded the configured limit.
+    #[error("multipart request exceeded max parts limit of {max_parts}")]
+    PartsLimitExceeded {
+        /// Maximum allowed number of parts.
+        max_parts: usize,
+    },
     /// The request body exceeded the configured body-size limit.
     #[error("multipart request exceeded max body size of {max_body_size} bytes")]
     BodySizeLimitExceeded {
@@ -167,7 +262,166 @@ pub enum MulterError {
         /// MIME type encountered for the file part.
         mime: String,
     },
+    /// A file extension is not permitted by the field allowlist.
+    #[error("file field `{field}` has disallowed extension `{extension}`")]
+    ExtensionNotAllowed {
+        /// File field name.
+        field: String,
+        /// Extension encountered on the uploaded filename.
+        extension: String,
+    },
+    /// The request contained no file parts although at least one is required.
+    #[error("multipart request did not contain any file parts")]
+    NoFileParts,
+    /// A selected field arrived fewer times than its configured minimum.
+    #[error("field `{field}` requires at least {min_count} part(s)")]
+    RequiredFieldMissing {
+        /// Field name that did not reach its minimum count.
+        field: String,
+        /// Minimum number of parts required for this field.
+        min_count: usize,
+    },
+    /// A part body could not be deserialized into the requested type.
+    #[error("field `{field}` could not be deserialized: {message}")]
+    Deserialize {
+        /// Field name whose body failed to deserialize.
+        field: String,
+        /// Deserializer error message.
+        message: String,
+    },
     /// Multipart stream ended before a complete terminal boundary.
     #[error("multipart stream ended unexpectedly")]
     IncompleteStream,
+    /// The client did not deliver the next part within the read timeout.
+    #[error("multipart read timed out after {elapsed_ms} ms")]
+    Timeout {
+        /// Configured timeout that elapsed, in milliseconds.
+        elapsed_ms: u64,
+    },
+}
+
+impl MulterError {
+    /// Returns a compact JSON object describing this error.
+    ///
+    /// The object always contains `"error_code"`, `"message"`, `"field"` and
+    /// `"http_status"`; `"field"` is `null` when the error is not tied to a
+    /// specific multipart field.
+    pub fn to_json(&self) -> String {
+        let entries = self
+            .json_entries()
+            .iter()
+            .map(|(key, value)| format!("\"{key}\":{value}"))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", entries.join(","))
+    }
+
+    /// Returns the same object as [`MulterError::to_json`], indented for logging.
+    pub fn to_json_pretty(&self) -> String {
+        let entries = self
+            .json_entries()
+            .iter()
+            .map(|(key, value)| format!("  \"{key}\": {value}"))
+            .collect::<Vec<_>>();
+        format!("{{\n{}\n}}", entries.join(",\n"))
+    }
+
+    fn json_entries(&self) -> [(&'static str, String); 4] {
+        [
+            ("error_code", json_string(self.error_code())),
+            ("message", json_string(&self.to_string())),
+            (
+                "field",
+                self.field().map_or_else(|| "null".to_owned(), json_string),
+            ),
+            ("http_status", self.http_status().to_string()),
+        ]
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            Self::Config(_) => "config_error",
+            Self::Parse(_) => "parse_error",
+            Self::Storage(_) => "storage_error",
+            Self::UnexpectedField { .. } => "unexpected_field",
+            Self::FieldCountLimitExceeded { .. } => "field_count_limit_exceeded",
+            Self::FileSizeLimitExceeded { .. } => "file_size_limit_exceeded",
+            Self::FieldSizeLimitExceeded { .. } => "field_size_limit_exceeded",
+            Self::FilesLimitExceeded { .. } => "files_limit_exceeded",
+            Self::FieldsLimitExceeded { .. } => "fields_limit_exceeded",
+            Self::PartsLimitExceeded { .. } => "parts_limit_exceeded",
+            Self::BodySizeLimitExceeded { .. } => "body_size_limit_exceeded",
+            Self::MimeTypeNotAllowed { .. } => "mime_type_not_allowed",
+            Self::ExtensionNotAllowed { .. } => "extension_not_allowed",
+            Self::NoFileParts => "no_file_parts",
+            Self::RequiredFieldMissing { .. } => "required_field_missing",
+            Self::Deserialize { .. } => "deserialize_error",
+            Self::IncompleteStream => "incomplete_stream",
+            Self::Timeout { .. } => "timeout",
+        }
+    }
+
+    fn field(&self) -> Option<&str> {
+        match self {
+            Self::UnexpectedField { field }
+            | Self::FieldCountLimitExceeded { field, .. }
+            | Self::FileSizeLimitExceeded { field, .. }
+            | Self::FieldSizeLimitExceeded { field, .. }
+            | Self::MimeTypeNotAllowed { field, .. }
+            | Self::ExtensionNotAllowed { field, .. }
+            | Self::RequiredFieldMissing { field, .. }
+            | Self::Deserialize { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Returns the HTTP status code a server should answer with for this error.
+    ///
+    /// Size limit violations map to `413`, MIME rejections to `415`,
+    /// read timeouts to `408`, configuration and storage failures to `500`,
+    /// and every other parse, selector or limit error to `400`.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::Config(_) | Self::Storage(_) => 500,
+            Self::FileSizeLimitExceeded { .. }
+            | Self::FieldSizeLimitExceeded { .. }
+            | Self::BodySizeLimitExceeded { .. } => 413,
+            Self::MimeTypeNotAllowed { .. } => 415,
+            Self::Timeout { .. } => 408,
+            _ => 400,
+        }
+    }
+
+    /// Returns `true` when the error was caused by the request (a `4xx` status).
+    pub fn is_client_error(&self) -> bool {
+        (400..500).contains(&self.http_status())
+    }
+
+    /// Returns `true` when the error was caused by the server (a `5xx` status).
+    pub fn is_server_error(&self) -> bool {
+        (500..600).contains(&self.http_status())
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if u32::from(ch) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(ch))),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl From<std::io::Error> for MulterError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Storage(err.into())
+    }
 }
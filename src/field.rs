@@ -12,10 +12,10 @@ pub enum Field {
 impl Field {
     /// Creates a selector field descriptor.
     ///
-    /// This is a convenience alias for [`SelectedField::new`].
+    /// This is a convenience alias for [`SelectedField::file`].
     #[allow(clippy::new_ret_no_self)]
     pub fn new(name: impl Into<String>) -> SelectedField {
-        SelectedField::new(name)
+        SelectedField::file(name)
     }
 
     /// Creates a file field model for the provided name.
@@ -36,6 +36,26 @@ impl Field {
         self
     }
 
+    /// Requires the field to appear.
+    ///
+    /// File fields get a `min_count` and `max_count` of `1`, so exactly one
+    /// file is accepted. Text fields get a `min_count` of `1` and must appear
+    /// at least once with a non-empty value; repeats are not limited.
+    pub fn required(self) -> Self {
+        match self {
+            Self::File(field) => Self::File(field.required()),
+            Self::Text(field) => Self::Text(field.required()),
+        }
+    }
+
+    /// Allows the field to be omitted; file fields still accept at most one part.
+    pub fn optional(self) -> Self {
+        match self {
+            Self::File(field) => Self::File(field.optional()),
+            Self::Text(field) => Self::Text(field.optional()),
+        }
+    }
+
     /// Sets MIME patterns accepted for this file field.
     pub fn allowed_mime_types<I, M>(mut self, patterns: I) -> Self
     where
@@ -89,6 +109,8 @@ pub struct FileField {
     pub name: String,
     /// Maximum number of file parts accepted for this field.
     pub max_count: Option<usize>,
+    /// Minimum number of file parts required for this field.
+    pub min_count: usize,
     /// Allowed MIME patterns for this field.
     pub allowed_mime_types: Vec<String>,
 }
@@ -99,10 +121,25 @@ impl FileField {
         Self {
             name: name.into(),
             max_count: None,
+            min_count: 0,
             allowed_mime_types: Vec::new(),
         }
     }
 
+    /// Requires exactly one file part for this field.
+    pub fn required(mut self) -> Self {
+        self.min_count = 1;
+        self.max_count = Some(1);
+        self
+    }
+
+    /// Accepts at most one file part for this field, or none.
+    pub fn optional(mut self) -> Self {
+        self.min_count = 0;
+        self.max_count = Some(1);
+        self
+    }
+
     /// Sets the maximum number of file parts for this field.
     pub fn with_max_count(mut self, max_count: usize) -> Self {
         self.max_count = Some(max_count);
@@ -141,6 +178,8 @@ pub struct TextField {
     pub name: String,
     /// Maximum accepted text size in bytes.
     pub max_size: Option<u64>,
+    /// Minimum number of non-empty values required for this field.
+    pub min_count: usize,
 }
 
 impl TextField {
@@ -149,9 +188,22 @@ impl TextField {
         Self {
             name: name.into(),
             max_size: None,
+            min_count: 0,
         }
     }
 
+    /// Requires a non-empty value for this field.
+    pub fn required(mut self) -> Self {
+        self.min_count = 1;
+        self
+    }
+
+    /// Lets this field be omitted.
+    pub fn optional(mut self) -> Self {
+        self.min_count = 0;
+        self
+    }
+
     /// Sets the maximum text size in bytes for this field.
     pub fn with_max_size(mut self, max_size: u64) -> Self {
         self.max_size = Some(max_size);
@@ -184,8 +236,10 @@ impl From<FileField> for SelectedField {
             name: value.name,
             kind: SelectedFieldKind::File,
             max_count: value.max_count,
+            min_count: value.min_count,
             max_size: None,
             allowed_mime_types: value.allowed_mime_types,
+            allowed_extensions: Vec::new(),
         }
     }
 }
@@ -196,8 +250,10 @@ impl From<TextField> for SelectedField {
             name: value.name,
             kind: SelectedFieldKind::Text,
             max_count: None,
+            min_count: value.min_count,
             max_size: value.max_size,
             allowed_mime_types: Vec::new(),
+            allowed_extensions: Vec::new(),
         }
     }
 }
@@ -3,23 +3,40 @@
 use std::{future::Future, pin::Pin, sync::Arc};
 
 use bytes::Bytes;
-use futures::{Stream, StreamExt};
+use futures::{future::BoxFuture, Stream, StreamExt};
 use http_body_util::BodyExt;
 use hyper::{header, service::Service, Request, Response};
 
-use crate::{parser, Multer, MulterError, ParseError, StorageEngine};
+use crate::{parser, Multer, MulterError, ParseError, ProcessedMultipart, StorageEngine};
 
 /// Boxed error type used by [`MulterService`].
 pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
 /// Hyper body stream mapped into `multigear` chunk errors.
 pub type HyperBodyBoxStream =
     Pin<Box<dyn Stream<Item = Result<Bytes, MulterError>> + Send + 'static>>;
-
-/// Service wrapper that parses multipart requests and forwards stored files to a handler.
-#[derive(Clone)]
+/// Boxed handler accepted by [`MulterService`], for naming the service type.
+pub type MulterHandler<S, ResBody, E> = Box<
+    dyn Fn(
+            ProcessedMultipart<<S as StorageEngine>::Output>,
+        ) -> BoxFuture<'static, Result<Response<ResBody>, E>>
+        + Send
+        + Sync,
+>;
+
+/// Service wrapper that parses multipart requests and forwards the stored
+/// files and text fields to a handler.
 pub struct MulterService<S, H> {
     multer: Arc<Multer<S>>,
-    handler: H,
+    handler: Arc<H>,
+}
+
+impl<S, H> Clone for MulterService<S, H> {
+    fn clone(&self) -> Self {
+        Self {
+            multer: Arc::clone(&self.multer),
+            handler: Arc::clone(&self.handler),
+        }
+    }
 }
 
 impl<S, H> std::fmt::Debug for MulterService<S, H> {
@@ -34,7 +51,10 @@ impl<S, H> std::fmt::Debug for MulterService<S, H> {
 impl<S, H> MulterService<S, H> {
     /// Creates a new Hyper service wrapper around a configured multer instance.
     pub fn new(multer: Arc<Multer<S>>, handler: H) -> Self {
-        Self { multer, handler }
+        Self {
+            multer,
+            handler: Arc::new(handler),
+        }
     }
 }
 
@@ -43,7 +63,7 @@ where
     S: StorageEngine,
     ReqBody: hyper::body::Body<Data = Bytes> + Send + 'static,
     ReqBody::Error: std::error::Error + Send + Sync + 'static,
-    H: Fn(Vec<S::Output>) -> Fut + Clone + Send + Sync + 'static,
+    H: Fn(ProcessedMultipart<S::Output>) -> Fut + Send + Sync + 'static,
     Fut: Future<Output = Result<Response<ResBody>, E>> + Send + 'static,
     E: std::error::Error + Send + Sync + 'static,
 {
@@ -53,7 +73,7 @@ where
 
     fn call(&self, request: Request<ReqBody>) -> Self::Future {
         let multer = Arc::clone(&self.multer);
-        let handler = self.handler.clone();
+        let handler = Arc::clone(&self.handler);
 
         Box::pin(async move {
             let content_type = content_type_from_request(&request).map_err(into_box_error)?;
@@ -61,20 +81,12 @@ where
                 parser::extract_multipart_boundary(content_type).map_err(into_box_error)?;
             let body_stream = map_body_stream(request.into_body());
 
-            let mut multipart = multer
-                .parse_stream(body_stream, boundary)
+            let processed = multer
+                .parse_and_store(boundary, body_stream)
                 .await
                 .map_err(into_box_error)?;
 
-            let mut saved_files = Vec::new();
-            while let Some(part) = multipart.next_part().await.map_err(into_box_error)? {
-                if part.file_name().is_some() {
-                    let stored = multer.store(part).await.map_err(into_box_error)?;
-                    saved_files.push(stored);
-                }
-            }
-
-            handler(saved_files).await.map_err(into_box_error)
+            handler(processed).await.map_err(into_box_error)
         })
     }
 }
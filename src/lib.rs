@@ -3,6 +3,9 @@
 
 //! Core crate surface for `multigear`.
 
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use tokio::io::AsyncRead;
@@ -35,25 +38,43 @@ pub mod actix;
 pub mod axum;
 #[cfg(feature = "hyper")]
 pub mod hyper;
+#[cfg(feature = "rocket")]
+pub mod rocket;
+#[cfg(feature = "tower")]
+pub mod tower;
+#[cfg(feature = "warp")]
+pub mod warp;
 
-pub use builder::MulterBuilder;
-pub use config::{MulterConfig, SelectedField, SelectedFieldKind, Selector, UnknownFieldPolicy};
+pub use builder::{MulterBuilder, MultipartBodyBuilder};
+pub use config::{
+    AsyncCompletionHook, CompletionHook, ConfigDiff, MulterConfig, SelectedField,
+    SelectedFieldKind, Selector, SelectorFormBuilder, UnknownFieldAction, UnknownFieldPolicy,
+};
 pub use error::{ConfigError, MulterError, ParseError, StorageError};
 pub use field::{Field, FieldKind, FileField, TextField};
 pub use limits::Limits;
-pub use multipart::Multipart;
-pub use part::Part;
+pub use multipart::{
+    CollectedFilePart, CollectedMultipart, DrainStats, Multipart, MultipartState, OwnedMultipart,
+};
+pub use parser::{is_valid_multipart_boundary, validate_multipart_boundary, MAX_BOUNDARY_LEN};
+pub use part::{BufferedPart, Part};
 pub use selector::{SelectorAction, SelectorEngine};
 pub use storage::{
-    BoxStream, DiskStorage, DiskStorageBuilder, FileMeta, FilenameStrategy, MemoryStorage,
-    NoopStorage, StorageEngine, StoredFile,
+    BoxStream, BoxedStorage, BytesRef, CollisionPolicy, CreateMode, DigestAlgorithm, DiskStorage,
+    DiskStorageBuilder, DynStorageEngine, FileMeta, FilenameStrategy, HybridStorage, MemoryStorage,
+    NoopStorage, ProgressFn, StorageEngine, StorageEngineWithProgress, StoredFile, TeePolicy,
+    TeeStorageEngine, TotalSize,
 };
+#[cfg(feature = "s3")]
+pub use storage::{S3Storage, S3StorageBuilder};
 
 /// `AsyncRead` adapter stream used by [`Multer::parse_reader`].
 pub type AsyncReadStream<R> = futures::stream::Map<
     ReaderStream<R>,
     fn(Result<Bytes, std::io::Error>) -> Result<Bytes, MulterError>,
 >;
+/// Single-chunk stream used by [`Multer::parse_bytes`] and [`Multer::parse_vec`].
+pub type BytesStream = futures::stream::Once<futures::future::Ready<Result<Bytes, MulterError>>>;
 /// Generic body stream adapter used by [`Multer::parse_stream`].
 pub type MappedBodyStream<T, E> =
     futures::stream::Map<T, fn(Result<Bytes, E>) -> Result<Bytes, MulterError>>;
@@ -72,6 +93,57 @@ pub struct ProcessedMultipart<O = StoredFile> {
     pub text_fields: Vec<(String, String)>,
 }
 
+impl<O> ProcessedMultipart<O> {
+    /// Returns the first value of the text field `name`.
+    pub fn text(&self, name: &str) -> Option<&str> {
+        self.text_fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns every value of the text field `name` in arrival order.
+    pub fn all_text<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.text_fields
+            .iter()
+            .filter(move |(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the combined length in bytes of every text field value.
+    pub fn total_text_bytes(&self) -> usize {
+        self.text_fields.iter().map(|(_, value)| value.len()).sum()
+    }
+
+    /// Returns the number of stored files plus collected text fields.
+    pub fn part_count(&self) -> usize {
+        self.stored_files.len() + self.text_fields.len()
+    }
+}
+
+impl<O: TotalSize> ProcessedMultipart<O> {
+    /// Returns the combined size in bytes of every stored file.
+    pub fn total_file_bytes(&self) -> u64 {
+        self.stored_files.iter().map(TotalSize::total_size).sum()
+    }
+}
+
+impl ProcessedMultipart<StoredFile> {
+    /// Returns the first file stored for the field `name`.
+    pub fn file(&self, name: &str) -> Option<&StoredFile> {
+        self.stored_files
+            .iter()
+            .find(|file| file.field_name == name)
+    }
+
+    /// Returns every file stored for the field `name` in arrival order.
+    pub fn all_files<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a StoredFile> + 'a {
+        self.stored_files
+            .iter()
+            .filter(move |file| file.field_name == name)
+    }
+}
+
 impl<O> Default for ProcessedMultipart<O> {
     fn default() -> Self {
         Self {
@@ -86,6 +158,8 @@ impl<O> Default for ProcessedMultipart<O> {
 pub struct Multer<S = NoopStorage> {
     config: MulterConfig,
     storage: S,
+    on_complete: Option<CompletionHook<S>>,
+    on_complete_async: Option<AsyncCompletionHook<S>>,
 }
 
 impl<S> Multer<S> {
@@ -101,13 +175,20 @@ impl<S> Multer<S> {
         Self {
             config: MulterConfig::default(),
             storage,
+            on_complete: None,
+            on_complete_async: None,
         }
     }
 
     /// Creates a new multer instance with explicit validated configuration.
     pub fn with_config(storage: S, config: MulterConfig) -> Result<Self, ConfigError> {
         config.validate()?;
-        Ok(Self { config, storage })
+        Ok(Self {
+            config,
+            storage,
+            on_complete: None,
+            on_complete_async: None,
+        })
     }
 
     /// Returns an immutable reference to the active configuration.
@@ -119,6 +200,44 @@ impl<S> Multer<S> {
     pub fn storage(&self) -> &S {
         &self.storage
     }
+
+    /// Returns a best-effort size estimate for `part`, for progress reporting.
+    ///
+    /// The part's own `Content-Length` ([`Part::size_hint`]) is used when
+    /// present. Otherwise, when the selector and limits allow at most one
+    /// file, a file part falls back to the request `Content-Length`
+    /// ([`Part::request_content_length`]). That fallback is a heuristic: it
+    /// overestimates by the size of boundaries, headers and any text fields,
+    /// so use it for progress bars rather than limits.
+    pub fn part_size_hint(&self, part: &Part<'_>) -> Option<u64> {
+        if let Some(size_hint) = part.size_hint() {
+            return Some(size_hint);
+        }
+        if part.file_name().is_none() || self.max_expected_files() != Some(1) {
+            return None;
+        }
+        part.request_content_length()
+    }
+
+    /// Returns the most file parts the selector and limits can accept.
+    fn max_expected_files(&self) -> Option<usize> {
+        let selector_max = match &self.config.selector {
+            Selector::Single { .. } => Some(1),
+            Selector::Array { max_count, .. } => *max_count,
+            Selector::Fields(fields) => fields
+                .iter()
+                .filter(|field| field.kind == SelectedFieldKind::File)
+                .map(|field| field.max_count)
+                .sum(),
+            Selector::Regex { max_count, .. } => *max_count,
+            Selector::None => Some(0),
+            Selector::Any => None,
+        };
+        selector_max
+            .into_iter()
+            .chain(self.config.limits.max_files)
+            .min()
+    }
 }
 
 impl<S> Multer<S>
@@ -126,24 +245,99 @@ where
     S: StorageEngine,
 {
     /// Stores a file part through the configured storage backend.
-    pub async fn store(&self, mut part: Part<'_>) -> Result<S::Output, MulterError> {
+    pub async fn store(&self, part: Part<'_>) -> Result<S::Output, MulterError> {
+        self.store_part(part, None).await
+    }
+
+    /// Stores a file part, forwarding `extra` metadata to
+    /// [`StorageEngine::store_full`].
+    ///
+    /// Use this to hand the backend values the part metadata does not carry,
+    /// such as custom part or request headers.
+    pub async fn store_with_meta(
+        &self,
+        part: Part<'_>,
+        extra: HashMap<String, String>,
+    ) -> Result<S::Output, MulterError> {
+        self.store_part(part, Some(&extra)).await
+    }
+
+    async fn store_part(
+        &self,
+        mut part: Part<'_>,
+        extra: Option<&HashMap<String, String>>,
+    ) -> Result<S::Output, MulterError> {
         let field_name = part.field_name().to_owned();
         let file_name = part.file_name().map(ToOwned::to_owned);
-        let content_type = part.content_type().to_string();
-        let stream = part.stream();
+        let mut content_type = part.content_type().to_string();
+        let size_hint = part.size_hint();
+        #[cfg(feature = "tracing")]
+        let part_span = part.span().clone();
+        // Storage engines flatten stream errors into their own error type, so
+        // the first parser error is recorded here and reported unchanged.
+        let stream_error = Arc::new(Mutex::new(None::<MulterError>));
+        let mut stream: BoxStream<'_, Result<Bytes, MulterError>> = Box::pin({
+            let stream_error = Arc::clone(&stream_error);
+            part.stream().inspect(move |item| {
+                if let Err(err) = item {
+                    let mut slot = stream_error
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    slot.get_or_insert_with(|| err.clone());
+                }
+            })
+        });
+
+        if self.storage.sniffs_mime() && storage::is_generic_mime(&content_type) {
+            let (sniffed, replayed) = storage::sniff_mime(stream).await;
+            stream = replayed;
+            if let Some(sniffed) = sniffed {
+                content_type = sniffed.to_string();
+            }
+            self.check_sniffed_mime(&field_name, &content_type)?;
+        }
 
         #[cfg(feature = "tracing")]
-        tracing::debug!(
-            field_name = field_name.as_str(),
-            file_name = file_name.as_deref().unwrap_or("<none>"),
-            content_type = content_type.as_str(),
-            "multer: dispatching part to storage engine"
+        part_span.in_scope(|| {
+            tracing::debug!(
+                field_name = field_name.as_str(),
+                file_name = file_name.as_deref().unwrap_or("<none>"),
+                content_type = content_type.as_str(),
+                "multer: dispatching part to storage engine"
+            )
+        });
+
+        let stored = storage::store_with_extra(
+            &self.storage,
+            &field_name,
+            file_name.as_deref(),
+            &content_type,
+            size_hint,
+            extra,
+            stream,
+        );
+        #[cfg(feature = "tracing")]
+        let stored = tracing::Instrument::instrument(
+            stored,
+            optional_span(self.config.emit_spans, || {
+                part_span.in_scope(|| {
+                    tracing::info_span!(
+                        "multer.store",
+                        field_name = field_name.as_str(),
+                        file_name = file_name.as_deref().unwrap_or("<none>"),
+                        content_type = content_type.as_str(),
+                    )
+                })
+            }),
         );
 
-        self.storage
-            .store(&field_name, file_name.as_deref(), &content_type, stream)
-            .await
-            .map_err(|err| MulterError::Storage(StorageError::new(err.to_string())))
+        stored.await.map_err(|err| {
+            stream_error
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .take()
+                .unwrap_or_else(|| MulterError::Storage(StorageError::new(err.to_string())))
+        })
     }
 
     /// Creates a configured multipart parser from a raw multipart boundary.
@@ -155,7 +349,10 @@ where
     where
         T: Stream<Item = Result<Bytes, MulterError>> + Unpin,
     {
-        Multipart::with_config(boundary, stream, self.config.clone())
+        Ok(
+            Multipart::with_config(boundary, stream, self.config.clone())?
+                .defer_generic_mime(self.storage.sniffs_mime()),
+        )
     }
 
     /// Creates a configured multipart parser from an HTTP `Content-Type` value.
@@ -213,8 +410,51 @@ where
         self.multipart_from_boundary(boundary, map_async_read_stream(reader))
     }
 
+    /// Creates a configured multipart parser over a body already held in memory.
+    ///
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use multigear::{MemoryStorage, Multer};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let multer = Multer::new(MemoryStorage::new());
+    /// let body = Bytes::from_static(
+    ///     b"--BOUND\r\nContent-Disposition: form-data; name=\"field\"\r\n\r\nvalue\r\n--BOUND--\r\n",
+    /// );
+    ///
+    /// let mut multipart = multer.parse_bytes("BOUND", body).expect("parse bytes");
+    /// let mut part = multipart.next_part().await.expect("next part").expect("part");
+    /// assert_eq!(part.text().await.expect("text"), "value");
+    /// # }
+    /// ```
+    pub fn parse_bytes(
+        &self,
+        boundary: impl Into<String>,
+        body: Bytes,
+    ) -> Result<Multipart<BytesStream>, MulterError> {
+        self.multipart_from_boundary(
+            boundary,
+            futures::stream::once(futures::future::ready(Ok(body))),
+        )
+    }
+
+    /// Like [`Multer::parse_bytes`], taking the body as a `Vec<u8>`.
+    pub fn parse_vec(
+        &self,
+        boundary: impl Into<String>,
+        body: Vec<u8>,
+    ) -> Result<Multipart<BytesStream>, MulterError> {
+        self.parse_bytes(boundary, Bytes::from(body))
+    }
+
     /// Parses multipart input and stores all file parts using the active storage backend.
     ///
+    /// Configured completion hooks run after every part has been processed and
+    /// before the output is returned. When a part or a required-field check
+    /// fails, files already stored for the request are handed to
+    /// [`StorageEngine::discard`] before the error is returned.
+    ///
     /// ```rust
     /// use bytes::Bytes;
     /// use futures::stream;
@@ -250,9 +490,57 @@ where
     where
         T: Stream<Item = Result<Bytes, MulterError>> + Unpin + Send,
     {
-        let mut multipart = self.multipart_from_boundary(boundary, stream)?;
-        let mut out = ProcessedMultipart::default();
+        let multipart = self.multipart_from_boundary(boundary, stream)?;
+        #[cfg(feature = "tracing")]
+        let span = optional_span(self.config.emit_spans, || {
+            tracing::info_span!("multer.parse_and_store", boundary = multipart.boundary())
+        });
+
+        let processed = self.store_all(multipart);
+        #[cfg(feature = "tracing")]
+        let processed = tracing::Instrument::instrument(processed, span);
+        processed.await
+    }
+
+    async fn store_all<T>(
+        &self,
+        mut multipart: Multipart<T>,
+    ) -> Result<ProcessedMultipart<S::Output>, MulterError>
+    where
+        T: Stream<Item = Result<Bytes, MulterError>> + Unpin + Send,
+    {
+        let capacity = self.config.field_count_hint.unwrap_or_default();
+        let mut out = ProcessedMultipart {
+            stored_files: Vec::with_capacity(capacity),
+            text_fields: Vec::with_capacity(capacity),
+        };
+
+        if let Err(err) = self.store_parts(&mut multipart, &mut out).await {
+            self.discard_stored(std::mem::take(&mut out.stored_files))
+                .await;
+            return Err(err);
+        }
 
+        if let Some(hook) = &self.on_complete {
+            hook.call(&out);
+        }
+        if let Some(hook) = &self.on_complete_async {
+            let pending = hook.call(&out);
+            pending.await;
+        }
+        Ok(out)
+    }
+
+    /// Stores and collects every remaining part, then runs the final
+    /// request-level checks.
+    async fn store_parts<T>(
+        &self,
+        multipart: &mut Multipart<T>,
+        out: &mut ProcessedMultipart<S::Output>,
+    ) -> Result<(), MulterError>
+    where
+        T: Stream<Item = Result<Bytes, MulterError>> + Unpin + Send,
+    {
         while let Some(mut part) = multipart.next_part().await? {
             if part.file_name().is_some() {
                 #[cfg(feature = "tracing")]
@@ -271,7 +559,56 @@ where
             }
         }
 
-        Ok(out)
+        if self.config.require_at_least_one_file && out.stored_files.is_empty() {
+            return Err(MulterError::NoFileParts);
+        }
+        multipart.validate_required_fields()
+    }
+
+    /// Discards files stored for a request that failed, keeping the original error.
+    async fn discard_stored(&self, stored_files: Vec<S::Output>) {
+        for stored in stored_files {
+            if let Err(err) = self.storage.discard(stored).await {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %err, "multer: failed to discard stored file");
+                #[cfg(not(feature = "tracing"))]
+                let _ = err;
+            }
+        }
+    }
+
+    /// Applies the MIME allowlists that [`Multipart`] deferred for a sniffed part.
+    fn check_sniffed_mime(&self, field_name: &str, content_type: &str) -> Result<(), MulterError> {
+        let rejected = || MulterError::MimeTypeNotAllowed {
+            field: field_name.to_owned(),
+            mime: content_type.to_owned(),
+        };
+        let mime = content_type.parse::<mime::Mime>().map_err(|_| rejected())?;
+
+        if let Selector::Fields(fields) = &self.config.selector {
+            let patterns = fields
+                .iter()
+                .find(|field| field.name == field_name)
+                .map(|field| field.allowed_mime_types.as_slice())
+                .unwrap_or_default();
+            if !patterns.is_empty() && !multipart::mime_matches_any(&mime, patterns) {
+                return Err(rejected());
+            }
+        }
+        if !self.config.limits.is_mime_allowed(&mime) {
+            return Err(rejected());
+        }
+        Ok(())
+    }
+}
+
+/// Returns `make()` when spans are enabled and a disabled span otherwise.
+#[cfg(feature = "tracing")]
+pub(crate) fn optional_span(enabled: bool, make: impl FnOnce() -> tracing::Span) -> tracing::Span {
+    if enabled {
+        make()
+    } else {
+        tracing::Span::none()
     }
 }
 
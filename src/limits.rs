@@ -1,3 +1,5 @@
+use std::{cmp::Ordering, time::Duration};
+
 /// Request and field limits enforced during multipart parsing.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -10,9 +12,25 @@ pub struct Limits {
     pub max_field_size: Option<u64>,
     /// Maximum number of text fields in a request.
     pub max_fields: Option<usize>,
+    /// Maximum total number of parts in a request, files and text combined.
+    ///
+    /// Every part whose headers are parsed counts, including parts the
+    /// selector ignores.
+    pub max_parts: Option<usize>,
     /// Maximum request body size in bytes.
     pub max_body_size: Option<u64>,
+    /// Maximum size in bytes of a single part's header block.
+    ///
+    /// Bounds how much the parser buffers while looking for the blank line
+    /// that ends a part's headers.
+    pub max_header_size: Option<usize>,
+    /// Maximum time [`Multipart::next_part`](crate::Multipart::next_part) waits
+    /// for the next part, including draining the unread body of the previous one.
+    /// When `Multipart` is consumed as a `Stream`, it bounds each buffered
+    /// part, body included.
+    pub part_read_timeout: Option<Duration>,
     /// Allowed MIME patterns (for example: `image/png`, `image/*`).
+    #[cfg_attr(feature = "serde", serde(default))]
     pub allowed_mime_types: Vec<String>,
 }
 
@@ -22,6 +40,126 @@ impl Limits {
         Self::default()
     }
 
+    /// Layers `other` on top of `self`.
+    ///
+    /// Each limit set to `Some` in `other` replaces the value in `self`; a
+    /// non-empty MIME allowlist in `other` replaces the one in `self`.
+    ///
+    /// ```rust
+    /// use multigear::Limits;
+    ///
+    /// let base = Limits {
+    ///     max_file_size: Some(1024),
+    ///     max_files: Some(4),
+    ///     ..Limits::default()
+    /// };
+    /// let merged = base.merge(Limits {
+    ///     max_files: Some(1),
+    ///     ..Limits::default()
+    /// });
+    /// assert_eq!(merged.max_file_size, Some(1024));
+    /// assert_eq!(merged.max_files, Some(1));
+    /// ```
+    pub fn merge(self, other: Limits) -> Limits {
+        Limits {
+            max_file_size: other.max_file_size.or(self.max_file_size),
+            max_files: other.max_files.or(self.max_files),
+            max_field_size: other.max_field_size.or(self.max_field_size),
+            max_fields: other.max_fields.or(self.max_fields),
+            max_parts: other.max_parts.or(self.max_parts),
+            max_body_size: other.max_body_size.or(self.max_body_size),
+            max_header_size: other.max_header_size.or(self.max_header_size),
+            part_read_timeout: other.part_read_timeout.or(self.part_read_timeout),
+            allowed_mime_types: if other.allowed_mime_types.is_empty() {
+                self.allowed_mime_types
+            } else {
+                other.allowed_mime_types
+            },
+        }
+    }
+
+    /// Returns `true` when `self` is strictly tighter than `other`.
+    ///
+    /// Only the size, count and timeout limits set to `Some` in both values
+    /// are compared, and `self` must be lower on every one of them. Returns
+    /// `false` when no limit is set on both sides. The MIME allowlist is not
+    /// compared.
+    ///
+    /// ```rust
+    /// use multigear::Limits;
+    ///
+    /// let route = Limits {
+    ///     max_file_size: Some(1024),
+    ///     max_files: Some(1),
+    ///     ..Limits::default()
+    /// };
+    /// let global = Limits {
+    ///     max_file_size: Some(4096),
+    ///     max_files: Some(8),
+    ///     max_body_size: Some(16 * 1024),
+    ///     ..Limits::default()
+    /// };
+    /// assert!(route.is_stricter_than(&global));
+    /// assert!(!global.is_stricter_than(&route));
+    /// ```
+    pub fn is_stricter_than(&self, other: &Limits) -> bool {
+        let comparisons = [
+            compare_limit(&self.max_file_size, &other.max_file_size),
+            compare_limit(&self.max_files, &other.max_files),
+            compare_limit(&self.max_field_size, &other.max_field_size),
+            compare_limit(&self.max_fields, &other.max_fields),
+            compare_limit(&self.max_parts, &other.max_parts),
+            compare_limit(&self.max_body_size, &other.max_body_size),
+            compare_limit(&self.max_header_size, &other.max_header_size),
+            compare_limit(&self.part_read_timeout, &other.part_read_timeout),
+        ];
+        let mut compared = comparisons.into_iter().flatten().peekable();
+        compared.peek().is_some() && compared.all(|ordering| ordering == Ordering::Less)
+    }
+
+    /// Combines `a` and `b`, keeping the stricter value of every limit.
+    ///
+    /// Limits set on both sides take the minimum; a limit set on only one
+    /// side is kept. The MIME allowlists are merged into their union, in
+    /// order and without duplicates.
+    ///
+    /// ```rust
+    /// use multigear::Limits;
+    ///
+    /// let combined = Limits::min(
+    ///     Limits {
+    ///         max_file_size: Some(4096),
+    ///         max_files: Some(2),
+    ///         ..Limits::default()
+    ///     },
+    ///     Limits {
+    ///         max_file_size: Some(1024),
+    ///         ..Limits::default()
+    ///     },
+    /// );
+    /// assert_eq!(combined.max_file_size, Some(1024));
+    /// assert_eq!(combined.max_files, Some(2));
+    /// ```
+    pub fn min(a: Limits, b: Limits) -> Limits {
+        let mut allowed_mime_types = a.allowed_mime_types;
+        for pattern in b.allowed_mime_types {
+            if !allowed_mime_types.contains(&pattern) {
+                allowed_mime_types.push(pattern);
+            }
+        }
+        Limits {
+            max_file_size: stricter_limit(a.max_file_size, b.max_file_size),
+            max_files: stricter_limit(a.max_files, b.max_files),
+            max_field_size: stricter_limit(a.max_field_size, b.max_field_size),
+            max_fields: stricter_limit(a.max_fields, b.max_fields),
+            max_parts: stricter_limit(a.max_parts, b.max_parts),
+            max_body_size: stricter_limit(a.max_body_size, b.max_body_size),
+            max_header_size: stricter_limit(a.max_header_size, b.max_header_size),
+            part_read_timeout: stricter_limit(a.part_read_timeout, b.part_read_timeout),
+            allowed_mime_types,
+        }
+    }
+
     /// Returns `true` when `mime` is allowed by the configured allowlist.
     ///
     /// When no allowlist is configured, all MIME types are accepted.
@@ -48,6 +186,17 @@ impl Limits {
     }
 }
 
+fn compare_limit<T: Ord>(left: &Option<T>, right: &Option<T>) -> Option<Ordering> {
+    Some(left.as_ref()?.cmp(right.as_ref()?))
+}
+
+fn stricter_limit<T: Ord>(left: Option<T>, right: Option<T>) -> Option<T> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(left.min(right)),
+        (left, right) => left.or(right),
+    }
+}
+
 fn mime_matches_pattern(mime: &mime::Mime, pattern: &str) -> bool {
     if let Some((kind, subtype)) = pattern.split_once('/') {
         if subtype == "*" {
@@ -1,23 +1,151 @@
-use std::task::{Context, Poll};
+use std::{
+    fmt,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
 
-use bytes::Bytes;
-use futures::{future::poll_fn, Stream};
+use bytes::{Bytes, BytesMut};
+use futures::{future::poll_fn, Future, Stream};
+use tokio::time::Sleep;
 
 use crate::{
-    parser::stream::{MultipartStream, StreamLimits},
-    part::PartBodyReader,
+    parser::{
+        extract_multipart_boundary,
+        headers::ParsedPartHeaders,
+        stream::{MultipartStream, StreamLimits},
+    },
+    part::{BodyDecoder, PartBodyReader},
     selector::{SelectorAction, SelectorEngine},
-    Limits, MulterConfig, MulterError, ParseError, Part, Selector, UnknownFieldPolicy,
+    storage::is_generic_mime,
+    BufferedPart, Limits, MulterConfig, MulterError, ParseError, Part, Selector,
+    UnknownFieldPolicy,
 };
 
+/// Summary returned by [`Multipart::drain_all`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DrainStats {
+    /// Number of parts whose bodies were discarded, including a partially read part.
+    pub parts_drained: usize,
+    /// Total number of part body bytes discarded.
+    pub bytes_discarded: u64,
+}
+
+/// Buffered file part collected by [`Multipart::parse_all`].
+pub type CollectedFilePart = BufferedPart;
+
+/// Every part of a multipart body, returned by [`Multipart::parse_all`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CollectedMultipart {
+    /// Text field values in arrival order.
+    pub text_fields: Vec<(String, String)>,
+    /// File parts in arrival order, with their bodies buffered.
+    pub file_parts: Vec<CollectedFilePart>,
+}
+
+impl CollectedMultipart {
+    /// Returns the first value of the text field `name`.
+    pub fn text(&self, name: &str) -> Option<&str> {
+        self.text_fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns every value of the text field `name` in arrival order.
+    pub fn all_text<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.text_fields
+            .iter()
+            .filter(move |(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the first file uploaded under the field `name`.
+    pub fn file(&self, name: &str) -> Option<&CollectedFilePart> {
+        self.file_parts
+            .iter()
+            .find(|part| part.field_name() == name)
+    }
+
+    /// Returns every file uploaded under the field `name` in arrival order.
+    pub fn all_files<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a CollectedFilePart> + 'a {
+        self.file_parts
+            .iter()
+            .filter(move |part| part.field_name() == name)
+    }
+}
+
+/// Coarse parser state reported by [`Multipart::state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultipartState {
+    /// Waiting for the next part headers.
+    Active,
+    /// Positioned inside a part body that has not been fully read.
+    Draining,
+    /// The terminal boundary has been reached.
+    Done,
+    /// Parsing stopped because of an error.
+    Failed,
+}
+
 /// High-level multipart stream abstraction.
-#[derive(Debug)]
+///
+/// Parts can be pulled one at a time with [`Multipart::next_part`], or the value
+/// can be consumed as a [`Stream`] of [`BufferedPart`]s. Mixing both styles on
+/// the same instance discards any part the stream was buffering.
+///
+/// # Cancellation
+///
+/// Dropping a `Multipart`, or a future returned by one of its methods, at any
+/// point is safe. The parser spawns no task of its own, and the chunk source
+/// and any buffered bytes are released with it. The unread remainder of the
+/// body is simply not consumed, so [`Multipart::is_complete`] tells whether
+/// the terminal boundary was reached before the value was given up.
+///
+/// Parser progress lives in the `Multipart` itself, so after a dropped
+/// [`Multipart::next_part`] or [`Multipart::peek_next_headers`] future the
+/// value stays usable and the next call resumes where the last one stopped.
+/// Body bytes already pulled by a dropped [`Part`] read, such as
+/// [`Part::text`], are lost; the rest of that part is skipped by the next
+/// `next_part` call. A [`Limits::part_read_timeout`] expiry fails the stream
+/// for good.
+///
+/// The chunk source may still own a task. The actix helpers forward the
+/// payload from a task spawned with `actix_web::rt::spawn`, which exits once
+/// the payload ends or the next chunk finds the `Multipart` dropped.
 pub struct Multipart<S> {
     inner: MultipartStream<S>,
     selector: SelectorEngine,
     limits: Limits,
     file_count: usize,
     field_count: usize,
+    part_count: usize,
+    parts_yielded: usize,
+    #[cfg(feature = "tracing")]
+    emit_spans: bool,
+    defer_generic_mime: bool,
+    buffering: Option<BufferingPart>,
+    stream_deadline: Option<Pin<Box<Sleep>>>,
+    peeked: Option<ParsedPartHeaders>,
+    pending_text_field: Option<String>,
+    request_content_length: Option<u64>,
+    #[cfg(feature = "tracing")]
+    part_span: tracing::Span,
+}
+
+impl<S> fmt::Debug for Multipart<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Multipart")
+            .field("boundary", &self.boundary())
+            .field("state", &self.state())
+            .field("parts_yielded", &self.parts_yielded)
+            .field("bytes_received", &self.bytes_received())
+            .field("selector", &self.selector)
+            .finish()
+    }
 }
 
 impl<S> Multipart<S> {
@@ -29,9 +157,72 @@ impl<S> Multipart<S> {
             limits: Limits::default(),
             file_count: 0,
             field_count: 0,
+            part_count: 0,
+            parts_yielded: 0,
+            #[cfg(feature = "tracing")]
+            emit_spans: false,
+            defer_generic_mime: false,
+            buffering: None,
+            stream_deadline: None,
+            peeked: None,
+            pending_text_field: None,
+            request_content_length: None,
+            #[cfg(feature = "tracing")]
+            part_span: tracing::Span::none(),
         })
     }
 
+    /// Alias for [`Multipart::new`] that names the boundary argument.
+    ///
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use futures::stream;
+    /// use multigear::{MulterError, Multipart};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let body = b"--BOUND\r\nContent-Disposition: form-data; name=\"field\"\r\n\r\nvalue\r\n--BOUND--\r\n";
+    /// let stream = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(body))]);
+    ///
+    /// let mut multipart = Multipart::with_boundary("BOUND", stream).expect("valid boundary");
+    /// let mut part = multipart.next_part().await.expect("next part").expect("part");
+    /// assert_eq!(part.text().await.expect("text"), "value");
+    /// # }
+    /// ```
+    pub fn with_boundary(boundary: impl Into<String>, stream: S) -> Result<Self, ParseError> {
+        Self::new(boundary, stream)
+    }
+
+    /// Creates a multipart stream from a raw `Content-Type` header value.
+    ///
+    /// Fails when the value is not `multipart/form-data` with a valid boundary.
+    ///
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use futures::stream;
+    /// use multigear::{MulterError, Multipart};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let body = b"--BOUND\r\nContent-Disposition: form-data; name=\"field\"\r\n\r\nvalue\r\n--BOUND--\r\n";
+    /// let stream = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(body))]);
+    ///
+    /// let mut multipart =
+    ///     Multipart::from_content_type("multipart/form-data; boundary=BOUND", stream)
+    ///         .expect("valid content type");
+    /// assert_eq!(multipart.boundary(), "BOUND");
+    /// let mut part = multipart.next_part().await.expect("next part").expect("part");
+    /// assert_eq!(part.text().await.expect("text"), "value");
+    ///
+    /// let empty = stream::empty::<Result<Bytes, MulterError>>();
+    /// assert!(Multipart::from_content_type("application/json", empty).is_err());
+    /// # }
+    /// ```
+    pub fn from_content_type(content_type: &str, stream: S) -> Result<Self, MulterError> {
+        let boundary = extract_multipart_boundary(content_type)?;
+        Ok(Self::new(boundary, stream)?)
+    }
+
     /// Creates a multipart stream with explicit selector configuration.
     pub fn with_config(
         boundary: impl Into<String>,
@@ -43,6 +234,7 @@ impl<S> Multipart<S> {
             max_file_size: config.limits.max_file_size,
             max_field_size: config.limits.max_field_size,
             max_body_size: config.limits.max_body_size,
+            max_header_size: config.limits.max_header_size,
         };
         let selector = SelectorEngine::new(config.selector, config.unknown_field_policy);
         Ok(Self {
@@ -51,28 +243,321 @@ impl<S> Multipart<S> {
             limits: config.limits,
             file_count: 0,
             field_count: 0,
+            part_count: 0,
+            parts_yielded: 0,
+            #[cfg(feature = "tracing")]
+            emit_spans: config.emit_spans,
+            defer_generic_mime: false,
+            buffering: None,
+            stream_deadline: None,
+            peeked: None,
+            pending_text_field: None,
+            request_content_length: None,
+            #[cfg(feature = "tracing")]
+            part_span: tracing::Span::none(),
         })
     }
+
+    /// Skips MIME allowlist checks for `application/octet-stream` file parts.
+    ///
+    /// Used when the storage backend sniffs the real type, in which case
+    /// [`Multer::store`](crate::Multer::store) checks the detected type instead.
+    pub(crate) fn defer_generic_mime(mut self, enabled: bool) -> Self {
+        self.defer_generic_mime = enabled;
+        self
+    }
+
+    /// Records the `Content-Length` of the request carrying this body.
+    ///
+    /// Every yielded [`Part`] reports it through
+    /// [`Part::request_content_length`], which
+    /// [`Multer::part_size_hint`](crate::Multer::part_size_hint) falls back to.
+    pub fn with_request_content_length(mut self, content_length: Option<u64>) -> Self {
+        self.request_content_length = content_length;
+        self
+    }
+
+    /// Converts the parser into an owned stream of fully buffered parts.
+    ///
+    /// Each yielded [`BufferedPart`] owns its body, so items are `'static` and
+    /// can be moved into other tasks. Memory per part is bounded by
+    /// [`Limits::max_file_size`] for files and [`Limits::max_field_size`] for
+    /// text fields; leave those unset only for trusted input.
+    pub fn into_stream(self) -> OwnedMultipart<S> {
+        OwnedMultipart { inner: self }
+    }
+
+    /// Checks that every field with a `min_count` appeared often enough.
+    ///
+    /// Text fields only count when their body was not empty. MIME and
+    /// extension constraints are enforced as parts arrive, so a stream read
+    /// to the end has already satisfied them. This is a no-op for selectors
+    /// other than [`Selector::Fields`].
+    ///
+    /// Call this after [`Multipart::next_part`] returned `None`; earlier calls
+    /// only see the parts yielded so far.
+    pub fn validate_required_fields(&self) -> Result<(), MulterError> {
+        self.selector.validate_required_fields()
+    }
+
+    /// Returns the multipart boundary used to split this stream.
+    pub fn boundary(&self) -> &str {
+        self.inner.boundary()
+    }
+
+    /// Returns a coarse summary of the parser state.
+    pub fn state(&self) -> MultipartState {
+        if self.inner.is_failed() {
+            MultipartState::Failed
+        } else if self.inner.is_finished() {
+            MultipartState::Done
+        } else if self.inner.is_reading_part_body() {
+            MultipartState::Draining
+        } else {
+            MultipartState::Active
+        }
+    }
+
+    /// Returns the number of parts returned by [`Multipart::next_part`] so far.
+    pub fn parts_yielded(&self) -> usize {
+        self.parts_yielded
+    }
+
+    /// Returns the total number of raw body bytes received, including boundaries and headers.
+    pub fn bytes_received(&self) -> u64 {
+        self.inner.received_body_bytes()
+    }
+
+    /// Returns the configured `max_body_size`, for turning
+    /// [`Multipart::bytes_received`] into a progress percentage.
+    pub fn body_limit(&self) -> Option<u64> {
+        self.limits.max_body_size
+    }
+
+    /// Returns the number of parts whose headers have been parsed, including ignored parts.
+    pub fn parts_started(&self) -> usize {
+        self.inner.parts_started()
+    }
+
+    /// Returns the number of parts whose bodies have been fully consumed.
+    pub fn parts_completed(&self) -> usize {
+        self.inner.parts_completed()
+    }
+
+    /// Returns `true` once the terminal boundary has been consumed.
+    ///
+    /// Failed streams are never complete.
+    pub fn is_complete(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    /// Returns an upper bound on the number of parts still to come.
+    ///
+    /// This is `0` once the stream is complete and `usize::MAX` otherwise,
+    /// since counting the remaining parts requires parsing them.
+    pub fn remaining_parts_hint(&self) -> usize {
+        if self.is_complete() {
+            0
+        } else {
+            usize::MAX
+        }
+    }
 }
 
 impl<S> Multipart<S>
 where
     S: Stream<Item = Result<Bytes, MulterError>> + Unpin + Send,
 {
+    /// Consumes and discards all remaining parts up to the terminal boundary.
+    ///
+    /// This leaves the underlying body fully read, which is useful when a
+    /// handler aborts early but the connection should stay reusable. A part
+    /// that was only partially read is drained first and counted as well.
+    pub async fn drain_all(&mut self) -> Result<DrainStats, MulterError> {
+        let mut stats = DrainStats::default();
+
+        if self.inner.is_reading_part_body() {
+            while let Some(chunk) = poll_fn(|cx| self.inner.poll_next_part_chunk(cx)).await? {
+                stats.bytes_discarded = stats.bytes_discarded.saturating_add(chunk.len() as u64);
+            }
+            stats.parts_drained += 1;
+        }
+        self.peeked = None;
+
+        while let Some(mut part) = self.next_part().await? {
+            let discarded = part.drain().await?;
+            stats.bytes_discarded = stats.bytes_discarded.saturating_add(discarded);
+            stats.parts_drained += 1;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            parts_drained = stats.parts_drained,
+            bytes_discarded = stats.bytes_discarded,
+            "multipart: drained remaining parts"
+        );
+        Ok(stats)
+    }
+
+    /// Reads every remaining part into memory.
+    ///
+    /// Parts with a filename become [`CollectedFilePart`]s and all others are
+    /// decoded as UTF-8 text. Required field minimums are checked once the
+    /// body ends. Memory use is bounded only by the configured [`Limits`];
+    /// prefer [`Multipart::next_part`] for large uploads.
+    pub async fn parse_all(&mut self) -> Result<CollectedMultipart, MulterError> {
+        let mut collected = CollectedMultipart::default();
+        while let Some(mut part) = self.next_part().await? {
+            if part.file_name().is_some() {
+                collected.file_parts.push(part.into_buffered().await?);
+            } else {
+                let field_name = part.field_name().to_owned();
+                let text = part.text().await?;
+                collected.text_fields.push((field_name, text));
+            }
+        }
+        self.validate_required_fields()?;
+        Ok(collected)
+    }
+
     /// Returns the next multipart part, if available.
     pub async fn next_part(&mut self) -> Result<Option<Part<'_>>, MulterError> {
+        self.buffering = None;
+        self.stream_deadline = None;
+        let headers = match self.peeked.take() {
+            Some(headers) => Some(headers),
+            None => self.next_accepted_headers().await?,
+        };
+        #[cfg(feature = "tracing")]
+        let span = std::mem::replace(&mut self.part_span, tracing::Span::none());
+        Ok(headers.map(|headers| {
+            let part = Part::new(headers, &mut self.inner)
+                .with_request_content_length(self.request_content_length);
+            #[cfg(feature = "tracing")]
+            let part = part.with_span(span);
+            part
+        }))
+    }
+
+    /// Returns the next part without a filename, draining file parts on the way.
+    ///
+    /// Skipped file parts are read to their end and discarded, so they still
+    /// count toward selector and limit checks.
+    pub async fn next_text_part(&mut self) -> Result<Option<Part<'_>>, MulterError> {
+        self.next_part_of_kind(false).await
+    }
+
+    /// Returns the next part with a filename, draining text parts on the way.
+    ///
+    /// Skipped text parts are read to their end and discarded, so they still
+    /// count toward selector and limit checks.
+    pub async fn next_file_part(&mut self) -> Result<Option<Part<'_>>, MulterError> {
+        self.next_part_of_kind(true).await
+    }
+
+    async fn next_part_of_kind(&mut self, file: bool) -> Result<Option<Part<'_>>, MulterError> {
+        loop {
+            let Some(headers) = self.peek_next_headers().await? else {
+                return Ok(None);
+            };
+            if headers.file_name.is_some() == file {
+                return self.next_part().await;
+            }
+            if let Some(mut skipped) = self.next_part().await? {
+                skipped.drain().await?;
+            }
+        }
+    }
+
+    /// Returns the headers of the next part without consuming it.
+    ///
+    /// The parser advances to the start of the part body, and the following
+    /// [`Multipart::next_part`] call yields the same part without parsing its
+    /// headers again. Repeated peeks return the same headers. The selector
+    /// and limits are applied exactly as for `next_part`.
+    pub async fn peek_next_headers(&mut self) -> Result<Option<&ParsedPartHeaders>, MulterError> {
+        self.buffering = None;
+        self.stream_deadline = None;
+        if self.peeked.is_none() {
+            self.peeked = self.next_accepted_headers().await?;
+        }
+        Ok(self.peeked.as_ref())
+    }
+
+    /// Waits for the next accepted part headers, honouring [`Limits::part_read_timeout`].
+    async fn next_accepted_headers(&mut self) -> Result<Option<ParsedPartHeaders>, MulterError> {
+        match self.limits.part_read_timeout {
+            Some(timeout) => {
+                let next = poll_fn(|cx| self.poll_next_accepted_headers(cx));
+                match tokio::time::timeout(timeout, next).await {
+                    Ok(headers) => headers,
+                    Err(_) => Err(self.timed_out(timeout)),
+                }
+            }
+            None => poll_fn(|cx| self.poll_next_accepted_headers(cx)).await,
+        }
+    }
+
+    /// Fails the parser for good after a [`Limits::part_read_timeout`] expiry.
+    fn timed_out(&mut self, timeout: Duration) -> MulterError {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(?timeout, "multipart: part read timed out");
+        self.inner.fail();
+        MulterError::Timeout {
+            elapsed_ms: u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Polls until the headers of the next part accepted by the selector are available.
+    ///
+    /// Any unread body of the previous part and every ignored part are drained
+    /// along the way.
+    fn poll_next_accepted_headers(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<ParsedPartHeaders>, MulterError>> {
         loop {
-            if self.inner.is_reading_part_body() {
-                self.inner.drain_current_part().await?;
+            while self.inner.is_reading_part_body() {
+                ready!(self.inner.poll_next_part_chunk(cx))?;
+            }
+            if let Some(field_name) = self.pending_text_field.take() {
+                if self.inner.last_part_size() == 0 {
+                    self.selector.record_empty_text_field(&field_name);
+                }
             }
 
-            let headers = poll_fn(|cx| self.inner.poll_next_part_headers(cx)).await?;
-            let Some(headers) = headers else {
+            let headers = ready!(self.inner.poll_next_part_headers(cx))?;
+            let Some(mut headers) = headers else {
                 #[cfg(feature = "tracing")]
                 tracing::debug!("multipart: reached end of stream");
-                return Ok(None);
+                return Poll::Ready(Ok(None));
             };
 
+            self.part_count += 1;
+            if let Some(max_parts) = self.limits.max_parts {
+                if self.part_count > max_parts {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        max_parts = max_parts,
+                        seen_parts = self.part_count,
+                        "multipart: part count limit exceeded"
+                    );
+                    return Poll::Ready(Err(MulterError::PartsLimitExceeded { max_parts }));
+                }
+            }
+
+            #[cfg(feature = "tracing")]
+            let span = crate::optional_span(self.emit_spans, || {
+                tracing::info_span!(
+                    "multer.part",
+                    field_name = headers.field_name.as_str(),
+                    file_name = headers.file_name.as_deref().unwrap_or("<none>"),
+                    content_type = headers.content_type.essence_str(),
+                )
+            });
+            #[cfg(feature = "tracing")]
+            let _entered = span.clone().entered();
+
             if headers.file_name.is_none() {
                 match self.selector.evaluate_text_field(&headers.field_name) {
                     Ok(SelectorAction::Accept) => {}
@@ -82,16 +567,16 @@ where
                             field_name = headers.field_name.as_str(),
                             "multipart: ignoring unmatched text field"
                         );
-                        self.inner.drain_current_part().await?;
                         continue;
                     }
-                    Err(err) => return Err(err),
+                    Err(err) => return Poll::Ready(Err(err)),
                 }
 
                 if let Some(max_size) = self.selector.field_text_max_size(&headers.field_name) {
                     self.inner.tighten_current_part_max_size(Some(max_size));
                 }
 
+                self.selector.record_text_field(&headers.field_name);
                 self.field_count += 1;
                 if let Some(max_fields) = self.limits.max_fields {
                     if self.field_count > max_fields {
@@ -101,7 +586,7 @@ where
                             seen_fields = self.field_count,
                             "multipart: text field limit exceeded"
                         );
-                        return Err(MulterError::FieldsLimitExceeded { max_fields });
+                        return Poll::Ready(Err(MulterError::FieldsLimitExceeded { max_fields }));
                     }
                 }
 
@@ -110,13 +595,33 @@ where
                     field_name = headers.field_name.as_str(),
                     "multipart: yielding text part"
                 );
-                return Ok(Some(Part::new(headers, &mut self.inner)));
+                self.pending_text_field = Some(headers.field_name.clone());
+                #[cfg(feature = "tracing")]
+                {
+                    self.part_span = span;
+                }
+                self.parts_yielded += 1;
+                return Poll::Ready(Ok(Some(headers)));
+            }
+
+            if let Some(target) = self.selector.redirect_file_field(&headers.field_name) {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    field_name = headers.field_name.as_str(),
+                    redirect_to = target,
+                    "multipart: redirecting unknown file field"
+                );
+                headers.field_name = target.to_owned();
             }
 
             match self.selector.evaluate_file_field(&headers.field_name) {
                 Ok(SelectorAction::Accept) => {
-                    if let Some(patterns) =
-                        self.selector.field_allowed_mime_types(&headers.field_name)
+                    let check_mime = !(self.defer_generic_mime
+                        && is_generic_mime(headers.content_type.essence_str()));
+                    if let Some(patterns) = self
+                        .selector
+                        .field_allowed_mime_types(&headers.field_name)
+                        .filter(|_| check_mime)
                     {
                         if !patterns.is_empty()
                             && !mime_matches_any(&headers.content_type, patterns)
@@ -127,24 +632,42 @@ where
                                 mime = headers.content_type.essence_str(),
                                 "multipart: rejected by per-field MIME allowlist"
                             );
-                            return Err(MulterError::MimeTypeNotAllowed {
+                            return Poll::Ready(Err(MulterError::MimeTypeNotAllowed {
                                 field: headers.field_name.clone(),
                                 mime: headers.content_type.essence_str().to_owned(),
-                            });
+                            }));
                         }
                     }
 
-                    if !self.limits.is_mime_allowed(&headers.content_type) {
+                    if check_mime && !self.limits.is_mime_allowed(&headers.content_type) {
                         #[cfg(feature = "tracing")]
                         tracing::warn!(
                             field_name = headers.field_name.as_str(),
                             mime = headers.content_type.essence_str(),
                             "multipart: rejected by global MIME allowlist"
                         );
-                        return Err(MulterError::MimeTypeNotAllowed {
+                        return Poll::Ready(Err(MulterError::MimeTypeNotAllowed {
                             field: headers.field_name.clone(),
                             mime: headers.content_type.essence_str().to_owned(),
-                        });
+                        }));
+                    }
+
+                    if let Some(extensions) =
+                        self.selector.field_allowed_extensions(&headers.field_name)
+                    {
+                        let extension = file_extension(headers.file_name.as_deref());
+                        if !extensions.is_empty() && !extension_matches_any(extension, extensions) {
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!(
+                                field_name = headers.field_name.as_str(),
+                                extension = extension,
+                                "multipart: rejected by per-field extension allowlist"
+                            );
+                            return Poll::Ready(Err(MulterError::ExtensionNotAllowed {
+                                field: headers.field_name.clone(),
+                                extension: extension.to_owned(),
+                            }));
+                        }
                     }
 
                     self.file_count += 1;
@@ -156,7 +679,7 @@ where
                                 seen_files = self.file_count,
                                 "multipart: file count limit exceeded"
                             );
-                            return Err(MulterError::FilesLimitExceeded { max_files });
+                            return Poll::Ready(Err(MulterError::FilesLimitExceeded { max_files }));
                         }
                     }
 
@@ -167,7 +690,12 @@ where
                         mime = headers.content_type.essence_str(),
                         "multipart: yielding file part"
                     );
-                    return Ok(Some(Part::new(headers, &mut self.inner)));
+                    #[cfg(feature = "tracing")]
+                    {
+                        self.part_span = span;
+                    }
+                    self.parts_yielded += 1;
+                    return Poll::Ready(Ok(Some(headers)));
                 }
                 Ok(SelectorAction::Ignore) => {
                     #[cfg(feature = "tracing")]
@@ -175,15 +703,131 @@ where
                         field_name = headers.field_name.as_str(),
                         "multipart: ignoring unmatched file field"
                     );
-                    self.inner.drain_current_part().await?;
                     continue;
                 }
-                Err(err) => return Err(err),
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+    }
+}
+
+impl<S> Stream for Multipart<S>
+where
+    S: Stream<Item = Result<Bytes, MulterError>> + Unpin + Send,
+{
+    type Item = Result<BufferedPart, MulterError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let Some(timeout) = this.limits.part_read_timeout else {
+            return this.poll_next_buffered(cx);
+        };
+        if this.stream_deadline.is_none() {
+            this.stream_deadline = Some(Box::pin(tokio::time::sleep(timeout)));
+        }
+        if let Poll::Ready(item) = this.poll_next_buffered(cx) {
+            this.stream_deadline = None;
+            return Poll::Ready(item);
+        }
+
+        let deadline = this.stream_deadline.as_mut().expect("armed deadline");
+        ready!(deadline.as_mut().poll(cx));
+        this.stream_deadline = None;
+        this.buffering = None;
+        Poll::Ready(Some(Err(this.timed_out(timeout))))
+    }
+}
+
+impl<S> Multipart<S>
+where
+    S: Stream<Item = Result<Bytes, MulterError>> + Unpin + Send,
+{
+    /// Polls the next buffered part for the [`Stream`] impl.
+    fn poll_next_buffered(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<BufferedPart, MulterError>>> {
+        loop {
+            if let Some(part) = self.buffering.as_mut() {
+                let decoded = match ready!(self.inner.poll_next_part_chunk(cx)) {
+                    Ok(Some(chunk)) => part.decoder.decode(chunk),
+                    Ok(None) => match part.decoder.finish() {
+                        Ok(tail) => {
+                            part.body.extend_from_slice(&tail);
+                            let part = self.buffering.take().expect("buffering part");
+                            return Poll::Ready(Some(Ok(BufferedPart {
+                                headers: part.headers,
+                                body: part.body.freeze(),
+                            })));
+                        }
+                        Err(err) => Err(err),
+                    },
+                    Err(err) => Err(err),
+                };
+                match decoded {
+                    Ok(chunk) => part.body.extend_from_slice(&chunk),
+                    Err(err) => {
+                        self.buffering = None;
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+                continue;
             }
+
+            if let Some(headers) = self.peeked.take() {
+                self.buffering = Some(BufferingPart::new(headers));
+                continue;
+            }
+            match ready!(self.poll_next_accepted_headers(cx)) {
+                Ok(Some(headers)) => self.buffering = Some(BufferingPart::new(headers)),
+                Ok(None) => return Poll::Ready(None),
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+}
+
+/// Part body collected by the [`Stream`] impl, decoded like [`Part::stream`].
+struct BufferingPart {
+    headers: ParsedPartHeaders,
+    body: BytesMut,
+    decoder: BodyDecoder,
+}
+
+impl BufferingPart {
+    fn new(headers: ParsedPartHeaders) -> Self {
+        Self {
+            decoder: BodyDecoder::new(&headers),
+            headers,
+            body: BytesMut::new(),
         }
     }
 }
 
+/// Owned stream of buffered parts returned by [`Multipart::into_stream`].
+#[derive(Debug)]
+pub struct OwnedMultipart<S> {
+    inner: Multipart<S>,
+}
+
+impl<S> OwnedMultipart<S> {
+    /// Returns the underlying multipart parser.
+    pub fn into_inner(self) -> Multipart<S> {
+        self.inner
+    }
+}
+
+impl<S> Stream for OwnedMultipart<S>
+where
+    S: Stream<Item = Result<Bytes, MulterError>> + Unpin + Send,
+{
+    type Item = Result<BufferedPart, MulterError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
 impl<S> PartBodyReader for MultipartStream<S>
 where
     S: Stream<Item = Result<Bytes, MulterError>> + Unpin + Send,
@@ -196,7 +840,24 @@ where
     }
 }
 
-fn mime_matches_any(mime: &mime::Mime, patterns: &[String]) -> bool {
+fn file_extension(file_name: Option<&str>) -> &str {
+    file_name
+        .and_then(|name| std::path::Path::new(name).extension())
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("")
+}
+
+fn extension_matches_any(extension: &str, allowed: &[String]) -> bool {
+    !extension.is_empty()
+        && allowed.iter().any(|candidate| {
+            candidate
+                .trim()
+                .trim_start_matches('.')
+                .eq_ignore_ascii_case(extension)
+        })
+}
+
+pub(crate) fn mime_matches_any(mime: &mime::Mime, patterns: &[String]) -> bool {
     patterns
         .iter()
         .any(|pattern| mime_matches_pattern(mime, pattern))
@@ -1,7 +1,7 @@
+use super::validate_boundary_str;
 use crate::error::ParseError;
 
 const MULTIPART_FORM_DATA: &str = "multipart/form-data";
-const MAX_BOUNDARY_LEN: usize = 70;
 
 /// Extracts and validates the `boundary` parameter from a `Content-Type` value.
 pub fn extract_multipart_boundary(content_type: &str) -> Result<String, ParseError> {
@@ -19,34 +19,21 @@ pub fn extract_multipart_boundary(content_type: &str) -> Result<String, ParseErr
         .ok_or_else(|| ParseError::new("missing multipart boundary parameter"))?;
 
     let boundary = decode_boundary_percent_encoding(boundary)?;
-    validate_boundary(&boundary)?;
+    validate_boundary_str(&boundary)?;
     Ok(boundary)
 }
 
-fn validate_boundary(boundary: &str) -> Result<(), ParseError> {
-    if boundary.is_empty() {
-        return Err(ParseError::new("multipart boundary cannot be empty"));
-    }
-
-    if boundary.len() > MAX_BOUNDARY_LEN {
-        return Err(ParseError::new(
-            "multipart boundary cannot exceed 70 characters",
-        ));
-    }
-
-    if boundary.ends_with(' ') {
-        return Err(ParseError::new(
-            "multipart boundary cannot end with whitespace",
-        ));
-    }
-
-    if !boundary.chars().all(is_boundary_char) {
-        return Err(ParseError::new(
-            "multipart boundary contains invalid characters",
-        ));
-    }
+/// Validates a raw multipart boundary token against RFC 2046 rules.
+///
+/// The boundary must be 1 to 70 characters long, must not end with a space,
+/// and may only contain RFC 2046 boundary characters.
+pub fn validate_multipart_boundary(boundary: &str) -> Result<(), ParseError> {
+    validate_boundary_str(boundary)
+}
 
-    Ok(())
+/// Returns `true` when `boundary` is a valid multipart boundary token.
+pub fn is_valid_multipart_boundary(boundary: &str) -> bool {
+    validate_boundary_str(boundary).is_ok()
 }
 
 fn decode_boundary_percent_encoding(boundary: &str) -> Result<String, ParseError> {
@@ -91,11 +78,3 @@ fn hex_value(byte: u8) -> Result<u8, ParseError> {
         )),
     }
 }
-
-fn is_boundary_char(c: char) -> bool {
-    c.is_ascii_alphanumeric()
-        || matches!(
-            c,
-            '\'' | '(' | ')' | '+' | '_' | ',' | '-' | '.' | '/' | ':' | '=' | '?' | ' '
-        )
-}
@@ -1,8 +1,11 @@
-use http::{header, HeaderMap};
+use std::borrow::Cow;
+
+use http::{header, HeaderMap, HeaderValue};
 
 use crate::error::ParseError;
 
 const DEFAULT_PART_CONTENT_TYPE: &str = "application/octet-stream";
+const CONTENT_TRANSFER_ENCODING: &str = "content-transfer-encoding";
 
 /// Parsed `Content-Disposition` metadata for a multipart part.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,6 +18,33 @@ pub struct ContentDisposition {
     pub filename: Option<String>,
 }
 
+/// Body encoding declared by a part's `Content-Transfer-Encoding` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TransferEncoding {
+    /// No transfer encoding: the header is absent, `7bit`, `8bit`, `binary`
+    /// or a value this crate does not decode.
+    #[default]
+    Identity,
+    /// `base64`, decoded from part bodies when the `cte` feature is enabled.
+    Base64,
+    /// `quoted-printable`, decoded from part bodies when the `cte` feature is enabled.
+    QuotedPrintable,
+}
+
+impl TransferEncoding {
+    /// Parses a `Content-Transfer-Encoding` value, ignoring case and surrounding whitespace.
+    pub fn from_header_value(value: &str) -> Self {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("base64") {
+            Self::Base64
+        } else if value.eq_ignore_ascii_case("quoted-printable") {
+            Self::QuotedPrintable
+        } else {
+            Self::Identity
+        }
+    }
+}
+
 /// Parsed header model for a multipart part.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ParsedPartHeaders {
@@ -28,6 +58,8 @@ pub struct ParsedPartHeaders {
     pub file_name: Option<String>,
     /// Parsed part-level content type.
     pub content_type: mime::Mime,
+    /// Declared `Content-Transfer-Encoding` of the part body.
+    pub transfer_encoding: TransferEncoding,
 }
 
 /// Parses a multipart part `Content-Disposition` value.
@@ -88,14 +120,25 @@ pub fn parse_part_content_type(value: Option<&str>) -> Result<mime::Mime, ParseE
 
 /// Parses multipart part headers needed by higher-level parser stages.
 pub fn parse_part_headers(headers: &HeaderMap) -> Result<ParsedPartHeaders, ParseError> {
-    let disposition_raw = headers
-        .get(header::CONTENT_DISPOSITION)
+    // RFC 2183: when a part repeats `Content-Disposition`, the last one wins.
+    let dispositions = headers.get_all(header::CONTENT_DISPOSITION);
+    let disposition_raw = dispositions
+        .iter()
+        .next_back()
         .ok_or_else(|| ParseError::new("missing Content-Disposition header"))?;
+    #[cfg(feature = "tracing")]
+    {
+        let count = dispositions.iter().count();
+        if count > 1 {
+            tracing::warn!(
+                count,
+                "multipart parser: part has multiple Content-Disposition headers; using the last"
+            );
+        }
+    }
 
-    let disposition_raw = disposition_raw
-        .to_str()
-        .map_err(|_| ParseError::new("Content-Disposition header must be ASCII"))?;
-    let content_disposition = parse_content_disposition(disposition_raw)?;
+    let disposition_raw = disposition_text(disposition_raw)?;
+    let content_disposition = parse_content_disposition(&disposition_raw)?;
 
     let field_name = content_disposition
         .name
@@ -113,15 +156,59 @@ pub fn parse_part_headers(headers: &HeaderMap) -> Result<ParsedPartHeaders, Pars
 
     let content_type = parse_part_content_type(content_type_raw)?;
 
+    let transfer_encoding = headers
+        .get(CONTENT_TRANSFER_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map_or(
+            TransferEncoding::Identity,
+            TransferEncoding::from_header_value,
+        );
+
     Ok(ParsedPartHeaders {
         headers: headers.clone(),
         file_name: content_disposition.filename.clone(),
         content_disposition,
         field_name,
         content_type,
+        transfer_encoding,
     })
 }
 
+/// Decodes a `Content-Disposition` value as text.
+///
+/// With the `iso-8859-1` feature, non-ASCII values are read as UTF-8 when
+/// valid and as ISO-8859-1 otherwise.
+#[cfg(feature = "iso-8859-1")]
+fn disposition_text(value: &HeaderValue) -> Result<Cow<'_, str>, ParseError> {
+    let bytes = value.as_bytes();
+    Ok(match std::str::from_utf8(bytes) {
+        Ok(text) => Cow::Borrowed(text),
+        Err(_) => encoding_rs::mem::decode_latin1(bytes),
+    })
+}
+
+#[cfg(not(feature = "iso-8859-1"))]
+fn disposition_text(value: &HeaderValue) -> Result<Cow<'_, str>, ParseError> {
+    value
+        .to_str()
+        .map(Cow::Borrowed)
+        .map_err(|_| ParseError::new("Content-Disposition header must be ASCII"))
+}
+
+/// Reads a `Content-Length` header as a byte count.
+pub(crate) fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_content_length)
+}
+
+/// Parses a `Content-Length` value, ignoring surrounding whitespace and
+/// treating malformed values as absent.
+pub(crate) fn parse_content_length(value: &str) -> Option<u64> {
+    value.trim().parse::<u64>().ok()
+}
+
 fn parse_parameter_value(raw: &str) -> Result<String, ParseError> {
     if let Some(stripped) = raw.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
         return unescape_quoted_string(stripped);
@@ -179,11 +266,15 @@ fn parse_filename_value(value: &str) -> Result<String, ParseError> {
         return Ok(value.to_owned());
     }
 
-    percent_decode_utf8(
-        value,
-        "invalid percent-encoding in filename",
-        "filename is not valid UTF-8",
-    )
+    let bytes = percent_decode(value, "invalid percent-encoding in filename")?;
+    match String::from_utf8(bytes) {
+        Ok(decoded) => Ok(decoded),
+        // RFC 6266 filenames may carry ISO-8859-1 octets.
+        #[cfg(feature = "iso-8859-1")]
+        Err(err) => Ok(encoding_rs::mem::decode_latin1(err.as_bytes()).into_owned()),
+        #[cfg(not(feature = "iso-8859-1"))]
+        Err(_) => Err(ParseError::new("filename is not valid UTF-8")),
+    }
 }
 
 fn percent_decode_utf8(
@@ -191,6 +282,14 @@ fn percent_decode_utf8(
     invalid_encoding_message: &'static str,
     invalid_utf8_message: &'static str,
 ) -> Result<String, ParseError> {
+    let bytes = percent_decode(value, invalid_encoding_message)?;
+    String::from_utf8(bytes).map_err(|_| ParseError::new(invalid_utf8_message))
+}
+
+fn percent_decode(
+    value: &str,
+    invalid_encoding_message: &'static str,
+) -> Result<Vec<u8>, ParseError> {
     let mut bytes = Vec::with_capacity(value.len());
     let raw = value.as_bytes();
     let mut index = 0;
@@ -211,7 +310,7 @@ fn percent_decode_utf8(
         index += 1;
     }
 
-    String::from_utf8(bytes).map_err(|_| ParseError::new(invalid_utf8_message))
+    Ok(bytes)
 }
 
 fn hex_value(byte: u8, invalid_encoding_message: &'static str) -> Result<u8, ParseError> {
@@ -1,17 +1,65 @@
+use crate::error::ParseError;
+
 /// Multipart boundary parsing helpers.
 pub mod boundary;
 /// Multipart part header parsing helpers.
 pub mod headers;
 /// Streaming multipart parser state machine.
 pub mod stream;
+/// Incremental `Content-Transfer-Encoding` body decoders.
+#[cfg(feature = "cte")]
+pub(crate) mod transfer;
 
-pub use boundary::extract_multipart_boundary;
+pub use boundary::{
+    extract_multipart_boundary, is_valid_multipart_boundary, validate_multipart_boundary,
+};
 pub use headers::{
     parse_content_disposition, parse_part_content_type, parse_part_headers, ContentDisposition,
-    ParsedPartHeaders,
+    ParsedPartHeaders, TransferEncoding,
 };
 pub use stream::MultipartStream;
 
 /// Low-level multipart parser entry type.
 #[derive(Debug, Clone, Default)]
 pub struct Parser;
+
+/// Maximum multipart boundary length allowed by RFC 2046.
+pub const MAX_BOUNDARY_LEN: usize = 70;
+
+/// Validates a boundary token against RFC 2046 rules.
+///
+/// Shared by [`extract_multipart_boundary`], [`validate_multipart_boundary`]
+/// and [`MultipartStream::new`] so every entry point accepts the same tokens.
+pub(crate) fn validate_boundary_str(boundary: &str) -> Result<(), ParseError> {
+    if boundary.is_empty() {
+        return Err(ParseError::new("multipart boundary cannot be empty"));
+    }
+
+    if boundary.len() > MAX_BOUNDARY_LEN {
+        return Err(ParseError::new(
+            "multipart boundary cannot exceed 70 characters",
+        ));
+    }
+
+    if boundary.ends_with(' ') {
+        return Err(ParseError::new(
+            "multipart boundary cannot end with whitespace",
+        ));
+    }
+
+    if !boundary.chars().all(is_boundary_char) {
+        return Err(ParseError::new(
+            "multipart boundary contains invalid characters",
+        ));
+    }
+
+    Ok(())
+}
+
+fn is_boundary_char(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+        || matches!(
+            c,
+            '\'' | '(' | ')' | '+' | '_' | ',' | '-' | '.' | '/' | ':' | '=' | '?' | ' '
+        )
+}
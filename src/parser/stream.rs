@@ -1,4 +1,7 @@
-use std::task::{Context, Poll};
+use std::{
+    borrow::Cow,
+    task::{Context, Poll},
+};
 
 use bytes::Bytes;
 use futures::{future::poll_fn, Stream};
@@ -8,7 +11,10 @@ use http::{
 };
 
 use crate::{
-    parser::headers::{parse_part_headers, ParsedPartHeaders},
+    parser::{
+        headers::{parse_part_headers, ParsedPartHeaders},
+        validate_boundary_str,
+    },
     MulterError, ParseError,
 };
 
@@ -30,12 +36,15 @@ pub struct StreamLimits {
     pub max_field_size: Option<u64>,
     /// Maximum request body size in bytes.
     pub max_body_size: Option<u64>,
+    /// Maximum size in bytes of a single part's header block.
+    pub max_header_size: Option<usize>,
 }
 
 /// Incremental multipart parser over a chunked byte stream.
 #[derive(Debug)]
 pub struct MultipartStream<S> {
     stream: S,
+    boundary: String,
     boundary_line: Vec<u8>,
     boundary_end_line: Vec<u8>,
     delimiter: Vec<u8>,
@@ -44,9 +53,12 @@ pub struct MultipartStream<S> {
     current_headers: Option<ParsedPartHeaders>,
     current_part_max_size: Option<u64>,
     current_part_size: u64,
+    last_part_size: u64,
     current_part_is_file: bool,
     limits: StreamLimits,
     received_body_bytes: u64,
+    parts_started: usize,
+    parts_completed: usize,
     upstream_done: bool,
 }
 
@@ -63,7 +75,7 @@ impl<S> MultipartStream<S> {
         limits: StreamLimits,
     ) -> Result<Self, ParseError> {
         let boundary = boundary.into();
-        validate_boundary_input(&boundary)?;
+        validate_boundary_str(&boundary)?;
 
         let boundary_line = format!("--{boundary}").into_bytes();
         let boundary_end_line = format!("--{boundary}--").into_bytes();
@@ -71,6 +83,7 @@ impl<S> MultipartStream<S> {
 
         Ok(Self {
             stream,
+            boundary,
             boundary_line,
             boundary_end_line,
             delimiter,
@@ -79,18 +92,61 @@ impl<S> MultipartStream<S> {
             current_headers: None,
             current_part_max_size: None,
             current_part_size: 0,
+            last_part_size: 0,
             current_part_is_file: false,
             limits,
             received_body_bytes: 0,
+            parts_started: 0,
+            parts_completed: 0,
             upstream_done: false,
         })
     }
 
+    /// Returns the multipart boundary this parser was created with, without the `--` prefix.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Returns the total number of raw bytes received from the upstream body so far.
+    pub fn received_body_bytes(&self) -> u64 {
+        self.received_body_bytes
+    }
+
+    /// Returns the number of part header blocks parsed so far.
+    pub fn parts_started(&self) -> usize {
+        self.parts_started
+    }
+
+    /// Returns the number of parts whose bodies were read up to their closing boundary.
+    pub fn parts_completed(&self) -> usize {
+        self.parts_completed
+    }
+
+    /// Returns `true` once the terminal boundary has been consumed.
+    pub fn is_finished(&self) -> bool {
+        self.state == ParseState::End
+    }
+
+    /// Returns `true` when parsing stopped because of an error.
+    pub fn is_failed(&self) -> bool {
+        self.state == ParseState::Failed
+    }
+
     /// Returns `true` when the parser is currently positioned in a part body.
     pub fn is_reading_part_body(&self) -> bool {
         self.state == ParseState::Body
     }
 
+    /// Stops the parser; later polls report no further parts.
+    pub fn fail(&mut self) {
+        self.state = ParseState::Failed;
+    }
+
+    /// Returns the body size in bytes of the most recently completed part.
+    pub fn last_part_size(&self) -> u64 {
+        self.last_part_size
+    }
+
     /// Tightens the active part size limit while a part body is being read.
     pub fn tighten_current_part_max_size(&mut self, limit: Option<u64>) {
         if self.state != ParseState::Body {
@@ -149,7 +205,22 @@ impl<S> MultipartStream<S> {
                     return Poll::Ready(Err(ParseError::new("malformed opening boundary").into()));
                 }
                 ParseState::Headers => {
-                    let Some(split) = find_subslice(&self.buffer, b"\r\n\r\n") else {
+                    let split = find_subslice(&self.buffer, b"\r\n\r\n");
+                    if let Some(max_header_size) = self.limits.max_header_size {
+                        if split.unwrap_or(self.buffer.len()) > max_header_size {
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!(
+                                max_header_size,
+                                "multipart parser: header block exceeded maximum size"
+                            );
+                            self.state = ParseState::Failed;
+                            return Poll::Ready(Err(ParseError::new(
+                                "part header block exceeded maximum size",
+                            )
+                            .into()));
+                        }
+                    }
+                    let Some(split) = split else {
                         if self.upstream_done {
                             self.state = ParseState::Failed;
                             return Poll::Ready(Err(MulterError::IncompleteStream));
@@ -184,6 +255,7 @@ impl<S> MultipartStream<S> {
                     };
                     self.current_part_size = 0;
                     self.current_headers = Some(headers.clone());
+                    self.parts_started += 1;
                     self.state = ParseState::Body;
                     #[cfg(feature = "tracing")]
                     tracing::trace!(
@@ -232,6 +304,18 @@ impl<S> MultipartStream<S> {
                     }
                 };
 
+                // The chunk may end inside the `--\r\n` or `\r\n` that follows the delimiter.
+                // A suffix that is already complete must not wait for more input.
+                let suffix_incomplete = (boundary_suffix.len() < 4
+                    && b"--\r\n".starts_with(boundary_suffix))
+                    || (boundary_suffix.len() < 2 && b"\r\n".starts_with(boundary_suffix));
+                if suffix_incomplete && !self.upstream_done {
+                    match self.poll_fill_buffer(cx)? {
+                        Poll::Ready(()) => continue,
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+
                 let (consumed, is_terminal) = if boundary_suffix.starts_with(b"--\r\n") {
                     (suffix_start + 4, true)
                 } else if boundary_suffix.starts_with(b"\r\n") {
@@ -259,10 +343,12 @@ impl<S> MultipartStream<S> {
                 };
 
                 self.buffer.drain(..consumed);
+                self.last_part_size = self.current_part_size;
                 self.current_headers = None;
                 self.current_part_max_size = None;
                 self.current_part_size = 0;
                 self.current_part_is_file = false;
+                self.parts_completed += 1;
                 self.state = if is_terminal {
                     #[cfg(feature = "tracing")]
                     tracing::trace!("multipart parser: terminal boundary reached");
@@ -340,8 +426,8 @@ impl<S> MultipartStream<S> {
             Poll::Pending => Ok(Poll::Pending),
             Poll::Ready(Some(Ok(chunk))) => {
                 if !chunk.is_empty() {
+                    let next = self.received_body_bytes.saturating_add(chunk.len() as u64);
                     if let Some(max_body_size) = self.limits.max_body_size {
-                        let next = self.received_body_bytes.saturating_add(chunk.len() as u64);
                         if next > max_body_size {
                             #[cfg(feature = "tracing")]
                             tracing::warn!(
@@ -352,8 +438,8 @@ impl<S> MultipartStream<S> {
                             self.state = ParseState::Failed;
                             return Err(MulterError::BodySizeLimitExceeded { max_body_size });
                         }
-                        self.received_body_bytes = next;
                     }
+                    self.received_body_bytes = next;
 
                     self.buffer.extend_from_slice(&chunk);
                 }
@@ -412,8 +498,13 @@ impl<S> MultipartStream<S> {
 }
 
 fn parse_header_block(raw: &[u8]) -> Result<HeaderMap, ParseError> {
-    let text =
-        std::str::from_utf8(raw).map_err(|_| ParseError::new("part headers must be UTF-8"))?;
+    let text = match std::str::from_utf8(raw) {
+        Ok(text) => Cow::Borrowed(text),
+        #[cfg(feature = "iso-8859-1")]
+        Err(_) => encoding_rs::mem::decode_latin1(raw),
+        #[cfg(not(feature = "iso-8859-1"))]
+        Err(_) => return Err(ParseError::new("part headers must be UTF-8")),
+    };
     let mut headers = HeaderMap::new();
 
     for line in text.split("\r\n") {
@@ -474,15 +565,3 @@ fn has_malformed_boundary_line(
     let line = &buffer[line_start..line_start + relative_end];
     line != boundary_line && line != boundary_end_line
 }
-
-fn validate_boundary_input(boundary: &str) -> Result<(), ParseError> {
-    if boundary.is_empty() {
-        return Err(ParseError::new("multipart boundary cannot be empty"));
-    }
-
-    if boundary.contains('\r') || boundary.contains('\n') {
-        return Err(ParseError::new("multipart boundary cannot contain CRLF"));
-    }
-
-    Ok(())
-}
@@ -0,0 +1,142 @@
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine as _,
+};
+use bytes::Bytes;
+
+use crate::{parser::headers::TransferEncoding, ParseError};
+
+/// Accepts both padded and unpadded final groups.
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Incremental decoder for a transfer-encoded part body.
+///
+/// Chunks may split encoded units anywhere; incomplete trailing input is
+/// carried over to the next chunk and flushed by [`TransferDecoder::finish`].
+#[derive(Debug)]
+pub(crate) enum TransferDecoder {
+    Base64 { pending: Vec<u8> },
+    QuotedPrintable { pending: Vec<u8> },
+}
+
+impl TransferDecoder {
+    /// Returns a decoder for `encoding`, or `None` when the body is not encoded.
+    pub(crate) fn new(encoding: TransferEncoding) -> Option<Self> {
+        match encoding {
+            TransferEncoding::Identity => None,
+            TransferEncoding::Base64 => Some(Self::Base64 {
+                pending: Vec::new(),
+            }),
+            TransferEncoding::QuotedPrintable => Some(Self::QuotedPrintable {
+                pending: Vec::new(),
+            }),
+        }
+    }
+
+    /// Decodes the next raw body chunk.
+    ///
+    /// The result may be empty when the chunk only completed carried-over input
+    /// partially.
+    pub(crate) fn decode(&mut self, chunk: &[u8]) -> Result<Bytes, ParseError> {
+        match self {
+            Self::Base64 { pending } => {
+                pending.extend(
+                    chunk
+                        .iter()
+                        .copied()
+                        .filter(|byte| !byte.is_ascii_whitespace()),
+                );
+                let complete = pending.len() - pending.len() % 4;
+                let decoded = decode_base64(&pending[..complete])?;
+                pending.drain(..complete);
+                Ok(decoded)
+            }
+            Self::QuotedPrintable { pending } => {
+                let input = if pending.is_empty() {
+                    chunk.to_vec()
+                } else {
+                    let mut input = std::mem::take(pending);
+                    input.extend_from_slice(chunk);
+                    input
+                };
+                decode_quoted_printable(&input, pending)
+            }
+        }
+    }
+
+    /// Decodes input carried over from the last chunk once the body has ended.
+    pub(crate) fn finish(&mut self) -> Result<Bytes, ParseError> {
+        match self {
+            Self::Base64 { pending } => {
+                let decoded = decode_base64(pending)?;
+                pending.clear();
+                Ok(decoded)
+            }
+            Self::QuotedPrintable { pending } if pending.is_empty() => Ok(Bytes::new()),
+            Self::QuotedPrintable { pending } => {
+                pending.clear();
+                Err(ParseError::new(
+                    "quoted-printable part body ends inside an escape sequence",
+                ))
+            }
+        }
+    }
+}
+
+fn decode_base64(input: &[u8]) -> Result<Bytes, ParseError> {
+    if input.is_empty() {
+        return Ok(Bytes::new());
+    }
+    BASE64
+        .decode(input)
+        .map(Bytes::from)
+        .map_err(|err| ParseError::new(format!("invalid base64 part body: {err}")))
+}
+
+/// Decodes `input`, leaving an escape split by the chunk boundary in `pending`.
+fn decode_quoted_printable(input: &[u8], pending: &mut Vec<u8>) -> Result<Bytes, ParseError> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut index = 0;
+    while index < input.len() {
+        if input[index] != b'=' {
+            out.push(input[index]);
+            index += 1;
+            continue;
+        }
+        match &input[index + 1..] {
+            [] | [b'\r'] => {
+                pending.extend_from_slice(&input[index..]);
+                break;
+            }
+            [high] if high.is_ascii_hexdigit() => {
+                pending.extend_from_slice(&input[index..]);
+                break;
+            }
+            // Soft line break.
+            [b'\n', ..] => index += 2,
+            [b'\r', b'\n', ..] => index += 3,
+            [high, low, ..] if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+                out.push(hex_value(*high) << 4 | hex_value(*low));
+                index += 3;
+            }
+            _ => {
+                return Err(ParseError::new(
+                    "invalid quoted-printable escape in part body",
+                ))
+            }
+        }
+    }
+    Ok(Bytes::from(out))
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
@@ -1,14 +1,20 @@
 use std::{
-    fmt,
+    fmt, io,
     pin::Pin,
-    task::{Context, Poll},
+    task::{ready, Context, Poll},
 };
 
 use bytes::Bytes;
-use futures::{stream, Stream, StreamExt};
-use http::{header, HeaderMap};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use http::HeaderMap;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
 
-use crate::{parser::headers::ParsedPartHeaders, BoxStream, MulterError, ParseError};
+#[cfg(feature = "cte")]
+use crate::parser::transfer::TransferDecoder;
+use crate::{
+    parser::headers::{content_length, ParsedPartHeaders},
+    BoxStream, MulterError, ParseError,
+};
 
 pub(crate) trait PartBodyReader: Send {
     fn poll_next_chunk(&mut self, cx: &mut Context<'_>)
@@ -20,6 +26,9 @@ pub struct Part<'a> {
     /// Parsed part headers.
     pub headers: ParsedPartHeaders,
     body_reader: Option<&'a mut dyn PartBodyReader>,
+    request_content_length: Option<u64>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
 }
 
 impl fmt::Debug for Part<'_> {
@@ -37,9 +46,35 @@ impl<'a> Part<'a> {
         Self {
             headers,
             body_reader: Some(body_reader),
+            request_content_length: None,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::none(),
         }
     }
 
+    /// Records the `Content-Length` of the enclosing request.
+    pub(crate) fn with_request_content_length(mut self, content_length: Option<u64>) -> Self {
+        self.request_content_length = content_length;
+        self
+    }
+
+    /// Attaches the `multer.part` span that body reads and storage run under.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn with_span(mut self, span: tracing::Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Returns the `multer.part` span for this part.
+    ///
+    /// The span is disabled unless spans were enabled with
+    /// [`MulterBuilder::with_tracing_spans`](crate::MulterBuilder::with_tracing_spans).
+    /// It closes once the part and every body stream taken from it are dropped.
+    #[cfg(feature = "tracing")]
+    pub fn span(&self) -> &tracing::Span {
+        &self.span
+    }
+
     /// Returns the logical field name for this part.
     pub fn field_name(&self) -> &str {
         &self.headers.field_name
@@ -55,6 +90,14 @@ impl<'a> Part<'a> {
         self.headers.content_type.as_ref()
     }
 
+    /// Returns the `charset` parameter of the part's content type, if declared.
+    pub fn charset(&self) -> Option<&str> {
+        self.headers
+            .content_type
+            .get_param(mime::CHARSET)
+            .map(|value| value.as_str())
+    }
+
     /// Returns raw part headers.
     ///
     /// `headers()` exposes the original map for advanced inspection, while
@@ -63,21 +106,82 @@ impl<'a> Part<'a> {
         &self.headers.headers
     }
 
+    /// Returns the value of the header `name` as a string.
+    ///
+    /// The lookup is case-insensitive. Returns `None` when the header is
+    /// missing or its value is not visible ASCII.
+    pub fn raw_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    }
+
+    /// Iterates over every header whose value is visible ASCII.
+    ///
+    /// Names are lowercase, and repeated headers are yielded once per value.
+    pub fn raw_headers(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.headers
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+    }
+
     /// Returns parsed part headers.
     pub fn parsed_headers(&self) -> &ParsedPartHeaders {
         &self.headers
     }
 
+    /// Returns a clone of the parsed headers while leaving the body readable.
+    pub fn take_headers(&mut self) -> ParsedPartHeaders {
+        self.headers.clone()
+    }
+
+    /// Splits the part into owned headers and an owned body reader.
+    ///
+    /// The rest of the body is read into memory first, so neither half
+    /// borrows the parent multipart stream: both are `'static` and can be
+    /// moved into other tasks while the next part is requested. To keep
+    /// streaming the body instead, clone the headers with
+    /// [`Part::take_headers`].
+    pub async fn into_owned_headers(
+        mut self,
+    ) -> Result<(ParsedPartHeaders, OwnedBodyReader), MulterError> {
+        let chunks = self.stream().try_collect::<Vec<_>>().await?;
+        let body = stream::iter(chunks.into_iter().map(Ok)).boxed();
+        Ok((self.headers, OwnedBodyReader(body)))
+    }
+
+    /// Converts the part into an [`AsyncRead`] over its body.
+    ///
+    /// Chunks are pulled from the multipart stream only as the reader is
+    /// polled, and parser errors surface as [`io::Error`]s wrapping the
+    /// original [`MulterError`].
+    pub fn into_reader(self) -> PartReader<'a> {
+        PartReader {
+            decoder: BodyDecoder::new(&self.headers),
+            body_reader: self.body_reader,
+            pending: Bytes::new(),
+            finished: false,
+        }
+    }
+
     /// Returns the approximate body size hint in bytes from `Content-Length`, when present.
     ///
     /// The hint may be `None` when the incoming part does not declare a
     /// `Content-Length` header.
     pub fn size_hint(&self) -> Option<u64> {
-        self.headers
-            .headers
-            .get(header::CONTENT_LENGTH)
-            .and_then(|value| value.to_str().ok())
-            .and_then(|value| value.parse::<u64>().ok())
+        content_length(&self.headers.headers)
+    }
+
+    /// Returns the `Content-Length` of the whole request, when it was provided.
+    ///
+    /// Set through [`Multipart::with_request_content_length`](crate::Multipart::with_request_content_length).
+    /// The integrations that build a [`Multipart`](crate::Multipart) from a
+    /// request fill it in from the `Content-Length` header; malformed values
+    /// are treated as absent.
+    pub fn request_content_length(&self) -> Option<u64> {
+        self.request_content_length
     }
 
     /// Reads the full part body as bytes.
@@ -97,28 +201,247 @@ impl<'a> Part<'a> {
             .map_err(|_| ParseError::new("part body is not valid UTF-8").into())
     }
 
+    /// Reads the full part body and decodes it using the declared [`Part::charset`].
+    ///
+    /// Parts without a charset, or with a label `encoding_rs` does not
+    /// recognise, are decoded as UTF-8. Labels follow the WHATWG Encoding
+    /// Standard, so `iso-8859-1` decodes as `windows-1252`.
+    #[cfg(feature = "encoding")]
+    pub async fn decode_text(&mut self) -> Result<String, MulterError> {
+        let encoding = self
+            .charset()
+            .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+            .unwrap_or(encoding_rs::UTF_8);
+        let bytes = self.bytes().await?;
+        encoding
+            .decode_without_bom_handling_and_without_replacement(&bytes)
+            .map(|text| text.into_owned())
+            .ok_or_else(|| {
+                ParseError::new(format!("part body is not valid {}", encoding.name())).into()
+            })
+    }
+
+    /// Reads the full part body and deserializes it as JSON.
+    ///
+    /// Deserialization failures surface as [`MulterError::Deserialize`].
+    #[cfg(feature = "serde")]
+    pub async fn json<T>(&mut self) -> Result<T, MulterError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let bytes = self.bytes().await?;
+        serde_json::from_slice(&bytes).map_err(|err| MulterError::Deserialize {
+            field: self.headers.field_name.clone(),
+            message: err.to_string(),
+        })
+    }
+
+    /// Reads the full part body and parses it as `application/x-www-form-urlencoded` pairs.
+    ///
+    /// Keys and values are percent-decoded; invalid UTF-8 sequences are
+    /// replaced with `U+FFFD`.
+    pub async fn form_urlencoded_pairs(&mut self) -> Result<Vec<(String, String)>, MulterError> {
+        let bytes = self.bytes().await?;
+        Ok(form_urlencoded::parse(&bytes)
+            .into_owned()
+            .collect::<Vec<_>>())
+    }
+
+    /// Reads the full body and returns a part that no longer borrows the multipart stream.
+    pub async fn into_buffered(mut self) -> Result<BufferedPart, MulterError> {
+        let body = self.bytes().await?;
+        Ok(BufferedPart {
+            headers: self.headers,
+            body,
+        })
+    }
+
+    /// Reads and discards the remaining part body.
+    ///
+    /// Returns the number of body bytes that were discarded.
+    pub async fn drain(&mut self) -> Result<u64, MulterError> {
+        let mut stream = self.stream();
+        let mut discarded = 0u64;
+        while let Some(chunk) = stream.next().await {
+            discarded = discarded.saturating_add(chunk?.len() as u64);
+        }
+        Ok(discarded)
+    }
+
+    /// Writes the remaining part body to `writer` and flushes it.
+    ///
+    /// Returns the number of body bytes written. Write failures surface as
+    /// [`MulterError::Storage`].
+    pub async fn copy_to<W>(&mut self, writer: &mut W) -> Result<u64, MulterError>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let mut stream = self.stream();
+        let mut copied = 0u64;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await?;
+            copied = copied.saturating_add(chunk.len() as u64);
+        }
+        writer.flush().await?;
+        Ok(copied)
+    }
+
     /// Returns a one-shot body stream for this part.
     ///
     /// The returned stream can only be created once; subsequent calls return a
-    /// stream that yields a single "already consumed" error item.
+    /// stream that yields a single "already consumed" error item. With the
+    /// `cte` feature, `base64` and `quoted-printable` bodies are decoded as
+    /// they stream.
     pub fn stream(&mut self) -> BoxStream<'_, Result<Bytes, MulterError>> {
-        let Some(body_reader) = self.body_reader.take() else {
-            return Box::pin(stream::once(async {
-                Err(ParseError::new("part body was already consumed").into())
-            }));
-        };
-
-        Box::pin(PartBodyStream {
-            body_reader,
-            finished: false,
-        })
+        body_stream(
+            self.body_reader.take(),
+            BodyDecoder::new(&self.headers),
+            #[cfg(feature = "tracing")]
+            self.span.clone(),
+        )
+    }
+}
+
+/// Fully buffered multipart part returned by [`Part::into_buffered`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferedPart {
+    /// Parsed part headers.
+    pub headers: ParsedPartHeaders,
+    /// Complete part body.
+    pub body: Bytes,
+}
+
+impl BufferedPart {
+    /// Returns the logical field name for this part.
+    pub fn field_name(&self) -> &str {
+        &self.headers.field_name
+    }
+
+    /// Returns the optional file name for this part.
+    pub fn file_name(&self) -> Option<&str> {
+        self.headers.file_name.as_deref()
+    }
+
+    /// Returns the parsed content type for this part.
+    pub fn content_type(&self) -> &mime::Mime {
+        &self.headers.content_type
+    }
+
+    /// Returns the buffered body bytes.
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Returns the buffered body decoded as UTF-8 text.
+    pub fn text(&self) -> Result<&str, MulterError> {
+        std::str::from_utf8(&self.body)
+            .map_err(|_| ParseError::new("part body is not valid UTF-8").into())
+    }
+}
+
+fn body_stream(
+    body_reader: Option<&mut dyn PartBodyReader>,
+    decoder: BodyDecoder,
+    #[cfg(feature = "tracing")] span: tracing::Span,
+) -> BoxStream<'_, Result<Bytes, MulterError>> {
+    let Some(body_reader) = body_reader else {
+        return Box::pin(stream::once(async {
+            Err(ParseError::new("part body was already consumed").into())
+        }));
+    };
+
+    Box::pin(PartBodyStream {
+        body_reader,
+        decoder,
+        finished: false,
+        #[cfg(feature = "tracing")]
+        span,
+    })
+}
+
+/// Buffered body stream returned by [`Part::into_owned_headers`].
+pub struct OwnedBodyReader(BoxStream<'static, Result<Bytes, MulterError>>);
+
+impl fmt::Debug for OwnedBodyReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedBodyReader").finish_non_exhaustive()
+    }
+}
+
+impl Stream for OwnedBodyReader {
+    type Item = Result<Bytes, MulterError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_next(cx)
+    }
+}
+
+/// [`AsyncRead`] adapter returned by [`Part::into_reader`].
+pub struct PartReader<'a> {
+    body_reader: Option<&'a mut dyn PartBodyReader>,
+    decoder: BodyDecoder,
+    pending: Bytes,
+    finished: bool,
+}
+
+impl fmt::Debug for PartReader<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PartReader")
+            .field("buffered", &self.pending.len())
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+impl AsyncRead for PartReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            if !self.pending.is_empty() {
+                let len = self.pending.len().min(buf.remaining());
+                let chunk = self.pending.split_to(len);
+                buf.put_slice(&chunk);
+                return Poll::Ready(Ok(()));
+            }
+            if self.finished {
+                return Poll::Ready(Ok(()));
+            }
+
+            let Some(body_reader) = self.body_reader.as_mut() else {
+                self.finished = true;
+                let err = MulterError::from(ParseError::new("part body was already consumed"));
+                return Poll::Ready(Err(io::Error::other(err)));
+            };
+            let decoded = match ready!(body_reader.poll_next_chunk(cx)) {
+                Ok(Some(chunk)) => self.decoder.decode(chunk),
+                Ok(None) => {
+                    self.finished = true;
+                    self.decoder.finish()
+                }
+                Err(err) => Err(err),
+            };
+            match decoded {
+                Ok(chunk) => self.pending = chunk,
+                Err(err) => {
+                    self.finished = true;
+                    return Poll::Ready(Err(io::Error::other(err)));
+                }
+            }
+        }
     }
 }
 
 /// One-shot stream returned by [`Part::stream`].
 pub struct PartBodyStream<'a> {
     body_reader: &'a mut dyn PartBodyReader,
+    decoder: BodyDecoder,
     finished: bool,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
 }
 
 impl fmt::Debug for PartBodyStream<'_> {
@@ -137,17 +460,67 @@ impl Stream for PartBodyStream<'_> {
             return Poll::Ready(None);
         }
 
-        match self.body_reader.poll_next_chunk(cx) {
-            Poll::Ready(Ok(Some(bytes))) => Poll::Ready(Some(Ok(bytes))),
-            Poll::Ready(Ok(None)) => {
-                self.finished = true;
-                Poll::Ready(None)
+        #[cfg(feature = "tracing")]
+        let span = self.span.clone();
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+        loop {
+            let decoded = match ready!(self.body_reader.poll_next_chunk(cx)) {
+                Ok(Some(bytes)) => self.decoder.decode(bytes),
+                Ok(None) => {
+                    self.finished = true;
+                    match self.decoder.finish() {
+                        Ok(bytes) if bytes.is_empty() => return Poll::Ready(None),
+                        decoded => decoded,
+                    }
+                }
+                Err(err) => Err(err),
+            };
+            match decoded {
+                // A decoder may need more input before producing output.
+                Ok(bytes) if bytes.is_empty() && !self.finished => continue,
+                Ok(bytes) => return Poll::Ready(Some(Ok(bytes))),
+                Err(err) => {
+                    self.finished = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
             }
-            Poll::Ready(Err(err)) => {
-                self.finished = true;
-                Poll::Ready(Some(Err(err)))
-            }
-            Poll::Pending => Poll::Pending,
         }
     }
 }
+
+/// Applies the part's `Content-Transfer-Encoding` to body chunks.
+///
+/// Without the `cte` feature this passes chunks through unchanged.
+#[derive(Debug, Default)]
+pub(crate) struct BodyDecoder {
+    #[cfg(feature = "cte")]
+    inner: Option<TransferDecoder>,
+}
+
+impl BodyDecoder {
+    pub(crate) fn new(headers: &ParsedPartHeaders) -> Self {
+        #[cfg(not(feature = "cte"))]
+        let _ = headers;
+        Self {
+            #[cfg(feature = "cte")]
+            inner: TransferDecoder::new(headers.transfer_encoding),
+        }
+    }
+
+    pub(crate) fn decode(&mut self, chunk: Bytes) -> Result<Bytes, MulterError> {
+        #[cfg(feature = "cte")]
+        if let Some(decoder) = &mut self.inner {
+            return Ok(decoder.decode(&chunk)?);
+        }
+        Ok(chunk)
+    }
+
+    pub(crate) fn finish(&mut self) -> Result<Bytes, MulterError> {
+        #[cfg(feature = "cte")]
+        if let Some(decoder) = &mut self.inner {
+            return Ok(decoder.finish()?);
+        }
+        Ok(Bytes::new())
+    }
+}
@@ -0,0 +1,152 @@
+//! Rocket integration helpers.
+
+use std::{fmt, marker::PhantomData, ops::Deref, sync::Arc};
+
+use futures::TryStreamExt;
+use rocket::{
+    data::{ByteUnit, Data, FromData, Limits, Outcome},
+    fairing::{Fairing, Info, Kind},
+    http::Status,
+    outcome::Outcome as RocketOutcome,
+    Build, Request, Rocket,
+};
+use tokio_util::io::ReaderStream;
+
+use crate::{
+    parser::headers::parse_content_length, CollectedMultipart, Multer, MulterError, ParseError,
+    StorageEngine,
+};
+
+/// Fairing registering a shared [`Multer`] in Rocket's managed state.
+///
+/// The instance is managed as `Arc<Multer<S>>`, so routes can also request it
+/// directly through `&State<Arc<Multer<S>>>`.
+pub struct MulterFairing<S> {
+    multer: Arc<Multer<S>>,
+}
+
+impl<S> MulterFairing<S> {
+    /// Creates a fairing backed by a shared `Multer` instance.
+    pub fn new(multer: Arc<Multer<S>>) -> Self {
+        Self { multer }
+    }
+}
+
+impl<S> fmt::Debug for MulterFairing<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MulterFairing").finish_non_exhaustive()
+    }
+}
+
+#[rocket::async_trait]
+impl<S> Fairing for MulterFairing<S>
+where
+    S: StorageEngine,
+{
+    fn info(&self) -> Info {
+        Info {
+            name: "multigear",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        Ok(rocket.manage(Arc::clone(&self.multer)))
+    }
+}
+
+/// Data guard parsing a multipart body with the managed [`Multer<S>`].
+///
+/// Every part is read into memory through [`crate::Multipart::parse_all`],
+/// enforcing the configured selectors and limits. Requests without a
+/// `multipart/form-data` content type are forwarded with
+/// `415 Unsupported Media Type`; parse failures fail the guard with the status
+/// from [`MulterError::http_status`].
+///
+/// The body is read up to `max_body_size` when configured and otherwise up to
+/// Rocket's `data-form` limit.
+pub struct MulterForm<S> {
+    collected: CollectedMultipart,
+    _storage: PhantomData<fn() -> S>,
+}
+
+impl<S> MulterForm<S> {
+    /// Returns the collected multipart body.
+    pub fn into_inner(self) -> CollectedMultipart {
+        self.collected
+    }
+}
+
+impl<S> Deref for MulterForm<S> {
+    type Target = CollectedMultipart;
+
+    fn deref(&self) -> &Self::Target {
+        &self.collected
+    }
+}
+
+impl<S> fmt::Debug for MulterForm<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MulterForm").field(&self.collected).finish()
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, S> FromData<'r> for MulterForm<S>
+where
+    S: StorageEngine,
+{
+    type Error = MulterError;
+
+    async fn from_data(request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let Some(content_type) = request
+            .headers()
+            .get_one("Content-Type")
+            .filter(|value| crate::parser::extract_multipart_boundary(value).is_ok())
+        else {
+            return RocketOutcome::Forward((data, Status::UnsupportedMediaType));
+        };
+        let Some(multer) = request.rocket().state::<Arc<Multer<S>>>() else {
+            return RocketOutcome::Error((
+                Status::InternalServerError,
+                ParseError::new("Multer state is not managed; attach MulterFairing").into(),
+            ));
+        };
+
+        let limit = match multer.config().limits.max_body_size {
+            // One byte past the limit so oversized bodies surface as limit errors.
+            Some(max_body_size) => ByteUnit::from(max_body_size.saturating_add(1)),
+            None => request
+                .limits()
+                .get("data-form")
+                .unwrap_or(Limits::DATA_FORM),
+        };
+        let body = ReaderStream::new(data.open(limit)).map_err(|err| {
+            MulterError::from(ParseError::new(format!("rocket body stream error: {err}")))
+        });
+
+        let content_length = request
+            .headers()
+            .get_one("Content-Length")
+            .and_then(parse_content_length);
+        let collected = match multer.multipart_from_content_type(content_type, body) {
+            Ok(multipart) => {
+                let mut multipart = multipart.with_request_content_length(content_length);
+                multipart.parse_all().await
+            }
+            Err(err) => Err(err),
+        };
+        match collected {
+            Ok(collected) => RocketOutcome::Success(Self {
+                collected,
+                _storage: PhantomData,
+            }),
+            Err(err) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %err, "rocket: failed to parse multipart body");
+                let status = Status::from_code(err.http_status()).unwrap_or(Status::BadRequest);
+                RocketOutcome::Error((status, err))
+            }
+        }
+    }
+}
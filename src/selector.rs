@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use crate::{MulterError, SelectedField, SelectedFieldKind, Selector, UnknownFieldPolicy};
+use crate::{
+    MulterError, SelectedField, SelectedFieldKind, Selector, UnknownFieldAction, UnknownFieldPolicy,
+};
 
 /// Runtime decision for a candidate incoming file part.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,18 +19,60 @@ pub struct SelectorEngine {
     selector: Selector,
     unknown_field_policy: UnknownFieldPolicy,
     counts: HashMap<String, usize>,
+    empty_text_counts: HashMap<String, usize>,
     fields: HashMap<String, FieldRules>,
+    #[cfg(feature = "regex")]
+    pattern: Option<regex::Regex>,
+    pattern_matches: usize,
 }
 
 impl SelectorEngine {
     /// Creates a selector engine with runtime counters.
+    ///
+    /// A [`Selector::Regex`] pattern is compiled here once; a pattern that
+    /// fails to compile matches no field names.
     pub fn new(selector: Selector, unknown_field_policy: UnknownFieldPolicy) -> Self {
         let fields = build_fields_map(&selector);
+        #[cfg(feature = "regex")]
+        let pattern = match &selector {
+            Selector::Regex { pattern, .. } => crate::config::compile_field_pattern(pattern).ok(),
+            _ => None,
+        };
         Self {
             selector,
             unknown_field_policy,
             counts: HashMap::new(),
+            empty_text_counts: HashMap::new(),
             fields,
+            #[cfg(feature = "regex")]
+            pattern,
+            pattern_matches: 0,
+        }
+    }
+
+    /// Returns the field name an unknown file field is redirected to, if any.
+    ///
+    /// This is `Some` only under [`UnknownFieldPolicy::Redirect`] and only for
+    /// file fields the selector does not describe.
+    pub fn redirect_file_field(&self, field_name: &str) -> Option<&str> {
+        match &self.unknown_field_policy {
+            UnknownFieldPolicy::Redirect { to } if !self.is_known_file_field(field_name) => {
+                Some(to)
+            }
+            _ => None,
+        }
+    }
+
+    fn is_known_file_field(&self, field_name: &str) -> bool {
+        match &self.selector {
+            Selector::Single { name } | Selector::Array { name, .. } => field_name == name,
+            Selector::Fields(_) => self
+                .fields
+                .get(field_name)
+                .is_some_and(|rules| rules.kind == SelectedFieldKind::File),
+            Selector::Regex { .. } => self.matches_pattern(field_name),
+            Selector::None => false,
+            Selector::Any => true,
         }
     }
 
@@ -59,11 +103,88 @@ impl SelectorEngine {
                 self.record_with_limit(field_name, rules.max_count)?;
                 Ok(SelectorAction::Accept)
             }
+            Selector::Regex { max_count, .. } => {
+                if !self.matches_pattern(field_name) {
+                    return self.handle_unknown_field(field_name);
+                }
+                if let Some(max_count) = *max_count {
+                    if self.pattern_matches >= max_count {
+                        return Err(MulterError::FieldCountLimitExceeded {
+                            field: field_name.to_owned(),
+                            max_count,
+                        });
+                    }
+                }
+                self.pattern_matches += 1;
+                self.record_with_limit(field_name, None)?;
+                Ok(SelectorAction::Accept)
+            }
             Selector::None => self.handle_unknown_field(field_name),
             Selector::Any => Ok(SelectorAction::Accept),
         }
     }
 
+    /// Records an accepted text field so required-field checks can see it.
+    pub(crate) fn record_text_field(&mut self, field_name: &str) {
+        if self
+            .fields
+            .get(field_name)
+            .is_some_and(|rules| rules.kind == SelectedFieldKind::Text)
+        {
+            *self.counts.entry(field_name.to_owned()).or_default() += 1;
+        }
+    }
+
+    /// Records that an accepted text field arrived with an empty body.
+    ///
+    /// Empty values do not count towards the field's `min_count`.
+    pub(crate) fn record_empty_text_field(&mut self, field_name: &str) {
+        if self
+            .fields
+            .get(field_name)
+            .is_some_and(|rules| rules.kind == SelectedFieldKind::Text)
+        {
+            *self
+                .empty_text_counts
+                .entry(field_name.to_owned())
+                .or_default() += 1;
+        }
+    }
+
+    /// Returns how many parts have been accepted for `field_name` so far.
+    ///
+    /// File fields are always counted; text fields are counted only when they
+    /// are declared in a [`Selector::Fields`] selector.
+    pub fn field_count(&self, field_name: &str) -> usize {
+        self.counts.get(field_name).copied().unwrap_or(0)
+    }
+
+    /// Checks that every field declaring a `min_count` was seen often enough.
+    ///
+    /// Text fields recorded as empty do not count towards the minimum. Call
+    /// this once the stream is exhausted; fields are checked in selector order.
+    pub fn validate_required_fields(&self) -> Result<(), MulterError> {
+        let Selector::Fields(fields) = &self.selector else {
+            return Ok(());
+        };
+
+        for field in fields.iter().filter(|field| field.min_count > 0) {
+            let empty = self
+                .empty_text_counts
+                .get(&field.name)
+                .copied()
+                .unwrap_or(0);
+            if self.field_count(&field.name).saturating_sub(empty) < field.min_count {
+                return Err(MulterError::RequiredFieldMissing {
+                    field: field.name.clone(),
+                    min_count: field.min_count,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Applies selector rules for a text field and returns the action.
     pub fn evaluate_text_field(&self, field_name: &str) -> Result<SelectorAction, MulterError> {
         match &self.selector {
@@ -76,18 +197,41 @@ impl SelectorEngine {
                 }
                 Ok(SelectorAction::Accept)
             }
-            Selector::Single { .. } | Selector::Array { .. } | Selector::None | Selector::Any => {
-                Ok(SelectorAction::Accept)
-            }
+            _ => Ok(SelectorAction::Accept),
         }
     }
 
+    #[cfg(feature = "regex")]
+    fn matches_pattern(&self, field_name: &str) -> bool {
+        self.pattern
+            .as_ref()
+            .is_some_and(|pattern| pattern.is_match(field_name))
+    }
+
+    /// Without the `regex` feature a [`Selector::Regex`] matches no field names.
+    #[cfg(not(feature = "regex"))]
+    fn matches_pattern(&self, _field_name: &str) -> bool {
+        false
+    }
+
     fn handle_unknown_field(&self, field_name: &str) -> Result<SelectorAction, MulterError> {
-        match self.unknown_field_policy {
-            UnknownFieldPolicy::Reject => Err(MulterError::UnexpectedField {
+        let action = match &self.unknown_field_policy {
+            UnknownFieldPolicy::Reject => UnknownFieldAction::Reject,
+            UnknownFieldPolicy::Ignore | UnknownFieldPolicy::Redirect { .. } => {
+                UnknownFieldAction::Ignore
+            }
+            UnknownFieldPolicy::Callback(decide) => decide(field_name),
+        };
+        match action {
+            UnknownFieldAction::Reject => Err(MulterError::UnexpectedField {
                 field: field_name.to_owned(),
             }),
-            UnknownFieldPolicy::Ignore => Ok(SelectorAction::Ignore),
+            UnknownFieldAction::Ignore => Ok(SelectorAction::Ignore),
+            UnknownFieldAction::IgnoreAndLog => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(field_name = field_name, "selector: ignoring unknown field");
+                Ok(SelectorAction::Ignore)
+            }
         }
     }
 
@@ -116,6 +260,13 @@ impl SelectorEngine {
             .map(|rules| rules.allowed_mime_types.as_slice())
     }
 
+    /// Returns filename extensions configured for a selected field, if present.
+    pub fn field_allowed_extensions(&self, field_name: &str) -> Option<&[String]> {
+        self.fields
+            .get(field_name)
+            .map(|rules| rules.allowed_extensions.as_slice())
+    }
+
     /// Returns the configured text size limit for a selected field, if present.
     pub fn field_text_max_size(&self, field_name: &str) -> Option<u64> {
         self.fields.get(field_name).and_then(|rules| {
@@ -134,6 +285,7 @@ struct FieldRules {
     max_count: Option<usize>,
     max_size: Option<u64>,
     allowed_mime_types: Vec<String>,
+    allowed_extensions: Vec<String>,
 }
 
 fn build_fields_map(selector: &Selector) -> HashMap<String, FieldRules> {
@@ -144,8 +296,10 @@ fn build_fields_map(selector: &Selector) -> HashMap<String, FieldRules> {
                 name,
                 kind,
                 max_count,
+                min_count: _,
                 max_size,
                 allowed_mime_types,
+                allowed_extensions,
             } in fields
             {
                 map.insert(
@@ -155,6 +309,7 @@ fn build_fields_map(selector: &Selector) -> HashMap<String, FieldRules> {
                         max_count: *max_count,
                         max_size: *max_size,
                         allowed_mime_types: allowed_mime_types.clone(),
+                        allowed_extensions: allowed_extensions.clone(),
                     },
                 );
             }
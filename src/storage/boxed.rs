@@ -0,0 +1,198 @@
+use std::{collections::HashMap, fmt};
+
+use bytes::Bytes;
+
+use super::{BoxStream, StorageEngine, StoredFile};
+use crate::{MulterError, StorageError};
+
+/// Object-safe storage contract with concrete [`StoredFile`] and
+/// [`StorageError`] types.
+///
+/// Every `StorageEngine<Output = StoredFile, Error = StorageError>` implements
+/// it, so differently typed backends can share one collection such as
+/// `HashMap<String, Box<dyn DynStorageEngine>>` and be routed by name.
+#[async_trait::async_trait]
+pub trait DynStorageEngine: Send + Sync + 'static {
+    /// Stores a file stream; see [`StorageEngine::store_sized`].
+    async fn store_dyn(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<StoredFile, StorageError>;
+
+    /// Stores a file stream with extra metadata; see
+    /// [`StorageEngine::store_full`].
+    async fn store_full_dyn(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        extra: &HashMap<String, String>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<StoredFile, StorageError>;
+
+    /// Returns whether the backend sniffs MIME types; see
+    /// [`StorageEngine::sniffs_mime`].
+    fn sniffs_mime_dyn(&self) -> bool {
+        false
+    }
+
+    /// Removes a stored file; see [`StorageEngine::discard`].
+    async fn discard_dyn(&self, output: StoredFile) -> Result<(), StorageError>;
+
+    /// Boxes this backend as a trait object.
+    fn into_boxed(self) -> Box<dyn DynStorageEngine>
+    where
+        Self: Sized,
+    {
+        Box::new(self)
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> DynStorageEngine for S
+where
+    S: StorageEngine<Output = StoredFile, Error = StorageError>,
+{
+    async fn store_dyn(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<StoredFile, StorageError> {
+        self.store_sized(field_name, file_name, content_type, size_hint, stream)
+            .await
+    }
+
+    async fn store_full_dyn(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        extra: &HashMap<String, String>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<StoredFile, StorageError> {
+        self.store_full(
+            field_name,
+            file_name,
+            content_type,
+            size_hint,
+            extra,
+            stream,
+        )
+        .await
+    }
+
+    fn sniffs_mime_dyn(&self) -> bool {
+        self.sniffs_mime()
+    }
+
+    async fn discard_dyn(&self, output: StoredFile) -> Result<(), StorageError> {
+        self.discard(output).await
+    }
+}
+
+/// Storage engine chosen at runtime behind a trait object.
+///
+/// `Multer<BoxedStorage>` has the same type whichever backend it wraps, so it
+/// can sit in shared framework state while the backend is picked from
+/// configuration.
+pub struct BoxedStorage {
+    inner: Box<dyn DynStorageEngine>,
+}
+
+impl BoxedStorage {
+    /// Boxes `storage` behind a trait object.
+    pub fn new<S>(storage: S) -> Self
+    where
+        S: DynStorageEngine,
+    {
+        Self {
+            inner: storage.into_boxed(),
+        }
+    }
+
+    /// Returns the wrapped storage engine.
+    pub fn inner(&self) -> &dyn DynStorageEngine {
+        self.inner.as_ref()
+    }
+}
+
+impl From<Box<dyn DynStorageEngine>> for BoxedStorage {
+    fn from(inner: Box<dyn DynStorageEngine>) -> Self {
+        Self { inner }
+    }
+}
+
+impl fmt::Debug for BoxedStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxedStorage").finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl StorageEngine for BoxedStorage {
+    type Output = StoredFile;
+    type Error = StorageError;
+
+    async fn store(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.inner
+            .store_dyn(field_name, file_name, content_type, None, stream)
+            .await
+    }
+
+    async fn store_sized(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.inner
+            .store_dyn(field_name, file_name, content_type, size_hint, stream)
+            .await
+    }
+
+    async fn store_full(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        extra: &HashMap<String, String>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.inner
+            .store_full_dyn(
+                field_name,
+                file_name,
+                content_type,
+                size_hint,
+                extra,
+                stream,
+            )
+            .await
+    }
+
+    fn sniffs_mime(&self) -> bool {
+        self.inner.sniffs_mime_dyn()
+    }
+
+    async fn discard(&self, output: Self::Output) -> Result<(), Self::Error> {
+        self.inner.discard_dyn(output).await
+    }
+}
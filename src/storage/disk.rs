@@ -1,19 +1,40 @@
 use std::{
-    fmt,
+    fmt::{self, Write as _},
+    future::Future,
+    hash::{Hash, Hasher},
+    io,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 use bytes::Bytes;
-use futures::StreamExt;
-use tokio::io::AsyncWriteExt;
+use futures::{future::BoxFuture, stream, Stream, StreamExt};
+use md5::Digest as _;
+use tokio::{
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWrite, AsyncWriteExt, BufWriter},
+    sync::Semaphore,
+};
+use tokio_util::io::StreamReader;
 use uuid::Uuid;
 
-use super::{BoxStream, FileMeta, StorageEngine, StoredFile};
+use super::{
+    is_generic_mime, report_progress, sniff_mime, BoxStream, FileMeta, ProgressFn, StorageEngine,
+    StoredFile,
+};
 use crate::{MulterError, StorageError};
 
 type CustomFilenameFn = dyn Fn(String) -> String + Send + Sync;
+type AsyncCustomFilenameFn = dyn Fn(String, &FileMeta) -> BoxFuture<'static, String> + Send + Sync;
 type FileFilterFn = dyn Fn(&FileMeta) -> bool + Send + Sync;
+type AsyncFileFilterFn = dyn Fn(FileMeta) -> BoxFuture<'static, bool> + Send + Sync;
+type DiskSpaceProbeFn = dyn Fn() -> u64 + Send + Sync;
+
+const DEFAULT_RETRY_INITIAL_DELAY: Duration = Duration::from_millis(10);
+const DEFAULT_DIR_PERMISSIONS: u32 = 0o755;
 
 /// Strategy used to derive the final stored filename.
 #[derive(Clone)]
@@ -22,8 +43,23 @@ pub enum FilenameStrategy {
     Keep,
     /// Always generate a random filename.
     Random,
+    /// Generate a random stem but keep the sanitized original extension.
+    ///
+    /// Only the last extension is kept (`archive.tar.gz` becomes `<uuid>.gz`);
+    /// names without a usable extension get a bare random stem.
+    KeepExtension,
+    /// Use the current UTC time, formatted with `chrono` strftime syntax, as the stem.
+    ///
+    /// The sanitized original extension is appended when there is one. Files
+    /// stored within the same timestamp get the usual collision suffix.
+    Timestamp {
+        /// `chrono` strftime format, e.g. `"%Y%m%dT%H%M%S%.3f"`.
+        format: String,
+    },
     /// Apply a user-provided filename transform.
     Custom(Arc<CustomFilenameFn>),
+    /// Apply a user-provided async filename transform that also sees file metadata.
+    AsyncCustom(Arc<AsyncCustomFilenameFn>),
 }
 
 impl fmt::Debug for FilenameStrategy {
@@ -31,17 +67,217 @@ impl fmt::Debug for FilenameStrategy {
         match self {
             Self::Keep => f.write_str("Keep"),
             Self::Random => f.write_str("Random"),
+            Self::KeepExtension => f.write_str("KeepExtension"),
+            Self::Timestamp { format } => {
+                f.debug_struct("Timestamp").field("format", format).finish()
+            }
             Self::Custom(_) => f.write_str("Custom(<fn>)"),
+            Self::AsyncCustom(_) => f.write_str("AsyncCustom(<fn>)"),
+        }
+    }
+}
+
+/// Serializes built-in strategies as their variant name.
+///
+/// `Custom` and `AsyncCustom` wrap closures and fail to serialize.
+#[cfg(feature = "serde")]
+impl serde::Serialize for FilenameStrategy {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let variant = match self {
+            Self::Keep => FilenameStrategyRepr::Keep,
+            Self::Random => FilenameStrategyRepr::Random,
+            Self::KeepExtension => FilenameStrategyRepr::KeepExtension,
+            Self::Timestamp { format } => FilenameStrategyRepr::Timestamp {
+                format: format.clone(),
+            },
+            Self::Custom(_) | Self::AsyncCustom(_) => {
+                return Err(serde::ser::Error::custom(
+                    "custom filename strategies cannot be serialized",
+                ))
+            }
+        };
+        variant.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FilenameStrategy {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match FilenameStrategyRepr::deserialize(deserializer)? {
+            FilenameStrategyRepr::Keep => Self::Keep,
+            FilenameStrategyRepr::Random => Self::Random,
+            FilenameStrategyRepr::KeepExtension => Self::KeepExtension,
+            FilenameStrategyRepr::Timestamp { format } => Self::Timestamp { format },
+        })
+    }
+}
+
+/// Serde representation of the closure-free [`FilenameStrategy`] variants.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+enum FilenameStrategyRepr {
+    Keep,
+    Random,
+    KeepExtension,
+    Timestamp { format: String },
+}
+
+/// Custom strategies compare equal only when they share the same underlying closure.
+impl PartialEq for FilenameStrategy {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Keep, Self::Keep)
+            | (Self::Random, Self::Random)
+            | (Self::KeepExtension, Self::KeepExtension) => true,
+            (Self::Timestamp { format: left }, Self::Timestamp { format: right }) => left == right,
+            (Self::Custom(left), Self::Custom(right)) => Arc::ptr_eq(left, right),
+            (Self::AsyncCustom(left), Self::AsyncCustom(right)) => Arc::ptr_eq(left, right),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for FilenameStrategy {}
+
+impl Hash for FilenameStrategy {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Keep => 0u8.hash(state),
+            Self::Random => 1u8.hash(state),
+            Self::Custom(transform) => {
+                2u8.hash(state);
+                Arc::as_ptr(transform).cast::<()>().hash(state);
+            }
+            Self::AsyncCustom(transform) => {
+                3u8.hash(state);
+                Arc::as_ptr(transform).cast::<()>().hash(state);
+            }
+            Self::KeepExtension => 4u8.hash(state),
+            Self::Timestamp { format } => {
+                5u8.hash(state);
+                format.hash(state);
+            }
         }
     }
 }
 
+impl FilenameStrategy {
+    /// Returns `true` for [`FilenameStrategy::Keep`].
+    pub fn is_keep(&self) -> bool {
+        matches!(self, Self::Keep)
+    }
+
+    /// Returns `true` for [`FilenameStrategy::Random`].
+    pub fn is_random(&self) -> bool {
+        matches!(self, Self::Random)
+    }
+
+    /// Returns `true` for user-provided transforms, sync or async.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_) | Self::AsyncCustom(_))
+    }
+}
+
+/// Controls how [`DiskStorage`] treats its root directory before writing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CreateMode {
+    /// Always call `create_dir_all` on the root before writing.
+    #[default]
+    AlwaysCreate,
+    /// Require the root to already exist and fail otherwise.
+    MustExist,
+    /// Create the root only when it does not exist yet.
+    CreateIfAbsent,
+}
+
+/// Controls what [`DiskStorage`] does when the chosen output path already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollisionPolicy {
+    /// Append a random suffix to the file stem.
+    #[default]
+    Suffix,
+    /// Replace the existing file.
+    Overwrite,
+    /// Fail with [`StorageError::FileAlreadyExists`].
+    Error,
+}
+
+/// Content hash computed by [`DiskStorage`] while a file is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DigestAlgorithm {
+    /// SHA-256.
+    Sha256,
+    /// MD5, for legacy integrations such as `Content-MD5` checks.
+    Md5,
+    /// Do not compute a digest.
+    #[default]
+    None,
+}
+
+/// Running hash state for a [`DigestAlgorithm`].
+enum RunningDigest {
+    Sha256(sha2::Sha256),
+    Md5(md5::Md5),
+}
+
+impl RunningDigest {
+    fn new(algorithm: DigestAlgorithm) -> Option<Self> {
+        match algorithm {
+            DigestAlgorithm::Sha256 => Some(Self::Sha256(sha2::Sha256::new())),
+            DigestAlgorithm::Md5 => Some(Self::Md5(md5::Md5::new())),
+            DigestAlgorithm::None => None,
+        }
+    }
+
+    fn update(&mut self, chunk: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(chunk),
+            Self::Md5(hasher) => hasher.update(chunk),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        let bytes = match self {
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+            Self::Md5(hasher) => hasher.finalize().to_vec(),
+        };
+        bytes
+            .iter()
+            .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            })
+    }
+}
+
 /// Builder for [`DiskStorage`].
 #[derive(Clone)]
 pub struct DiskStorageBuilder {
     root: PathBuf,
     strategy: FilenameStrategy,
+    filename_prefix: Option<String>,
+    filename_suffix: Option<String>,
     filter: Option<Arc<FileFilterFn>>,
+    async_filter: Option<Arc<AsyncFileFilterFn>>,
+    create_mode: CreateMode,
+    collision_policy: CollisionPolicy,
+    use_copy_buf: bool,
+    write_retries: usize,
+    retry_initial_delay: Duration,
+    max_total_files: Option<u64>,
+    max_concurrent_writes: Option<usize>,
+    check_disk_space: bool,
+    disk_space_probe: Option<Arc<DiskSpaceProbeFn>>,
+    on_progress: Option<Arc<ProgressFn>>,
+    atomic: bool,
+    sniff_mime: bool,
+    digest: DigestAlgorithm,
+    storage_key_from_digest: bool,
+    verify_write: bool,
+    date_subdirectory: Option<String>,
+    field_subdirectory: bool,
+    file_permissions: Option<u32>,
+    dir_permissions: u32,
 }
 
 impl fmt::Debug for DiskStorageBuilder {
@@ -49,7 +285,38 @@ impl fmt::Debug for DiskStorageBuilder {
         f.debug_struct("DiskStorageBuilder")
             .field("root", &self.root)
             .field("strategy", &self.strategy)
+            .field("filename_prefix", &self.filename_prefix)
+            .field("filename_suffix", &self.filename_suffix)
             .field("filter", &self.filter.as_ref().map(|_| "<fn>"))
+            .field("async_filter", &self.async_filter.as_ref().map(|_| "<fn>"))
+            .field("create_mode", &self.create_mode)
+            .field("collision_policy", &self.collision_policy)
+            .field("use_copy_buf", &self.use_copy_buf)
+            .field("write_retries", &self.write_retries)
+            .field("retry_initial_delay", &self.retry_initial_delay)
+            .field("max_total_files", &self.max_total_files)
+            .field("max_concurrent_writes", &self.max_concurrent_writes)
+            .field("check_disk_space", &self.check_disk_space)
+            .field(
+                "disk_space_probe",
+                &self.disk_space_probe.as_ref().map(|_| "<fn>"),
+            )
+            .field("on_progress", &self.on_progress.as_ref().map(|_| "<fn>"))
+            .field("atomic", &self.atomic)
+            .field("sniff_mime", &self.sniff_mime)
+            .field("digest", &self.digest)
+            .field("storage_key_from_digest", &self.storage_key_from_digest)
+            .field("verify_write", &self.verify_write)
+            .field("date_subdirectory", &self.date_subdirectory)
+            .field("field_subdirectory", &self.field_subdirectory)
+            .field(
+                "file_permissions",
+                &self.file_permissions.map(|mode| format!("{mode:#o}")),
+            )
+            .field(
+                "dir_permissions",
+                &format_args!("{:#o}", self.dir_permissions),
+            )
             .finish()
     }
 }
@@ -78,7 +345,20 @@ impl DiskStorageBuilder {
     }
 
     /// Sets a custom filename function.
-    pub fn custom_filename<F>(mut self, transform: F) -> Self
+    #[deprecated(note = "use `DiskStorageBuilder::filename_transform`")]
+    pub fn custom_filename<F>(self, transform: F) -> Self
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.filename_transform(transform)
+    }
+
+    /// Derives stored filenames with `transform`, selecting
+    /// [`FilenameStrategy::Custom`].
+    ///
+    /// `transform` receives the original filename, or a random name when the
+    /// part has none. Its result is sanitized like every other strategy.
+    pub fn filename_transform<F>(mut self, transform: F) -> Self
     where
         F: Fn(String) -> String + Send + Sync + 'static,
     {
@@ -86,6 +366,36 @@ impl DiskStorageBuilder {
         self
     }
 
+    /// Prepends `prefix` to every stored filename.
+    ///
+    /// It applies on top of the filename strategy, so with
+    /// [`FilenameStrategy::Keep`] `report.pdf` becomes `<prefix>report.pdf`.
+    /// The combined name is sanitized again.
+    pub fn filename_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.filename_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Appends `suffix` to every stored filename, before its extension.
+    ///
+    /// With [`FilenameStrategy::Keep`], `report.pdf` becomes
+    /// `report<suffix>.pdf`. The combined name is sanitized again.
+    pub fn filename_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.filename_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Sets an async filename function that also receives the file metadata.
+    ///
+    /// The returned name is sanitized like every other strategy.
+    pub fn custom_filename_async<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(String, &FileMeta) -> BoxFuture<'static, String> + Send + Sync + 'static,
+    {
+        self.strategy = FilenameStrategy::AsyncCustom(Arc::new(transform));
+        self
+    }
+
     /// Sets an optional filter to accept or reject files before persistence.
     pub fn filter<F>(mut self, filter: F) -> Self
     where
@@ -95,16 +405,270 @@ impl DiskStorageBuilder {
         self
     }
 
+    /// Sets an async filter awaited before any bytes are written.
+    ///
+    /// Use it for checks that need I/O, such as a virus-scan API call. When a
+    /// sync [`DiskStorageBuilder::filter`] is also set, it runs first and both
+    /// must accept the file.
+    pub fn async_filter<F, Fut>(mut self, filter: F) -> Self
+    where
+        F: Fn(FileMeta) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        self.async_filter = Some(Arc::new(move |meta| Box::pin(filter(meta))));
+        self
+    }
+
+    /// Sets a callback invoked with `(bytes_written, size_hint)` after every chunk.
+    ///
+    /// `size_hint` is the part's `Content-Length` when files are stored through
+    /// [`crate::Multer::store`] and the part declares one.
+    pub fn on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: Fn(u64, Option<u64>) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(on_progress));
+        self
+    }
+
+    /// Writes each file to a temporary `.tmp` sibling and renames it into place on success.
+    ///
+    /// Failed or cancelled stores remove the temporary file, so no partial
+    /// file is ever visible at the final path. Defaults to `false`.
+    pub fn atomic(mut self, enabled: bool) -> Self {
+        self.atomic = enabled;
+        self
+    }
+
+    /// Detects the real MIME type from the first bytes of generically typed files.
+    ///
+    /// When a part has no `Content-Type` or declares `application/octet-stream`,
+    /// up to 512 leading bytes are inspected and a recognised signature
+    /// replaces the declared type in [`StoredFile::content_type`]. Through
+    /// [`Multer`](crate::Multer), the detected type is checked against the
+    /// global and per-field MIME allowlists. Defaults to `false`.
+    pub fn sniff_mime(mut self, enabled: bool) -> Self {
+        self.sniff_mime = enabled;
+        self
+    }
+
+    /// Computes a content digest while writing and reports it in [`StoredFile::digest`].
+    ///
+    /// The digest is lowercase hex over exactly the bytes written to disk.
+    /// Defaults to [`DigestAlgorithm::None`].
+    pub fn compute_digest(mut self, algorithm: DigestAlgorithm) -> Self {
+        self.digest = algorithm;
+        self
+    }
+
+    /// Uses the content digest as [`StoredFile::storage_key`] instead of the file path.
+    ///
+    /// Identical uploads then share a key, which enables content-addressable
+    /// lookups and deduplication. Requires [`DiskStorageBuilder::compute_digest`].
+    pub fn storage_key_from_digest(mut self, enabled: bool) -> Self {
+        self.storage_key_from_digest = enabled;
+        self
+    }
+
+    /// Reads every file back after writing it and compares content digests.
+    ///
+    /// A mismatch fails the store with [`StorageError::WriteVerificationFailed`]
+    /// and removes the file. This doubles the I/O per file and is meant as a
+    /// safety net on unreliable filesystems such as NFS or FUSE mounts. The
+    /// [`DiskStorageBuilder::compute_digest`] algorithm is reused when set,
+    /// SHA-256 otherwise. Defaults to `false`.
+    pub fn verify_write(mut self, enabled: bool) -> Self {
+        self.verify_write = enabled;
+        self
+    }
+
+    /// Writes files under `root/<date>/`, formatting the current UTC time with `format`.
+    ///
+    /// `format` uses `chrono` strftime syntax and may contain `/` to nest
+    /// directories, e.g. `"%Y/%m/%d"`. Combined with
+    /// [`DiskStorageBuilder::subdirectory_by_field`], files land under
+    /// `root/<field_name>/<date>/`.
+    pub fn subdirectory_by_date(mut self, format: &str) -> Self {
+        self.date_subdirectory = Some(format.to_owned());
+        self
+    }
+
+    /// Writes files under `root/<field_name>/` when enabled.
+    ///
+    /// The field name is sanitized the same way as filenames.
+    pub fn subdirectory_by_field(mut self, enabled: bool) -> Self {
+        self.field_subdirectory = enabled;
+        self
+    }
+
+    /// Sets how the root directory is created before files are written.
+    pub fn with_create_mode(mut self, mode: CreateMode) -> Self {
+        self.create_mode = mode;
+        self
+    }
+
+    /// Sets what happens when the output path of a file already exists.
+    ///
+    /// The existence check and the write are not atomic, so a file created
+    /// concurrently in between may still be replaced.
+    pub fn on_collision(mut self, policy: CollisionPolicy) -> Self {
+        self.collision_policy = policy;
+        self
+    }
+
+    /// Sets whether file bodies are written with buffered `copy_buf`.
+    ///
+    /// When enabled (the default), the part stream is adapted into an
+    /// `AsyncBufRead` and copied into a buffered file writer, which coalesces
+    /// small chunks into fewer writes. Disable to write each chunk directly.
+    pub fn use_copy_buf(mut self, enabled: bool) -> Self {
+        self.use_copy_buf = enabled;
+        self
+    }
+
+    /// Sets how many times a transiently failing write or flush is retried.
+    ///
+    /// Only `Interrupted` and `WouldBlock` errors are retried; every other
+    /// I/O error fails the store immediately. With retries enabled, each chunk
+    /// is flushed to the file before the next one is written, and a failed
+    /// chunk is rewritten after reopening the file and truncating it to the
+    /// bytes already confirmed, so [`DiskStorageBuilder::use_copy_buf`] has no
+    /// effect. Defaults to `0`.
+    pub fn write_retries(mut self, retries: usize) -> Self {
+        self.write_retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first write retry; it doubles after each attempt.
+    pub fn retry_initial_delay(mut self, delay: Duration) -> Self {
+        self.retry_initial_delay = delay;
+        self
+    }
+
+    /// Caps the number of files this storage instance (and its clones) will write.
+    ///
+    /// The count is kept in memory and spans every request served by the
+    /// instance; files that existed before it was built are not counted.
+    pub fn max_total_files(mut self, max_files: usize) -> Self {
+        self.max_total_files = Some(max_files as u64);
+        self
+    }
+
+    /// Caps how many files this storage instance (and its clones) writes at once.
+    ///
+    /// A store waits for a free slot before opening its output file and
+    /// releases it when the write finishes or the store future is dropped.
+    /// Must be greater than zero.
+    pub fn max_concurrent_writes(mut self, limit: usize) -> Self {
+        self.max_concurrent_writes = Some(limit);
+        self
+    }
+
+    /// Sets the Unix permission bits applied to every stored file.
+    ///
+    /// The mode is set explicitly after the file is created, so the process
+    /// umask does not widen or narrow it. When unset (the default), files keep
+    /// the mode the process umask gives them.
+    #[cfg(unix)]
+    pub fn file_permissions(mut self, mode: u32) -> Self {
+        self.file_permissions = Some(mode);
+        self
+    }
+
+    /// Sets the Unix permission bits used when creating the root and its subdirectories.
+    ///
+    /// Only directories created by the storage are affected, and the process
+    /// umask still applies. Defaults to `0o755`.
+    #[cfg(unix)]
+    pub fn dir_permissions(mut self, mode: u32) -> Self {
+        self.dir_permissions = mode;
+        self
+    }
+
+    /// Checks free space on the root's filesystem before each file is created.
+    ///
+    /// A store whose declared size (the part's `Content-Length`) exceeds the
+    /// available bytes fails with [`StorageError::InsufficientDiskSpace`]
+    /// before anything is written. Stores without a size hint skip the check.
+    /// The free space is queried with `statvfs` on Unix and
+    /// `GetDiskFreeSpaceEx` on Windows, unless
+    /// [`DiskStorageBuilder::disk_space_probe`] replaces the query. Defaults to
+    /// `false`.
+    pub fn check_disk_space(mut self, enabled: bool) -> Self {
+        self.check_disk_space = enabled;
+        self
+    }
+
+    /// Replaces the platform free-space query used by [`DiskStorageBuilder::check_disk_space`].
+    ///
+    /// The probe returns the number of bytes available to the storage root.
+    pub fn disk_space_probe<F>(mut self, probe: F) -> Self
+    where
+        F: Fn() -> u64 + Send + Sync + 'static,
+    {
+        self.disk_space_probe = Some(Arc::new(probe));
+        self
+    }
+
     /// Builds a validated disk storage backend.
     pub fn build(self) -> Result<DiskStorage, StorageError> {
         if self.root.as_os_str().is_empty() {
             return Err(StorageError::new("disk storage root path cannot be empty"));
         }
+        if self.max_concurrent_writes == Some(0) {
+            return Err(StorageError::new(
+                "disk storage max_concurrent_writes must be greater than zero",
+            ));
+        }
+        if self.storage_key_from_digest && self.digest == DigestAlgorithm::None {
+            return Err(StorageError::new(
+                "disk storage storage_key_from_digest requires a digest algorithm",
+            ));
+        }
+        if let Some(format) = &self.date_subdirectory {
+            if !is_valid_time_format(format) {
+                return Err(StorageError::new(format!(
+                    "disk storage subdirectory date format `{format}` is invalid"
+                )));
+            }
+        }
+        if let FilenameStrategy::Timestamp { format } = &self.strategy {
+            if !is_valid_time_format(format) {
+                return Err(StorageError::new(format!(
+                    "disk storage timestamp filename format `{format}` is invalid"
+                )));
+            }
+        }
 
         Ok(DiskStorage {
             root: self.root,
             strategy: self.strategy,
+            filename_prefix: self.filename_prefix,
+            filename_suffix: self.filename_suffix,
             filter: self.filter,
+            async_filter: self.async_filter,
+            create_mode: self.create_mode,
+            collision_policy: self.collision_policy,
+            use_copy_buf: self.use_copy_buf,
+            write_retries: self.write_retries,
+            retry_initial_delay: self.retry_initial_delay,
+            max_total_files: self.max_total_files,
+            files_stored: Arc::new(AtomicU64::new(0)),
+            write_permits: self
+                .max_concurrent_writes
+                .map(|limit| Arc::new(Semaphore::new(limit))),
+            check_disk_space: self.check_disk_space,
+            disk_space_probe: self.disk_space_probe,
+            on_progress: self.on_progress,
+            atomic: self.atomic,
+            sniff_mime: self.sniff_mime,
+            digest: self.digest,
+            storage_key_from_digest: self.storage_key_from_digest,
+            verify_write: self.verify_write,
+            date_subdirectory: self.date_subdirectory,
+            field_subdirectory: self.field_subdirectory,
+            file_permissions: self.file_permissions,
+            dir_permissions: self.dir_permissions,
         })
     }
 }
@@ -114,7 +678,29 @@ impl Default for DiskStorageBuilder {
         Self {
             root: std::env::temp_dir().join("multigear"),
             strategy: FilenameStrategy::Random,
+            filename_prefix: None,
+            filename_suffix: None,
             filter: None,
+            async_filter: None,
+            create_mode: CreateMode::default(),
+            collision_policy: CollisionPolicy::default(),
+            use_copy_buf: true,
+            write_retries: 0,
+            retry_initial_delay: DEFAULT_RETRY_INITIAL_DELAY,
+            max_total_files: None,
+            max_concurrent_writes: None,
+            check_disk_space: false,
+            disk_space_probe: None,
+            on_progress: None,
+            atomic: false,
+            sniff_mime: false,
+            digest: DigestAlgorithm::None,
+            storage_key_from_digest: false,
+            verify_write: false,
+            date_subdirectory: None,
+            field_subdirectory: false,
+            file_permissions: None,
+            dir_permissions: DEFAULT_DIR_PERMISSIONS,
         }
     }
 }
@@ -124,7 +710,30 @@ impl Default for DiskStorageBuilder {
 pub struct DiskStorage {
     root: PathBuf,
     strategy: FilenameStrategy,
+    filename_prefix: Option<String>,
+    filename_suffix: Option<String>,
     filter: Option<Arc<FileFilterFn>>,
+    async_filter: Option<Arc<AsyncFileFilterFn>>,
+    create_mode: CreateMode,
+    collision_policy: CollisionPolicy,
+    use_copy_buf: bool,
+    write_retries: usize,
+    retry_initial_delay: Duration,
+    max_total_files: Option<u64>,
+    files_stored: Arc<AtomicU64>,
+    write_permits: Option<Arc<Semaphore>>,
+    check_disk_space: bool,
+    disk_space_probe: Option<Arc<DiskSpaceProbeFn>>,
+    on_progress: Option<Arc<ProgressFn>>,
+    atomic: bool,
+    sniff_mime: bool,
+    digest: DigestAlgorithm,
+    storage_key_from_digest: bool,
+    verify_write: bool,
+    date_subdirectory: Option<String>,
+    field_subdirectory: bool,
+    file_permissions: Option<u32>,
+    dir_permissions: u32,
 }
 
 impl fmt::Debug for DiskStorage {
@@ -132,7 +741,45 @@ impl fmt::Debug for DiskStorage {
         f.debug_struct("DiskStorage")
             .field("root", &self.root)
             .field("strategy", &self.strategy)
+            .field("filename_prefix", &self.filename_prefix)
+            .field("filename_suffix", &self.filename_suffix)
             .field("filter", &self.filter.as_ref().map(|_| "<fn>"))
+            .field("async_filter", &self.async_filter.as_ref().map(|_| "<fn>"))
+            .field("create_mode", &self.create_mode)
+            .field("collision_policy", &self.collision_policy)
+            .field("use_copy_buf", &self.use_copy_buf)
+            .field("write_retries", &self.write_retries)
+            .field("retry_initial_delay", &self.retry_initial_delay)
+            .field("max_total_files", &self.max_total_files)
+            .field("files_stored", &self.files_stored())
+            .field(
+                "available_write_permits",
+                &self
+                    .write_permits
+                    .as_ref()
+                    .map(|permits| permits.available_permits()),
+            )
+            .field("check_disk_space", &self.check_disk_space)
+            .field(
+                "disk_space_probe",
+                &self.disk_space_probe.as_ref().map(|_| "<fn>"),
+            )
+            .field("on_progress", &self.on_progress.as_ref().map(|_| "<fn>"))
+            .field("atomic", &self.atomic)
+            .field("sniff_mime", &self.sniff_mime)
+            .field("digest", &self.digest)
+            .field("storage_key_from_digest", &self.storage_key_from_digest)
+            .field("verify_write", &self.verify_write)
+            .field("date_subdirectory", &self.date_subdirectory)
+            .field("field_subdirectory", &self.field_subdirectory)
+            .field(
+                "file_permissions",
+                &self.file_permissions.map(|mode| format!("{mode:#o}")),
+            )
+            .field(
+                "dir_permissions",
+                &format_args!("{:#o}", self.dir_permissions),
+            )
             .finish()
     }
 }
@@ -143,23 +790,367 @@ impl DiskStorage {
         DiskStorageBuilder::default()
     }
 
-    fn choose_output_name(&self, file_name: Option<&str>) -> String {
-        let input_name = file_name
-            .map(ToOwned::to_owned)
-            .unwrap_or_else(random_basename);
+    /// Returns how many files have been stored through this instance and its clones.
+    pub fn files_stored(&self) -> u64 {
+        self.files_stored.load(Ordering::Acquire)
+    }
+
+    /// Lists files previously written under `root`, oldest first.
+    ///
+    /// Subdirectories are walked recursively. The multipart context is not
+    /// persisted, so `field_name` is empty, `file_name` is the basename on
+    /// disk and `content_type` is guessed from the extension.
+    pub async fn list_stored_files(root: &Path) -> Result<Vec<StoredFile>, io::Error> {
+        let mut files = Vec::new();
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let mut entries = tokio::fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let file_type = entry.file_type().await?;
+                let path = entry.path();
+                if file_type.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                if !file_type.is_file() {
+                    continue;
+                }
+                let metadata = entry.metadata().await?;
+                let file = StoredFile {
+                    storage_key: path.to_string_lossy().into_owned(),
+                    field_name: String::new(),
+                    file_name: Some(entry.file_name().to_string_lossy().into_owned()),
+                    content_type: mime_guess::from_path(&path).first_or_octet_stream(),
+                    size: metadata.len(),
+                    path: Some(path),
+                    digest: None,
+                };
+                files.push((metadata.modified()?, file));
+            }
+        }
+        files.sort_by(|(left_modified, left), (right_modified, right)| {
+            left_modified
+                .cmp(right_modified)
+                .then_with(|| left.path.cmp(&right.path))
+        });
+        Ok(files.into_iter().map(|(_, file)| file).collect())
+    }
+
+    /// Reserves a slot in the total file count, failing once the limit is reached.
+    fn reserve_file_slot(&self) -> Result<(), StorageError> {
+        let previous = self.files_stored.fetch_add(1, Ordering::AcqRel);
+        if let Some(max_files) = self.max_total_files {
+            if previous >= max_files {
+                self.release_file_slot();
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    max_total_files = max_files,
+                    "disk storage: total file count limit exceeded"
+                );
+                return Err(StorageError::new("total file count limit exceeded"));
+            }
+        }
+        Ok(())
+    }
+
+    fn release_file_slot(&self) {
+        self.files_stored.fetch_sub(1, Ordering::AcqRel);
+    }
+
+    async fn choose_output_name_async(&self, meta: &FileMeta) -> String {
+        let input_name = meta.file_name.clone().unwrap_or_else(random_basename);
 
         let candidate = match &self.strategy {
             FilenameStrategy::Keep => input_name,
             FilenameStrategy::Random => random_basename(),
+            FilenameStrategy::KeepExtension => {
+                match meta.file_name.as_deref().and_then(safe_extension) {
+                    Some(extension) => format!("{}.{extension}", random_basename()),
+                    None => random_basename(),
+                }
+            }
+            FilenameStrategy::Timestamp { format } => {
+                let stem = chrono::Utc::now().format(format).to_string();
+                match meta.file_name.as_deref().and_then(safe_extension) {
+                    Some(extension) => format!("{stem}.{extension}"),
+                    None => stem,
+                }
+            }
             FilenameStrategy::Custom(transform) => transform(input_name),
+            FilenameStrategy::AsyncCustom(transform) => transform(input_name, meta).await,
         };
 
-        sanitize_filename(&candidate)
+        let name = sanitize_filename(&candidate);
+        if self.filename_prefix.is_none() && self.filename_suffix.is_none() {
+            return name;
+        }
+        let prefix = self.filename_prefix.as_deref().unwrap_or_default();
+        let suffix = self.filename_suffix.as_deref().unwrap_or_default();
+        let affixed = match safe_extension(&name) {
+            Some(extension) => {
+                let stem = &name[..name.len() - extension.len() - 1];
+                format!("{prefix}{stem}{suffix}.{extension}")
+            }
+            None => format!("{prefix}{name}{suffix}"),
+        };
+        sanitize_filename(&affixed)
+    }
+
+    /// Returns the directory a file for `field_name` is written to.
+    fn output_directory(&self, field_name: &str) -> PathBuf {
+        let mut directory = self.root.clone();
+        if self.field_subdirectory {
+            directory.push(sanitize_filename(field_name));
+        }
+        if let Some(format) = &self.date_subdirectory {
+            let formatted = chrono::Utc::now().format(format).to_string();
+            for segment in formatted
+                .split(['/', '\\'])
+                .filter(|segment| !matches!(*segment, "" | "." | ".."))
+            {
+                directory.push(sanitize_filename(segment));
+            }
+        }
+        directory
     }
 
     fn should_store(&self, meta: &FileMeta) -> bool {
         self.filter.as_ref().map_or(true, |filter| filter(meta))
     }
+
+    /// Fails when the root's filesystem has less free space than `size_hint`.
+    async fn ensure_disk_space(&self, size_hint: Option<u64>) -> Result<(), StorageError> {
+        if !self.check_disk_space {
+            return Ok(());
+        }
+        let Some(required) = size_hint else {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                root = %self.root.display(),
+                "disk storage: no size hint, skipping disk space check"
+            );
+            return Ok(());
+        };
+        let available = match &self.disk_space_probe {
+            Some(probe) => probe(),
+            None => available_disk_space(&self.root).await.map_err(|err| {
+                StorageError::new(format!("failed to query available disk space: {err}"))
+            })?,
+        };
+        if available < required {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                available = available,
+                required = required,
+                "disk storage: insufficient disk space"
+            );
+            return Err(StorageError::InsufficientDiskSpace {
+                available,
+                required,
+            });
+        }
+        Ok(())
+    }
+
+    async fn prepare_root(&self) -> Result<(), StorageError> {
+        match self.create_mode {
+            CreateMode::AlwaysCreate => self.create_root().await,
+            CreateMode::MustExist => {
+                if tokio::fs::metadata(&self.root).await.is_ok() {
+                    Ok(())
+                } else {
+                    Err(StorageError::new(format!(
+                        "storage directory `{}` does not exist",
+                        self.root.display()
+                    )))
+                }
+            }
+            CreateMode::CreateIfAbsent => {
+                let exists = tokio::fs::try_exists(&self.root).await.map_err(|err| {
+                    StorageError::new(format!("failed to inspect storage directory: {err}"))
+                })?;
+                if exists {
+                    Ok(())
+                } else {
+                    self.create_root().await
+                }
+            }
+        }
+    }
+
+    async fn create_root(&self) -> Result<(), StorageError> {
+        self.create_dir_all(&self.root)
+            .await
+            .map_err(|err| StorageError::new(format!("failed to create storage directory: {err}")))
+    }
+
+    /// Creates `path` and missing parents with the configured directory mode.
+    async fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let mut builder = tokio::fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        builder.mode(self.dir_permissions);
+        builder.create(path).await
+    }
+
+    async fn write_file(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        accepted_meta: &FileMeta,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<StoredFile, StorageError> {
+        let file_basename = self.choose_output_name_async(accepted_meta).await;
+
+        let directory = self.output_directory(field_name);
+        if directory != self.root {
+            self.create_dir_all(&directory).await.map_err(|err| {
+                StorageError::new(format!(
+                    "failed to create storage subdirectory `{}`: {err}",
+                    directory.display()
+                ))
+            })?;
+        }
+        let mut output_path = directory.join(file_basename);
+        let output_exists = tokio::fs::try_exists(&output_path).await.map_err(|err| {
+            StorageError::new(format!(
+                "failed to inspect output path `{}`: {err}",
+                output_path.display()
+            ))
+        })?;
+        if output_exists {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                path = %output_path.display(),
+                policy = ?self.collision_policy,
+                "disk storage: collision detected"
+            );
+            match self.collision_policy {
+                CollisionPolicy::Suffix => output_path = with_collision_suffix(&output_path),
+                CollisionPolicy::Overwrite => {}
+                CollisionPolicy::Error => {
+                    return Err(StorageError::FileAlreadyExists { path: output_path })
+                }
+            }
+        }
+
+        let temp_guard = self
+            .atomic
+            .then(|| TempFileGuard::new(temp_sibling(&output_path)));
+        let write_path = temp_guard
+            .as_ref()
+            .map_or(output_path.as_path(), TempFileGuard::path);
+
+        let _write_permit = match &self.write_permits {
+            Some(permits) => Some(permits.acquire().await.map_err(|_| {
+                StorageError::new("disk storage concurrent write limiter was closed")
+            })?),
+            None => None,
+        };
+        let file = tokio::fs::File::create(write_path).await.map_err(|err| {
+            StorageError::new(format!(
+                "failed to create output file `{}`: {err}",
+                write_path.display()
+            ))
+        })?;
+        #[cfg(unix)]
+        if let Some(mode) = self.file_permissions {
+            use std::os::unix::fs::PermissionsExt as _;
+            file.set_permissions(std::fs::Permissions::from_mode(mode))
+                .await
+                .map_err(|err| {
+                    StorageError::new(format!(
+                        "failed to set permissions on `{}`: {err}",
+                        write_path.display()
+                    ))
+                })?;
+        }
+        let verify_algorithm = match self.digest {
+            DigestAlgorithm::None if self.verify_write => DigestAlgorithm::Sha256,
+            algorithm => algorithm,
+        };
+        let mut digest = RunningDigest::new(verify_algorithm);
+        let stream: BoxStream<'_, Result<Bytes, MulterError>> = match digest.as_mut() {
+            Some(digest) => Box::pin(stream.inspect(move |chunk| {
+                if let Ok(chunk) = chunk {
+                    digest.update(chunk);
+                }
+            })),
+            None => stream,
+        };
+        let written = if self.write_retries > 0 {
+            write_stream_with_retries(
+                write_path,
+                file,
+                stream,
+                self.write_retries,
+                self.retry_initial_delay,
+                |len| reopen_at(write_path, len),
+            )
+            .await?
+        } else if self.use_copy_buf {
+            copy_stream_buffered(write_path, file, stream).await?
+        } else {
+            write_stream_chunks(write_path, file, stream).await?
+        };
+        let digest = digest.map(RunningDigest::finalize_hex);
+
+        if self.verify_write {
+            if let Some(expected) = &digest {
+                if let Err(err) = verify_digest(write_path, verify_algorithm, expected).await {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        path = %write_path.display(),
+                        error = %err,
+                        "disk storage: write verification failed"
+                    );
+                    if temp_guard.is_none() {
+                        let _ = tokio::fs::remove_file(write_path).await;
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        let digest = digest.filter(|_| self.digest != DigestAlgorithm::None);
+
+        if let Some(temp_guard) = temp_guard {
+            tokio::fs::rename(temp_guard.path(), &output_path)
+                .await
+                .map_err(|err| {
+                    StorageError::new(format!(
+                        "failed to move `{}` to `{}`: {err}",
+                        temp_guard.path().display(),
+                        output_path.display()
+                    ))
+                })?;
+            temp_guard.disarm();
+        }
+
+        let storage_key = match &digest {
+            Some(digest) if self.storage_key_from_digest => digest.clone(),
+            _ => output_path.to_string_lossy().into_owned(),
+        };
+        let parsed_content_type = content_type
+            .parse::<mime::Mime>()
+            .unwrap_or(mime::APPLICATION_OCTET_STREAM);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            field_name = field_name,
+            size = written,
+            path = %output_path.display(),
+            "disk storage: completed store"
+        );
+        Ok(StoredFile {
+            storage_key,
+            field_name: field_name.to_owned(),
+            file_name: file_name.map(ToOwned::to_owned),
+            content_type: parsed_content_type,
+            size: written,
+            path: Some(output_path),
+            digest,
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -172,8 +1163,32 @@ impl StorageEngine for DiskStorage {
         field_name: &str,
         file_name: Option<&str>,
         content_type: &str,
-        mut stream: BoxStream<'_, Result<Bytes, MulterError>>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
     ) -> Result<Self::Output, Self::Error> {
+        self.store_sized(field_name, file_name, content_type, None, stream)
+            .await
+    }
+
+    fn sniffs_mime(&self) -> bool {
+        self.sniff_mime
+    }
+
+    async fn store_sized(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        let (sniffed, stream) = if self.sniff_mime && is_generic_mime(content_type) {
+            sniff_mime(stream).await
+        } else {
+            (None, stream)
+        };
+        let sniffed = sniffed.map(|mime| mime.to_string());
+        let content_type = sniffed.as_deref().unwrap_or(content_type);
+
         #[cfg(feature = "tracing")]
         tracing::debug!(
             field_name = field_name,
@@ -199,71 +1214,260 @@ impl StorageEngine for DiskStorage {
                 "disk storage filter rejected file field `{field_name}`"
             )));
         }
+        if let Some(async_filter) = &self.async_filter {
+            if !async_filter(accepted_meta.clone()).await {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    field_name = field_name,
+                    file_name = file_name.unwrap_or("<none>"),
+                    "disk storage async filter rejected file"
+                );
+                return Err(StorageError::new(format!(
+                    "disk storage async filter rejected file field `{field_name}`"
+                )));
+            }
+        }
+
+        self.prepare_root().await?;
+        self.ensure_disk_space(size_hint).await?;
+        self.reserve_file_slot()?;
+        let stream = match &self.on_progress {
+            Some(on_progress) => {
+                let on_progress = Arc::clone(on_progress);
+                report_progress(stream, size_hint, move |written, total| {
+                    on_progress(written, total)
+                })
+            }
+            None => stream,
+        };
+        let result = self
+            .write_file(field_name, file_name, content_type, &accepted_meta, stream)
+            .await;
+        if result.is_err() {
+            self.release_file_slot();
+        }
+        result
+    }
 
-        tokio::fs::create_dir_all(&self.root).await.map_err(|err| {
-            StorageError::new(format!("failed to create storage directory: {err}"))
+    async fn discard(&self, output: Self::Output) -> Result<(), Self::Error> {
+        let Some(path) = output.path else {
+            return Ok(());
+        };
+        tokio::fs::remove_file(&path).await.map_err(|err| {
+            StorageError::new(format!("failed to remove `{}`: {err}", path.display()))
         })?;
+        self.release_file_slot();
+        Ok(())
+    }
+}
 
-        let file_basename = self.choose_output_name(file_name);
+async fn write_stream_chunks<W>(
+    path: &Path,
+    mut file: W,
+    mut stream: BoxStream<'_, Result<Bytes, MulterError>>,
+) -> Result<u64, StorageError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut written = 0u64;
 
-        let mut output_path = self.root.join(file_basename);
-        if tokio::fs::try_exists(&output_path)
+    while let Some(chunk) = stream.next().await {
+        let bytes = chunk.map_err(|err| StorageError::new(format!("stream read failed: {err}")))?;
+        file.write_all(&bytes)
             .await
-            .map_err(|err| StorageError::new(format!("failed to inspect output path: {err}")))?
-        {
-            #[cfg(feature = "tracing")]
-            tracing::debug!(
-                path = %output_path.display(),
-                "disk storage: collision detected, adding suffix"
-            );
-            output_path = with_collision_suffix(&output_path);
-        }
+            .map_err(|err| write_error(path, &err))?;
+        written = written.saturating_add(bytes.len() as u64);
+    }
 
-        let mut file = tokio::fs::File::create(&output_path)
-            .await
-            .map_err(|err| StorageError::new(format!("failed to create output file: {err}")))?;
+    file.flush().await.map_err(|err| flush_error(path, &err))?;
+    Ok(written)
+}
 
-        let mut written = 0u64;
+async fn copy_stream_buffered<W>(
+    path: &Path,
+    file: W,
+    stream: BoxStream<'_, Result<Bytes, MulterError>>,
+) -> Result<u64, StorageError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut reader = StreamReader::new(stream.map(|chunk| chunk.map_err(io::Error::other)));
+    let mut writer = BufWriter::new(file);
 
-        while let Some(chunk) = stream.next().await {
-            let bytes =
-                chunk.map_err(|err| StorageError::new(format!("stream read failed: {err}")))?;
-            file.write_all(&bytes)
-                .await
-                .map_err(|err| StorageError::new(format!("failed to write output file: {err}")))?;
-            written = written.saturating_add(bytes.len() as u64);
-        }
+    let written = tokio::io::copy_buf(&mut reader, &mut writer)
+        .await
+        .map_err(|err| {
+            if err.get_ref().is_some_and(|inner| inner.is::<MulterError>()) {
+                StorageError::new(format!("stream read failed: {err}"))
+            } else {
+                write_error(path, &err)
+            }
+        })?;
 
-        file.flush()
-            .await
-            .map_err(|err| StorageError::new(format!("failed to flush output file: {err}")))?;
+    writer
+        .flush()
+        .await
+        .map_err(|err| flush_error(path, &err))?;
+    Ok(written)
+}
 
-        let storage_key = output_path.to_string_lossy().into_owned();
-        let parsed_content_type = content_type
-            .parse::<mime::Mime>()
-            .unwrap_or(mime::APPLICATION_OCTET_STREAM);
-        #[cfg(feature = "tracing")]
-        tracing::debug!(
-            field_name = field_name,
-            size = written,
-            path = %output_path.display(),
-            "disk storage: completed store"
-        );
-        Ok(StoredFile {
-            storage_key,
-            field_name: field_name.to_owned(),
-            file_name: file_name.map(ToOwned::to_owned),
-            content_type: parsed_content_type,
-            size: written,
-            path: Some(output_path),
-        })
+/// Writes `stream` to `file` chunk by chunk, retrying transient failures.
+///
+/// `tokio::fs::File` accepts a buffer before writing it and reports a failure
+/// on a later call, so a chunk only counts as written once a flush succeeds.
+/// After a transient error `reopen` reopens the file at `path` truncated to the
+/// confirmed length before the chunk is written again, which neither drops nor
+/// duplicates bytes.
+async fn write_stream_with_retries<W, R, F>(
+    path: &Path,
+    mut file: W,
+    mut stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    retries: usize,
+    initial_delay: Duration,
+    mut reopen: R,
+) -> Result<u64, StorageError>
+where
+    W: AsyncWrite + Unpin,
+    R: FnMut(u64) -> F,
+    F: Future<Output = io::Result<W>>,
+{
+    let mut confirmed = 0u64;
+
+    while let Some(chunk) = stream.next().await {
+        let bytes = chunk.map_err(|err| StorageError::new(format!("stream read failed: {err}")))?;
+        let mut attempts = 0;
+        let mut delay = initial_delay;
+        loop {
+            let result = match file.write_all(&bytes).await {
+                Ok(()) => file.flush().await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(()) => break,
+                Err(err) if is_transient(&err) && attempts < retries => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        attempt = attempts + 1,
+                        delay_ms = delay.as_millis() as u64,
+                        confirmed = confirmed,
+                        error = %err,
+                        "disk storage: retrying transient write failure"
+                    );
+                    attempts += 1;
+                    tokio::time::sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                    file = reopen(confirmed).await.map_err(|err| {
+                        StorageError::new(format!(
+                            "failed to reopen output file `{}`: {err}",
+                            path.display()
+                        ))
+                    })?;
+                }
+                Err(err) => return Err(write_error(path, &err)),
+            }
+        }
+        confirmed = confirmed.saturating_add(bytes.len() as u64);
     }
+
+    Ok(confirmed)
+}
+
+fn write_error(path: &Path, err: &io::Error) -> StorageError {
+    StorageError::new(format!(
+        "failed to write output file `{}`: {err}",
+        path.display()
+    ))
+}
+
+fn flush_error(path: &Path, err: &io::Error) -> StorageError {
+    StorageError::new(format!(
+        "failed to flush output file `{}`: {err}",
+        path.display()
+    ))
+}
+
+/// Reopens the file at `path`, truncates it to `len` bytes and seeks to its end.
+async fn reopen_at(path: &Path, len: u64) -> io::Result<tokio::fs::File> {
+    let mut file = tokio::fs::OpenOptions::new().write(true).open(path).await?;
+    file.set_len(len).await?;
+    file.seek(io::SeekFrom::Start(len)).await?;
+    Ok(file)
+}
+
+fn is_transient(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock
+    )
+}
+
+/// Returns the bytes available to unprivileged users on the filesystem holding `path`.
+async fn available_disk_space(path: &Path) -> io::Result<u64> {
+    let path = path.to_owned();
+    tokio::task::spawn_blocking(move || fs2::available_space(path))
+        .await
+        .map_err(io::Error::other)?
+}
+
+/// Returns `true` when `format` is a strftime format producing non-empty output.
+fn is_valid_time_format(format: &str) -> bool {
+    let parses = chrono::format::StrftimeItems::new(format)
+        .all(|item| !matches!(item, chrono::format::Item::Error));
+    parses && !chrono::Utc::now().format(format).to_string().is_empty()
 }
 
 fn random_basename() -> String {
     Uuid::new_v4().simple().to_string()
 }
 
+/// Removes a temporary file on drop unless it was disarmed after a successful rename.
+struct TempFileGuard {
+    path: PathBuf,
+    armed: bool,
+}
+
+impl TempFileGuard {
+    fn new(path: PathBuf) -> Self {
+        Self { path, armed: true }
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        if self.armed {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+fn temp_sibling(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.tmp", Uuid::new_v4().simple()));
+    path.with_file_name(name)
+}
+
+/// Extensions longer than this are treated as part of the name and dropped.
+const MAX_EXTENSION_LEN: usize = 16;
+
+/// Returns the last extension of `file_name` when it is short, plain ASCII alphanumeric.
+fn safe_extension(file_name: &str) -> Option<&str> {
+    let base = file_name.rsplit(['/', '\\']).next().unwrap_or(file_name);
+    let (stem, extension) = base.rsplit_once('.')?;
+    let valid = !stem.trim_matches('.').is_empty()
+        && !extension.is_empty()
+        && extension.len() <= MAX_EXTENSION_LEN
+        && extension.bytes().all(|byte| byte.is_ascii_alphanumeric());
+    valid.then_some(extension)
+}
+
 fn with_collision_suffix(path: &Path) -> PathBuf {
     let suffix = Uuid::new_v4().simple().to_string();
     let stem = path
@@ -278,6 +1482,104 @@ fn with_collision_suffix(path: &Path) -> PathBuf {
     }
 }
 
+/// Hashes the file at `path` and compares it with the `expected` hex digest.
+///
+/// This is the read-back check behind [`DiskStorageBuilder::verify_write`].
+/// A mismatch fails with [`StorageError::WriteVerificationFailed`];
+/// [`DigestAlgorithm::None`] always passes.
+pub(crate) async fn verify_digest(
+    path: impl AsRef<Path>,
+    algorithm: DigestAlgorithm,
+    expected: &str,
+) -> Result<(), StorageError> {
+    let Some(mut digest) = RunningDigest::new(algorithm) else {
+        return Ok(());
+    };
+    let path = path.as_ref();
+    let read_error = |err: io::Error| {
+        StorageError::new(format!("failed to read back `{}`: {err}", path.display()))
+    };
+    let mut file = tokio::fs::File::open(path).await.map_err(read_error)?;
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await.map_err(read_error)?;
+        if read == 0 {
+            break;
+        }
+        digest.update(&buffer[..read]);
+    }
+    let actual = digest.finalize_hex();
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(StorageError::WriteVerificationFailed {
+            expected: expected.to_owned(),
+            actual,
+        })
+    }
+}
+
+/// Returns whether `path` exists on disk.
+pub async fn exists(path: impl AsRef<Path>) -> Result<bool, StorageError> {
+    Ok(tokio::fs::try_exists(path).await?)
+}
+
+/// Lists the entries of `root` as a stream of paths.
+///
+/// Entries are yielded in the order reported by the filesystem. A failure to
+/// open or read the directory is yielded as a single error item, after which
+/// the stream ends.
+pub fn list_directory(
+    root: impl AsRef<Path>,
+) -> impl Stream<Item = Result<PathBuf, StorageError>> + Send + 'static {
+    enum ListState {
+        Start(PathBuf),
+        Reading(tokio::fs::ReadDir),
+        Done,
+    }
+
+    stream::unfold(
+        ListState::Start(root.as_ref().to_path_buf()),
+        |state| async move {
+            let mut entries = match state {
+                ListState::Start(root) => match tokio::fs::read_dir(&root).await {
+                    Ok(entries) => entries,
+                    Err(err) => return Some((Err(err.into()), ListState::Done)),
+                },
+                ListState::Reading(entries) => entries,
+                ListState::Done => return None,
+            };
+
+            match entries.next_entry().await {
+                Ok(Some(entry)) => Some((Ok(entry.path()), ListState::Reading(entries))),
+                Ok(None) => None,
+                Err(err) => Some((Err(err.into()), ListState::Done)),
+            }
+        },
+    )
+}
+
+/// Lists the entries of `root` whose extension matches `extension`.
+///
+/// The extension is compared case-insensitively and may be given with or
+/// without a leading dot. Errors are passed through unfiltered.
+pub fn list_directory_filtered(
+    root: impl AsRef<Path>,
+    extension: &str,
+) -> impl Stream<Item = Result<PathBuf, StorageError>> + Send + 'static {
+    let extension = extension.trim_start_matches('.').to_owned();
+    list_directory(root).filter(move |entry| {
+        let keep = match entry {
+            Ok(path) => path
+                .extension()
+                .and_then(|value| value.to_str())
+                .is_some_and(|value| value.eq_ignore_ascii_case(&extension)),
+            Err(_) => true,
+        };
+        std::future::ready(keep)
+    })
+}
+
 /// Sanitizes filenames to prevent traversal and unsafe path characters.
 pub fn sanitize_filename(input: &str) -> String {
     // Handle both separator styles so traversal-like names are normalized the
@@ -305,3 +1607,88 @@ pub fn sanitize_filename(input: &str) -> String {
 
     sanitized
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        sync::Mutex,
+        task::{Context, Poll},
+    };
+
+    use super::*;
+
+    /// Output file that accepts writes into a pending buffer and fails every
+    /// other flush after persisting half of it, like a partial write reported late.
+    struct FlakyFile {
+        disk: Arc<Mutex<Vec<u8>>>,
+        flushes: Arc<AtomicU64>,
+        pending: Vec<u8>,
+    }
+
+    impl AsyncWrite for FlakyFile {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.pending.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            let pending = std::mem::take(&mut self.pending);
+            let mut disk = self.disk.lock().expect("disk lock");
+            if self.flushes.fetch_add(1, Ordering::SeqCst) % 2 == 0 {
+                disk.extend_from_slice(&pending[..pending.len() / 2]);
+                return Poll::Ready(Err(io::ErrorKind::Interrupted.into()));
+            }
+            disk.extend_from_slice(&pending);
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn write_retries_neither_duplicate_nor_drop_bytes() {
+        let disk = Arc::new(Mutex::new(Vec::new()));
+        let flushes = Arc::new(AtomicU64::new(0));
+        let open = |len: u64| {
+            let disk = Arc::clone(&disk);
+            let flushes = Arc::clone(&flushes);
+            async move {
+                disk.lock()
+                    .expect("disk lock")
+                    .truncate(usize::try_from(len).expect("length fits"));
+                Ok(FlakyFile {
+                    disk,
+                    flushes,
+                    pending: Vec::new(),
+                })
+            }
+        };
+        let payload = (0..64u8).collect::<Vec<_>>();
+        let chunks = payload
+            .chunks(10)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>();
+
+        let written = write_stream_with_retries(
+            Path::new("flaky.bin"),
+            open(0).await.expect("open"),
+            Box::pin(stream::iter(chunks)),
+            1,
+            Duration::from_millis(1),
+            open,
+        )
+        .await
+        .expect("every chunk should succeed on retry");
+
+        assert_eq!(written, 64);
+        assert_eq!(*disk.lock().expect("disk lock"), payload);
+        assert_eq!(flushes.load(Ordering::SeqCst), 14);
+    }
+}
@@ -0,0 +1,204 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use bytes::Bytes;
+use futures::channel::mpsc;
+
+use super::{fan_out, store_with_extra, BoxStream, FileMeta, StorageEngine, FAN_OUT_BUFFER};
+use crate::MulterError;
+
+type CachePredicateFn = dyn Fn(&FileMeta) -> bool + Send + Sync;
+
+/// Write-through storage that persists every file to a primary backend and a cache.
+///
+/// Both backends receive the same byte stream concurrently. The primary result is
+/// returned to the caller; cache failures are logged and otherwise ignored.
+#[derive(Clone)]
+pub struct HybridStorage<Primary, Cache> {
+    primary: Primary,
+    cache: Cache,
+    cache_predicate: Option<Arc<CachePredicateFn>>,
+}
+
+impl<Primary, Cache> HybridStorage<Primary, Cache>
+where
+    Primary: StorageEngine,
+    Cache: StorageEngine,
+{
+    /// Creates hybrid storage writing through to `primary` and `cache`.
+    pub fn new(primary: Primary, cache: Cache) -> Self {
+        Self {
+            primary,
+            cache,
+            cache_predicate: None,
+        }
+    }
+
+    /// Restricts caching to files for which `predicate` returns `true`.
+    ///
+    /// Files rejected by the predicate are written to the primary backend only.
+    pub fn with_cache_predicate(mut self, predicate: Arc<CachePredicateFn>) -> Self {
+        self.cache_predicate = Some(predicate);
+        self
+    }
+
+    /// Returns the primary storage backend.
+    pub fn primary(&self) -> &Primary {
+        &self.primary
+    }
+
+    /// Returns the cache storage backend.
+    pub fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// Writes `stream` to the primary and, when cached, the cache backend,
+    /// forwarding `extra` when it is set.
+    async fn store_through(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        extra: Option<&HashMap<String, String>>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Primary::Output, Primary::Error> {
+        let meta = FileMeta {
+            field_name: field_name.to_owned(),
+            file_name: file_name.map(ToOwned::to_owned),
+            content_type: content_type.to_owned(),
+        };
+        if !self.should_cache(&meta) {
+            return store_with_extra(
+                &self.primary,
+                field_name,
+                file_name,
+                content_type,
+                size_hint,
+                extra,
+                stream,
+            )
+            .await;
+        }
+
+        let (primary_tx, primary_rx) = mpsc::channel(FAN_OUT_BUFFER);
+        let (cache_tx, cache_rx) = mpsc::channel(FAN_OUT_BUFFER);
+
+        let (_, primary_result, cache_result) = tokio::join!(
+            fan_out(stream, primary_tx, cache_tx),
+            store_with_extra(
+                &self.primary,
+                field_name,
+                file_name,
+                content_type,
+                size_hint,
+                extra,
+                Box::pin(primary_rx)
+            ),
+            store_with_extra(
+                &self.cache,
+                field_name,
+                file_name,
+                content_type,
+                size_hint,
+                extra,
+                Box::pin(cache_rx)
+            ),
+        );
+
+        if let Err(err) = cache_result {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                field_name = field_name,
+                error = %err,
+                "hybrid storage: cache write failed"
+            );
+            #[cfg(not(feature = "tracing"))]
+            let _ = err;
+        }
+
+        primary_result
+    }
+
+    fn should_cache(&self, meta: &FileMeta) -> bool {
+        self.cache_predicate
+            .as_ref()
+            .map_or(true, |predicate| predicate(meta))
+    }
+}
+
+impl<Primary, Cache> fmt::Debug for HybridStorage<Primary, Cache>
+where
+    Primary: fmt::Debug,
+    Cache: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HybridStorage")
+            .field("primary", &self.primary)
+            .field("cache", &self.cache)
+            .field("has_cache_predicate", &self.cache_predicate.is_some())
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl<Primary, Cache> StorageEngine for HybridStorage<Primary, Cache>
+where
+    Primary: StorageEngine,
+    Cache: StorageEngine,
+{
+    type Output = Primary::Output;
+    type Error = Primary::Error;
+
+    async fn store(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.store_sized(field_name, file_name, content_type, None, stream)
+            .await
+    }
+
+    async fn store_sized(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.store_through(field_name, file_name, content_type, size_hint, None, stream)
+            .await
+    }
+
+    async fn store_full(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        extra: &HashMap<String, String>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.store_through(
+            field_name,
+            file_name,
+            content_type,
+            size_hint,
+            Some(extra),
+            stream,
+        )
+        .await
+    }
+
+    fn sniffs_mime(&self) -> bool {
+        self.primary.sniffs_mime() || self.cache.sniffs_mime()
+    }
+
+    /// Discards the primary copy; the cache output is not kept, so cached
+    /// copies are left for the cache to evict.
+    async fn discard(&self, output: Self::Output) -> Result<(), Self::Error> {
+        self.primary.discard(output).await
+    }
+}
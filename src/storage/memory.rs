@@ -1,17 +1,47 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 use bytes::Bytes;
 use futures::StreamExt;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, RwLockReadGuard};
 use uuid::Uuid;
 
-use super::{BoxStream, StorageEngine, StoredFile};
+use super::{report_progress, BoxStream, ProgressFn, StorageEngine, StoredFile};
 use crate::{MulterError, StorageError};
 
 /// In-memory storage engine keyed by generated UUIDs.
-#[derive(Debug, Clone, Default)]
+///
+/// Clones share the same stored payloads and capacity accounting.
+#[derive(Clone, Default)]
 pub struct MemoryStorage {
     files: Arc<RwLock<HashMap<String, Bytes>>>,
+    clone_on_get: bool,
+    on_progress: Option<Arc<ProgressFn>>,
+    max_bytes: Option<u64>,
+    used_bytes: Arc<AtomicU64>,
+    global_cap: Option<u64>,
+    stored_bytes: Arc<AtomicU64>,
+}
+
+impl fmt::Debug for MemoryStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryStorage")
+            .field("files", &self.files)
+            .field("clone_on_get", &self.clone_on_get)
+            .field("on_progress", &self.on_progress.as_ref().map(|_| "<fn>"))
+            .field("max_bytes", &self.max_bytes)
+            .field("used_bytes", &self.used_bytes())
+            .field("global_cap", &self.global_cap)
+            .field("stored_bytes", &self.stored_bytes())
+            .finish()
+    }
 }
 
 impl MemoryStorage {
@@ -20,9 +50,181 @@ impl MemoryStorage {
         Self::default()
     }
 
+    /// Creates an empty in-memory storage backend holding at most `max_bytes`.
+    ///
+    /// The limit covers all stored payloads plus files currently being
+    /// written and is checked per chunk, so an oversized upload fails as soon
+    /// as it crosses the limit with [`StorageError::CapacityExceeded`].
+    pub fn with_capacity(max_bytes: u64) -> Self {
+        Self {
+            max_bytes: Some(max_bytes),
+            ..Self::default()
+        }
+    }
+
+    /// Caps the total size of stored payloads at `max_total_bytes`.
+    ///
+    /// Unlike [`MemoryStorage::with_capacity`], only completed files count:
+    /// each file is admitted once fully received, and rejected with
+    /// [`StorageError::GlobalCapacityExceeded`] when it would push the stored
+    /// total past the cap. A known size hint is checked before any byte is
+    /// read. [`MemoryStorage::remove`] and [`MemoryStorage::clear`] free room.
+    pub fn with_global_cap(mut self, max_total_bytes: u64) -> Self {
+        self.global_cap = Some(max_total_bytes);
+        self
+    }
+
+    /// Controls whether [`MemoryStorage::get`] returns a deep copy of stored bytes.
+    ///
+    /// Stored payloads are `Bytes`, which are reference counted, so the default
+    /// (`false`) already returns a zero-copy handle that only bumps a refcount.
+    /// Enable this to hand out independent buffers instead.
+    pub fn with_clone_on_get(mut self, clone_on_get: bool) -> Self {
+        self.clone_on_get = clone_on_get;
+        self
+    }
+
+    /// Sets a callback invoked with `(bytes_stored, size_hint)` after every chunk.
+    pub fn on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: Fn(u64, Option<u64>) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(on_progress));
+        self
+    }
+
     /// Returns stored bytes for a previously stored key.
     pub async fn get(&self, key: &str) -> Option<Bytes> {
-        self.files.read().await.get(key).cloned()
+        let files = self.files.read().await;
+        let bytes = files.get(key)?;
+        if self.clone_on_get {
+            Some(Bytes::copy_from_slice(bytes))
+        } else {
+            Some(bytes.clone())
+        }
+    }
+
+    /// Returns borrowed access to stored bytes without cloning the `Bytes` handle.
+    ///
+    /// The returned [`BytesRef`] holds a read lock on the storage map, so
+    /// writes wait until it is dropped. Any number of readers may coexist.
+    pub async fn get_ref(&self, key: &str) -> Option<BytesRef<'_>> {
+        let files = self.files.read().await;
+        RwLockReadGuard::try_map(files, |files| files.get(key))
+            .ok()
+            .map(BytesRef)
+    }
+
+    /// Removes a stored payload, releasing its bytes from the capacity budget.
+    pub async fn remove(&self, key: &str) -> Option<Bytes> {
+        let removed = self.files.write().await.remove(key)?;
+        self.used_bytes
+            .fetch_sub(removed.len() as u64, Ordering::AcqRel);
+        self.stored_bytes
+            .fetch_sub(removed.len() as u64, Ordering::AcqRel);
+        Some(removed)
+    }
+
+    /// Removes every stored payload, releasing their bytes from the capacity budget.
+    ///
+    /// Writes still in flight are unaffected and land after the clear.
+    pub async fn clear(&self) {
+        let mut files = self.files.write().await;
+        let released = files.values().map(|bytes| bytes.len() as u64).sum::<u64>();
+        files.clear();
+        self.used_bytes.fetch_sub(released, Ordering::AcqRel);
+        self.stored_bytes.fetch_sub(released, Ordering::AcqRel);
+    }
+
+    /// Returns the bytes held by stored payloads and in-flight writes.
+    pub fn used_bytes(&self) -> u64 {
+        self.used_bytes.load(Ordering::Acquire)
+    }
+
+    /// Returns the bytes held by completed payloads, excluding in-flight writes.
+    pub fn stored_bytes(&self) -> u64 {
+        self.stored_bytes.load(Ordering::Acquire)
+    }
+
+    /// Fails when storing `incoming` more bytes would exceed the global cap.
+    fn check_global_cap(&self, incoming: u64) -> Result<(), StorageError> {
+        let Some(max) = self.global_cap else {
+            return Ok(());
+        };
+        let stored = self.stored_bytes();
+        if stored.saturating_add(incoming) <= max {
+            return Ok(());
+        }
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            stored = stored,
+            incoming = incoming,
+            global_cap = max,
+            "memory storage: global cap exceeded"
+        );
+        Err(StorageError::GlobalCapacityExceeded {
+            stored,
+            incoming,
+            max,
+        })
+    }
+
+    /// Reserves `additional` bytes, failing if the capacity would be exceeded.
+    fn reserve_bytes(&self, additional: u64) -> Result<(), StorageError> {
+        let Some(max) = self.max_bytes else {
+            self.used_bytes.fetch_add(additional, Ordering::AcqRel);
+            return Ok(());
+        };
+        self.used_bytes
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |used| {
+                used.checked_add(additional).filter(|total| *total <= max)
+            })
+            .map(|_| ())
+            .map_err(|used| {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    used = used,
+                    max_bytes = max,
+                    "memory storage: capacity exceeded"
+                );
+                StorageError::CapacityExceeded { used, max }
+            })
+    }
+
+    /// Returns a point-in-time copy of every stored payload, keyed by storage key.
+    ///
+    /// Payloads follow [`MemoryStorage::with_clone_on_get`]. Blocks the
+    /// current thread until the read lock is available.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called from an async execution context; wrap the call in
+    /// [`tokio::task::block_in_place`] or `spawn_blocking` there.
+    pub fn snapshot(&self) -> HashMap<String, Bytes> {
+        self.files
+            .blocking_read()
+            .iter()
+            .map(|(key, bytes)| {
+                let bytes = if self.clone_on_get {
+                    Bytes::copy_from_slice(bytes)
+                } else {
+                    bytes.clone()
+                };
+                (key.clone(), bytes)
+            })
+            .collect()
+    }
+
+    /// Returns the keys of every stored payload, in arbitrary order.
+    ///
+    /// Blocks the current thread until the read lock is available.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called from an async execution context, like
+    /// [`MemoryStorage::snapshot`].
+    pub fn keys_snapshot(&self) -> Vec<String> {
+        self.files.blocking_read().keys().cloned().collect()
     }
 
     /// Returns the current number of stored objects.
@@ -36,6 +238,37 @@ impl MemoryStorage {
     }
 }
 
+/// Bytes reserved by an in-flight store, released unless the payload is kept.
+struct Reservation<'a> {
+    used_bytes: &'a AtomicU64,
+    reserved: u64,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.reserved > 0 {
+            self.used_bytes.fetch_sub(self.reserved, Ordering::AcqRel);
+        }
+    }
+}
+
+/// Borrowed view of a stored payload returned by [`MemoryStorage::get_ref`].
+pub struct BytesRef<'a>(RwLockReadGuard<'a, Bytes>);
+
+impl Deref for BytesRef<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Debug for BytesRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BytesRef").field(&self.0.len()).finish()
+    }
+}
+
 #[async_trait::async_trait]
 impl StorageEngine for MemoryStorage {
     type Output = StoredFile;
@@ -46,7 +279,19 @@ impl StorageEngine for MemoryStorage {
         field_name: &str,
         file_name: Option<&str>,
         content_type: &str,
-        mut stream: BoxStream<'_, Result<Bytes, MulterError>>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.store_sized(field_name, file_name, content_type, None, stream)
+            .await
+    }
+
+    async fn store_sized(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
     ) -> Result<Self::Output, Self::Error> {
         #[cfg(feature = "tracing")]
         tracing::debug!(
@@ -56,9 +301,28 @@ impl StorageEngine for MemoryStorage {
             "memory storage: begin streaming store"
         );
 
+        if let Some(size_hint) = size_hint {
+            self.check_global_cap(size_hint)?;
+        }
+
+        let mut stream = match &self.on_progress {
+            Some(on_progress) => {
+                let on_progress = Arc::clone(on_progress);
+                report_progress(stream, size_hint, move |stored, total| {
+                    on_progress(stored, total)
+                })
+            }
+            None => stream,
+        };
+        let mut reservation = Reservation {
+            used_bytes: &self.used_bytes,
+            reserved: 0,
+        };
         let mut body = Vec::new();
         while let Some(chunk) = stream.next().await {
             let chunk = chunk.map_err(|err| StorageError::new(err.to_string()))?;
+            self.reserve_bytes(chunk.len() as u64)?;
+            reservation.reserved += chunk.len() as u64;
             body.extend_from_slice(&chunk);
         }
         let body = Bytes::from(body);
@@ -69,7 +333,15 @@ impl StorageEngine for MemoryStorage {
             .parse::<mime::Mime>()
             .unwrap_or(mime::APPLICATION_OCTET_STREAM);
 
-        self.files.write().await.insert(storage_key.clone(), body);
+        {
+            // Admission happens under the write lock so concurrent stores
+            // cannot both squeeze under the global cap.
+            let mut files = self.files.write().await;
+            self.check_global_cap(size)?;
+            files.insert(storage_key.clone(), body);
+            self.stored_bytes.fetch_add(size, Ordering::AcqRel);
+        }
+        reservation.reserved = 0;
 
         #[cfg(feature = "tracing")]
         tracing::debug!(
@@ -86,6 +358,12 @@ impl StorageEngine for MemoryStorage {
             content_type: parsed_content_type,
             size,
             path: None,
+            digest: None,
         })
     }
+
+    async fn discard(&self, output: Self::Output) -> Result<(), Self::Error> {
+        self.remove(&output.storage_key).await;
+        Ok(())
+    }
 }
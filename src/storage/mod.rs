@@ -1,22 +1,41 @@
 //! Storage engine abstractions and built-in implementations.
 
-use std::pin::Pin;
+use std::{collections::HashMap, io, path::Path, pin::Pin};
 
 use bytes::Bytes;
-use futures::Stream;
+use futures::{channel::mpsc, SinkExt, Stream, StreamExt};
 
 use crate::{MulterError, StorageError};
 
+/// Runtime-selected storage backend behind a trait object.
+pub mod boxed;
 /// Disk-backed storage backend implementation.
 pub mod disk;
+/// Write-through storage combining a primary backend with a cache.
+pub mod hybrid;
 /// In-memory storage backend implementation.
 pub mod memory;
-pub use disk::{DiskStorage, DiskStorageBuilder, FilenameStrategy};
-pub use memory::MemoryStorage;
+/// S3-compatible object storage backend implementation.
+#[cfg(feature = "s3")]
+pub mod s3;
+/// Storage engine writing to two backends concurrently.
+pub mod tee;
+pub use boxed::{BoxedStorage, DynStorageEngine};
+pub use disk::{
+    CollisionPolicy, CreateMode, DigestAlgorithm, DiskStorage, DiskStorageBuilder, FilenameStrategy,
+};
+pub use hybrid::HybridStorage;
+pub use memory::{BytesRef, MemoryStorage};
+#[cfg(feature = "s3")]
+pub use s3::{S3Storage, S3StorageBuilder};
+pub use tee::{TeePolicy, TeeStorageEngine};
 
 /// Boxed stream type used by storage backends.
 pub type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + Send + 'a>>;
 
+/// Progress callback receiving bytes stored so far and the expected total, when known.
+pub type ProgressFn = dyn Fn(u64, Option<u64>) + Send + Sync;
+
 /// Metadata describing a file part before persistence.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileMeta {
@@ -43,6 +62,58 @@ pub struct StoredFile {
     pub size: u64,
     /// Final filesystem path when stored on disk.
     pub path: Option<std::path::PathBuf>,
+    /// Lowercase hex content digest, when the backend computed one.
+    pub digest: Option<String>,
+}
+
+impl StoredFile {
+    /// Opens the stored file for reading.
+    ///
+    /// Fails with [`io::ErrorKind::NotFound`] when the backend did not write
+    /// the file to disk and [`StoredFile::path`] is `None`.
+    pub async fn open(&self) -> io::Result<tokio::fs::File> {
+        tokio::fs::File::open(self.disk_path()?).await
+    }
+
+    /// Reads the whole stored file into memory.
+    pub async fn read_bytes(&self) -> io::Result<Bytes> {
+        tokio::fs::read(self.disk_path()?).await.map(Bytes::from)
+    }
+
+    /// Deletes the stored file from disk.
+    pub async fn delete(&self) -> io::Result<()> {
+        tokio::fs::remove_file(self.disk_path()?).await
+    }
+
+    fn disk_path(&self) -> io::Result<&Path> {
+        self.path.as_deref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("stored file `{}` has no filesystem path", self.storage_key),
+            )
+        })
+    }
+}
+
+/// Storage outputs that know how many bytes they persisted.
+///
+/// Used by [`ProcessedMultipart::total_file_bytes`](crate::ProcessedMultipart::total_file_bytes).
+pub trait TotalSize {
+    /// Returns the number of bytes persisted for this output.
+    fn total_size(&self) -> u64;
+}
+
+impl TotalSize for StoredFile {
+    fn total_size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// Tee outputs report the size written to the primary backend.
+impl<A: TotalSize, B> TotalSize for (A, Option<B>) {
+    fn total_size(&self) -> u64 {
+        self.0.total_size()
+    }
 }
 
 /// Async trait abstraction for file storage backends.
@@ -61,6 +132,168 @@ pub trait StorageEngine: Send + Sync + 'static {
         content_type: &str,
         stream: BoxStream<'_, Result<Bytes, MulterError>>,
     ) -> Result<Self::Output, Self::Error>;
+
+    /// Stores a file stream whose total size may be known up front.
+    ///
+    /// `size_hint` is usually the part's `Content-Length`. Backends that report
+    /// progress override this to forward the hint; the default ignores it.
+    async fn store_sized(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        let _ = size_hint;
+        self.store(field_name, file_name, content_type, stream)
+            .await
+    }
+
+    /// Stores a file stream together with caller-supplied metadata.
+    ///
+    /// [`Multer::store_with_meta`](crate::Multer::store_with_meta) passes
+    /// `extra` through unchanged, for example custom part headers. The default
+    /// ignores `extra` and delegates to [`StorageEngine::store_sized`], so the
+    /// size hint still reaches the backend.
+    async fn store_full(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        extra: &HashMap<String, String>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        let _ = extra;
+        self.store_sized(field_name, file_name, content_type, size_hint, stream)
+            .await
+    }
+
+    /// Returns whether this backend replaces generic content types with ones
+    /// sniffed from the file's leading bytes.
+    ///
+    /// [`Multer`](crate::Multer) defers MIME allowlist checks for
+    /// `application/octet-stream` parts until after detection when this is `true`.
+    fn sniffs_mime(&self) -> bool {
+        false
+    }
+
+    /// Removes a file this backend stored, after the request it belonged to
+    /// was rejected.
+    ///
+    /// [`Multer::parse_and_store`](crate::Multer::parse_and_store) calls this
+    /// for every file it already stored when a later part or the final
+    /// required-field check fails. The default keeps the file.
+    async fn discard(&self, output: Self::Output) -> Result<(), Self::Error> {
+        let _ = output;
+        Ok(())
+    }
+}
+
+/// Extension trait for storing a file while observing byte progress.
+///
+/// Implemented for every [`StorageEngine`]; `on_progress` runs after each
+/// chunk is handed to the backend.
+#[async_trait::async_trait]
+pub trait StorageEngineWithProgress: StorageEngine {
+    /// Stores a file stream and reports `(bytes_so_far, size_hint)` per chunk.
+    async fn store_with_progress<F>(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+        on_progress: F,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        F: Fn(u64, Option<u64>) + Send + 'static;
+}
+
+#[async_trait::async_trait]
+impl<S> StorageEngineWithProgress for S
+where
+    S: StorageEngine,
+{
+    async fn store_with_progress<F>(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+        on_progress: F,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        F: Fn(u64, Option<u64>) + Send + 'static,
+    {
+        let stream = report_progress(stream, size_hint, on_progress);
+        self.store_sized(field_name, file_name, content_type, size_hint, stream)
+            .await
+    }
+}
+
+/// Wraps `stream` so `on_progress` observes the running byte count of every chunk.
+pub(crate) fn report_progress<'a, F>(
+    stream: BoxStream<'a, Result<Bytes, MulterError>>,
+    size_hint: Option<u64>,
+    on_progress: F,
+) -> BoxStream<'a, Result<Bytes, MulterError>>
+where
+    F: Fn(u64, Option<u64>) + Send + 'a,
+{
+    let mut stored = 0u64;
+    Box::pin(stream.inspect(move |chunk| {
+        if let Ok(chunk) = chunk {
+            stored = stored.saturating_add(chunk.len() as u64);
+            on_progress(stored, size_hint);
+        }
+    }))
+}
+
+/// Number of leading bytes inspected when sniffing a file's MIME type.
+pub(crate) const SNIFF_LEN: usize = 512;
+
+/// Returns whether `content_type` carries no useful type information.
+pub(crate) fn is_generic_mime(content_type: &str) -> bool {
+    content_type.parse::<mime::Mime>().map_or(true, |mime| {
+        mime.essence_str() == mime::APPLICATION_OCTET_STREAM.essence_str()
+    })
+}
+
+/// Detects a MIME type from the first [`SNIFF_LEN`] bytes of `stream`.
+///
+/// The inspected chunks are replayed in front of the remaining stream, so the
+/// returned stream yields exactly the original bytes. Stream errors stop
+/// detection and are replayed as-is.
+pub(crate) async fn sniff_mime(
+    mut stream: BoxStream<'_, Result<Bytes, MulterError>>,
+) -> (
+    Option<mime::Mime>,
+    BoxStream<'_, Result<Bytes, MulterError>>,
+) {
+    let mut head = Vec::new();
+    let mut prefix = Vec::new();
+    while head.len() < SNIFF_LEN {
+        let Some(chunk) = stream.next().await else {
+            break;
+        };
+        let failed = chunk.is_err();
+        if let Ok(bytes) = &chunk {
+            head.extend_from_slice(&bytes[..bytes.len().min(SNIFF_LEN - head.len())]);
+        }
+        prefix.push(chunk);
+        if failed {
+            break;
+        }
+    }
+
+    let detected = infer::get(&head).and_then(|kind| kind.mime_type().parse().ok());
+    (
+        detected,
+        Box::pin(futures::stream::iter(prefix).chain(stream)),
+    )
 }
 
 /// Placeholder storage implementation used as the default backend.
@@ -84,3 +317,67 @@ impl StorageEngine for NoopStorage {
         ))
     }
 }
+
+/// Stores through [`StorageEngine::store_full`] when `extra` is set and
+/// [`StorageEngine::store_sized`] otherwise.
+pub(crate) async fn store_with_extra<S>(
+    storage: &S,
+    field_name: &str,
+    file_name: Option<&str>,
+    content_type: &str,
+    size_hint: Option<u64>,
+    extra: Option<&HashMap<String, String>>,
+    stream: BoxStream<'_, Result<Bytes, MulterError>>,
+) -> Result<S::Output, S::Error>
+where
+    S: StorageEngine,
+{
+    match extra {
+        Some(extra) => {
+            storage
+                .store_full(
+                    field_name,
+                    file_name,
+                    content_type,
+                    size_hint,
+                    extra,
+                    stream,
+                )
+                .await
+        }
+        None => {
+            storage
+                .store_sized(field_name, file_name, content_type, size_hint, stream)
+                .await
+        }
+    }
+}
+
+/// Channel capacity used when fanning a file stream out to two backends.
+pub(crate) const FAN_OUT_BUFFER: usize = 8;
+
+/// Forwards every chunk of `source` to both backends until the source ends.
+///
+/// A backend that stops reading early is skipped for the remaining chunks.
+pub(crate) async fn fan_out(
+    mut source: BoxStream<'_, Result<Bytes, MulterError>>,
+    mut primary_tx: mpsc::Sender<Result<Bytes, MulterError>>,
+    mut secondary_tx: mpsc::Sender<Result<Bytes, MulterError>>,
+) {
+    while let Some(item) = source.next().await {
+        match item {
+            Ok(chunk) => {
+                let _ = primary_tx.send(Ok(chunk.clone())).await;
+                let _ = secondary_tx.send(Ok(chunk)).await;
+                if primary_tx.is_closed() && secondary_tx.is_closed() {
+                    return;
+                }
+            }
+            Err(err) => {
+                let _ = secondary_tx.send(Err(err.clone())).await;
+                let _ = primary_tx.send(Err(err)).await;
+                return;
+            }
+        }
+    }
+}
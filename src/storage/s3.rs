@@ -0,0 +1,368 @@
+use std::fmt;
+
+use aws_sdk_s3::{
+    error::DisplayErrorContext,
+    primitives::ByteStream,
+    types::{CompletedMultipartUpload, CompletedPart},
+    Client,
+};
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use uuid::Uuid;
+
+use super::{disk::sanitize_filename, BoxStream, StorageEngine, StoredFile};
+use crate::{MulterError, StorageError};
+
+/// Smallest part size S3 accepts for every part except the last one.
+pub const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Builder for [`S3Storage`].
+#[derive(Clone, Default)]
+pub struct S3StorageBuilder {
+    client: Option<Client>,
+    bucket: Option<String>,
+    key_prefix: String,
+    part_size: Option<usize>,
+}
+
+impl fmt::Debug for S3StorageBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3StorageBuilder")
+            .field("client", &self.client.as_ref().map(|_| "<client>"))
+            .field("bucket", &self.bucket)
+            .field("key_prefix", &self.key_prefix)
+            .field("part_size", &self.part_size)
+            .finish()
+    }
+}
+
+impl S3StorageBuilder {
+    /// Sets the pre-configured S3 client used for uploads.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Sets the destination bucket.
+    pub fn bucket(mut self, bucket: impl Into<String>) -> Self {
+        self.bucket = Some(bucket.into());
+        self
+    }
+
+    /// Sets a prefix prepended to every object key, such as `"uploads/"`.
+    pub fn key_prefix(mut self, key_prefix: impl Into<String>) -> Self {
+        self.key_prefix = key_prefix.into();
+        self
+    }
+
+    /// Sets how many bytes are buffered before each multipart upload request.
+    ///
+    /// Must be at least [`MIN_PART_SIZE`]. Defaults to [`MIN_PART_SIZE`].
+    pub fn part_size(mut self, part_size: usize) -> Self {
+        self.part_size = Some(part_size);
+        self
+    }
+
+    /// Builds a validated S3 storage backend.
+    pub fn build(self) -> Result<S3Storage, StorageError> {
+        let client = self
+            .client
+            .ok_or_else(|| StorageError::new("s3 storage requires a client"))?;
+        let bucket = self
+            .bucket
+            .filter(|bucket| !bucket.trim().is_empty())
+            .ok_or_else(|| StorageError::new("s3 storage bucket cannot be empty"))?;
+        let part_size = self.part_size.unwrap_or(MIN_PART_SIZE);
+        if part_size < MIN_PART_SIZE {
+            return Err(StorageError::new(format!(
+                "s3 part size must be at least {MIN_PART_SIZE} bytes"
+            )));
+        }
+
+        Ok(S3Storage {
+            client,
+            bucket,
+            key_prefix: self.key_prefix,
+            part_size,
+        })
+    }
+}
+
+/// Storage engine streaming files into an S3-compatible bucket.
+///
+/// Files smaller than the part size are written with a single `PutObject`;
+/// larger files use a multipart upload so at most one part is buffered in
+/// memory at a time. Failed multipart uploads are aborted.
+#[derive(Clone)]
+pub struct S3Storage {
+    client: Client,
+    bucket: String,
+    key_prefix: String,
+    part_size: usize,
+}
+
+impl fmt::Debug for S3Storage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3Storage")
+            .field("bucket", &self.bucket)
+            .field("key_prefix", &self.key_prefix)
+            .field("part_size", &self.part_size)
+            .finish()
+    }
+}
+
+impl S3Storage {
+    /// Creates an S3 storage builder.
+    pub fn builder() -> S3StorageBuilder {
+        S3StorageBuilder::default()
+    }
+
+    /// Returns the destination bucket.
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// Returns the underlying S3 client.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    fn object_key(&self, file_name: Option<&str>) -> String {
+        let id = Uuid::new_v4().simple().to_string();
+        match file_name.map(sanitize_filename) {
+            Some(name) => format!("{}{id}-{name}", self.key_prefix),
+            None => format!("{}{id}", self.key_prefix),
+        }
+    }
+
+    async fn put_object(
+        &self,
+        key: &str,
+        content_type: &str,
+        body: Bytes,
+    ) -> Result<(), StorageError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|err| s3_error("put object", err))?;
+        Ok(())
+    }
+
+    async fn multipart_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+        first_part: Bytes,
+        buffer: BytesMut,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<u64, StorageError> {
+        let upload = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(|err| s3_error("create multipart upload", err))?;
+        let upload_id = upload
+            .upload_id()
+            .ok_or_else(|| StorageError::new("s3 did not return a multipart upload id"))?
+            .to_owned();
+
+        let result = self
+            .upload_parts(key, &upload_id, first_part, buffer, stream)
+            .await;
+        if result.is_err() {
+            let aborted = self
+                .client
+                .abort_multipart_upload()
+                .bucket(&self.bucket)
+                .key(key)
+                .upload_id(&upload_id)
+                .send()
+                .await;
+            #[cfg(feature = "tracing")]
+            if let Err(err) = &aborted {
+                tracing::warn!(
+                    key = key,
+                    error = %DisplayErrorContext(err),
+                    "s3 storage: failed to abort multipart upload"
+                );
+            }
+            #[cfg(not(feature = "tracing"))]
+            let _ = aborted;
+        }
+        result
+    }
+
+    async fn upload_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+        first_part: Bytes,
+        mut buffer: BytesMut,
+        mut stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<u64, StorageError> {
+        let mut written = (first_part.len() + buffer.len()) as u64;
+        let mut completed = vec![self.upload_part(key, upload_id, 1, first_part).await?];
+
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.map_err(|err| StorageError::new(format!("stream read failed: {err}")))?;
+            written = written.saturating_add(chunk.len() as u64);
+            buffer.extend_from_slice(&chunk);
+            while buffer.len() >= self.part_size {
+                let part = buffer.split_to(self.part_size).freeze();
+                let part_number = next_part_number(&completed)?;
+                completed.push(self.upload_part(key, upload_id, part_number, part).await?);
+            }
+        }
+        if !buffer.is_empty() {
+            let part_number = next_part_number(&completed)?;
+            completed.push(
+                self.upload_part(key, upload_id, part_number, buffer.freeze())
+                    .await?,
+            );
+        }
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(completed))
+                    .build(),
+            )
+            .send()
+            .await
+            .map_err(|err| s3_error("complete multipart upload", err))?;
+        Ok(written)
+    }
+
+    async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: i32,
+        body: Bytes,
+    ) -> Result<CompletedPart, StorageError> {
+        let output = self
+            .client
+            .upload_part()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .part_number(part_number)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|err| s3_error("upload part", err))?;
+
+        Ok(CompletedPart::builder()
+            .set_e_tag(output.e_tag().map(ToOwned::to_owned))
+            .part_number(part_number)
+            .build())
+    }
+}
+
+#[async_trait::async_trait]
+impl StorageEngine for S3Storage {
+    type Output = StoredFile;
+    type Error = StorageError;
+
+    async fn store(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        mut stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        let key = self.object_key(file_name);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            field_name = field_name,
+            bucket = self.bucket.as_str(),
+            key = key.as_str(),
+            "s3 storage: begin streaming store"
+        );
+
+        let mut buffer = BytesMut::new();
+        let mut first_part = None;
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.map_err(|err| StorageError::new(format!("stream read failed: {err}")))?;
+            buffer.extend_from_slice(&chunk);
+            if buffer.len() >= self.part_size {
+                first_part = Some(buffer.split_to(self.part_size).freeze());
+                break;
+            }
+        }
+
+        let size = match first_part {
+            Some(first_part) => {
+                self.multipart_upload(&key, content_type, first_part, buffer, stream)
+                    .await?
+            }
+            None => {
+                let size = buffer.len() as u64;
+                self.put_object(&key, content_type, buffer.freeze()).await?;
+                size
+            }
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            field_name = field_name,
+            size = size,
+            key = key.as_str(),
+            "s3 storage: completed store"
+        );
+        Ok(StoredFile {
+            storage_key: key,
+            field_name: field_name.to_owned(),
+            file_name: file_name.map(ToOwned::to_owned),
+            content_type: content_type
+                .parse::<mime::Mime>()
+                .unwrap_or(mime::APPLICATION_OCTET_STREAM),
+            size,
+            path: None,
+            digest: None,
+        })
+    }
+
+    async fn discard(&self, output: Self::Output) -> Result<(), Self::Error> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(&output.storage_key)
+            .send()
+            .await
+            .map_err(|err| s3_error("delete object", err))?;
+        Ok(())
+    }
+}
+
+fn next_part_number(completed: &[CompletedPart]) -> Result<i32, StorageError> {
+    i32::try_from(completed.len() + 1)
+        .ok()
+        .filter(|part_number| *part_number <= 10_000)
+        .ok_or_else(|| StorageError::new("s3 multipart upload exceeds 10000 parts"))
+}
+
+fn s3_error<E>(operation: &str, err: E) -> StorageError
+where
+    E: std::error::Error,
+{
+    StorageError::new(format!(
+        "s3 {operation} failed: {}",
+        DisplayErrorContext(err)
+    ))
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use futures::channel::mpsc;
+
+use super::{fan_out, store_with_extra, BoxStream, StorageEngine, FAN_OUT_BUFFER};
+use crate::{MulterError, StorageError};
+
+/// Failure handling for [`TeeStorageEngine`].
+///
+/// The policy is chosen at runtime, so the tee output has one shape for
+/// both: `(A::Output, Option<B::Output>)`. The secondary result is always
+/// `Some` under [`TeePolicy::FailFast`]; a plain `(A::Output, B::Output)` pair
+/// could not express a [`TeePolicy::BestEffort`] store whose secondary write
+/// failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TeePolicy {
+    /// Fail the store when either backend fails, discarding the copy the
+    /// other backend stored.
+    #[default]
+    FailFast,
+    /// Succeed whenever the primary backend succeeds.
+    ///
+    /// A secondary failure is logged and reported as `None` in the output.
+    /// When the primary fails, the secondary copy is discarded.
+    BestEffort,
+}
+
+/// Storage engine that writes every file to two backends concurrently.
+///
+/// Each backend reads its own copy of the byte stream, so neither has to
+/// buffer the whole file. The output pairs the primary result with the
+/// secondary one, which is `None` only when [`TeePolicy::BestEffort`] absorbed a
+/// secondary failure.
+#[derive(Debug, Clone)]
+pub struct TeeStorageEngine<A, B> {
+    primary: A,
+    secondary: B,
+    policy: TeePolicy,
+}
+
+impl<A, B> TeeStorageEngine<A, B>
+where
+    A: StorageEngine,
+    B: StorageEngine,
+{
+    /// Creates a tee writing to `primary` and `secondary` with [`TeePolicy::FailFast`].
+    pub fn new(primary: A, secondary: B) -> Self {
+        Self {
+            primary,
+            secondary,
+            policy: TeePolicy::default(),
+        }
+    }
+
+    /// Sets how partial failures are handled.
+    pub fn with_policy(mut self, policy: TeePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the configured failure policy.
+    pub fn policy(&self) -> TeePolicy {
+        self.policy
+    }
+
+    /// Returns the primary storage backend.
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Returns the secondary storage backend.
+    pub fn secondary(&self) -> &B {
+        &self.secondary
+    }
+
+    /// Writes `stream` to both backends, forwarding `extra` when it is set.
+    async fn store_both(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        extra: Option<&HashMap<String, String>>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<(A::Output, Option<B::Output>), StorageError> {
+        let (primary_tx, primary_rx) = mpsc::channel(FAN_OUT_BUFFER);
+        let (secondary_tx, secondary_rx) = mpsc::channel(FAN_OUT_BUFFER);
+
+        let primary = async {
+            store_with_extra(
+                &self.primary,
+                field_name,
+                file_name,
+                content_type,
+                size_hint,
+                extra,
+                Box::pin(primary_rx),
+            )
+            .await
+            .map_err(|err| StorageError::new(format!("primary storage failed: {err}")))
+        };
+        let secondary = async {
+            store_with_extra(
+                &self.secondary,
+                field_name,
+                file_name,
+                content_type,
+                size_hint,
+                extra,
+                Box::pin(secondary_rx),
+            )
+            .await
+            .map_err(|err| StorageError::new(format!("secondary storage failed: {err}")))
+        };
+
+        let ((), primary, secondary) = tokio::join!(
+            fan_out(stream, primary_tx, secondary_tx),
+            primary,
+            secondary
+        );
+        match (primary, secondary) {
+            (Ok(primary), Ok(secondary)) => Ok((primary, Some(secondary))),
+            (Ok(primary), Err(err)) => match self.policy {
+                TeePolicy::FailFast => {
+                    discard_orphan(&self.primary, primary).await;
+                    Err(err)
+                }
+                TeePolicy::BestEffort => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        field_name = field_name,
+                        error = %err,
+                        "tee storage: secondary write failed"
+                    );
+                    #[cfg(not(feature = "tracing"))]
+                    let _ = err;
+                    Ok((primary, None))
+                }
+            },
+            (Err(err), Ok(secondary)) => {
+                discard_orphan(&self.secondary, secondary).await;
+                Err(err)
+            }
+            (Err(err), Err(_)) => Err(err),
+        }
+    }
+}
+
+/// Discards the copy one backend stored after the other backend failed.
+async fn discard_orphan<S: StorageEngine>(storage: &S, output: S::Output) {
+    if let Err(err) = storage.discard(output).await {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %err, "tee storage: failed to discard orphaned copy");
+        #[cfg(not(feature = "tracing"))]
+        let _ = err;
+    }
+}
+
+#[async_trait::async_trait]
+impl<A, B> StorageEngine for TeeStorageEngine<A, B>
+where
+    A: StorageEngine,
+    B: StorageEngine,
+{
+    type Output = (A::Output, Option<B::Output>);
+    type Error = StorageError;
+
+    async fn store(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.store_sized(field_name, file_name, content_type, None, stream)
+            .await
+    }
+
+    async fn store_sized(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.store_both(field_name, file_name, content_type, size_hint, None, stream)
+            .await
+    }
+
+    async fn store_full(
+        &self,
+        field_name: &str,
+        file_name: Option<&str>,
+        content_type: &str,
+        size_hint: Option<u64>,
+        extra: &HashMap<String, String>,
+        stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.store_both(
+            field_name,
+            file_name,
+            content_type,
+            size_hint,
+            Some(extra),
+            stream,
+        )
+        .await
+    }
+
+    fn sniffs_mime(&self) -> bool {
+        self.primary.sniffs_mime() || self.secondary.sniffs_mime()
+    }
+
+    async fn discard(&self, output: Self::Output) -> Result<(), Self::Error> {
+        let (primary, secondary) = output;
+        let primary = async {
+            self.primary
+                .discard(primary)
+                .await
+                .map_err(|err| StorageError::new(format!("primary storage failed: {err}")))
+        };
+        let secondary =
+            async {
+                match secondary {
+                    Some(secondary) => self.secondary.discard(secondary).await.map_err(|err| {
+                        StorageError::new(format!("secondary storage failed: {err}"))
+                    }),
+                    None => Ok(()),
+                }
+            };
+        let (primary, secondary) = tokio::join!(primary, secondary);
+        primary.and(secondary)
+    }
+}
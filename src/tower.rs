@@ -0,0 +1,180 @@
+//! Framework-agnostic `tower` integration helpers.
+
+use std::{
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::{channel::oneshot, future, FutureExt, Stream, StreamExt};
+use http::{header, Request};
+use http_body_util::BodyExt;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{
+    parser::{extract_multipart_boundary, headers::content_length},
+    Multer, MulterError, Multipart, ParseError, StorageEngine,
+};
+
+/// Request body stream mapped into `multigear` chunk errors.
+pub type TowerBodyBoxStream =
+    Pin<Box<dyn Stream<Item = Result<Bytes, MulterError>> + Send + 'static>>;
+
+/// Multipart type attached to requests by [`MulterBodyService`].
+pub type TowerMultipart = Multipart<TowerBodyBoxStream>;
+
+/// Request extension holding the parsed [`TowerMultipart`].
+///
+/// Request extensions must be `Clone`, so the multipart stream sits behind a
+/// shared slot; the first [`MultipartExtension::take`] call receives it.
+#[derive(Clone)]
+pub struct MultipartExtension(Arc<Mutex<Option<TowerMultipart>>>);
+
+impl MultipartExtension {
+    /// Takes the multipart stream out of the extension.
+    ///
+    /// Returns `None` when it was already taken through this or a cloned handle.
+    pub fn take(&self) -> Option<TowerMultipart> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+}
+
+impl fmt::Debug for MultipartExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MultipartExtension")
+            .field(&"<multipart>")
+            .finish()
+    }
+}
+
+/// `tower::Layer` attaching a streaming [`Multipart`] to multipart requests.
+///
+/// Unlike the axum `MulterLayer`, nothing is parsed or stored up front: the
+/// inner service receives a [`MultipartExtension`] and pulls parts itself.
+pub struct MulterLayer<S> {
+    multer: Arc<Multer<S>>,
+}
+
+impl<S> MulterLayer<S> {
+    /// Creates a layer backed by a shared `Multer` instance.
+    pub fn new(multer: Arc<Multer<S>>) -> Self {
+        Self { multer }
+    }
+}
+
+impl<S> Clone for MulterLayer<S> {
+    fn clone(&self) -> Self {
+        Self {
+            multer: Arc::clone(&self.multer),
+        }
+    }
+}
+
+impl<S> fmt::Debug for MulterLayer<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MulterLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S, Inner> Layer<Inner> for MulterLayer<S> {
+    type Service = MulterBodyService<Inner, S>;
+
+    fn layer(&self, inner: Inner) -> Self::Service {
+        MulterBodyService {
+            inner,
+            multer: Arc::clone(&self.multer),
+        }
+    }
+}
+
+/// Service produced by [`MulterLayer`].
+///
+/// Requests with a valid `multipart/form-data` content type are forwarded
+/// with an empty body and a [`MultipartExtension`] streaming the original
+/// body. All other requests, and requests the parser cannot be built for, are
+/// forwarded unchanged.
+pub struct MulterBodyService<Inner, S> {
+    inner: Inner,
+    multer: Arc<Multer<S>>,
+}
+
+impl<Inner: Clone, S> Clone for MulterBodyService<Inner, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            multer: Arc::clone(&self.multer),
+        }
+    }
+}
+
+impl<Inner, S> fmt::Debug for MulterBodyService<Inner, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MulterBodyService").finish_non_exhaustive()
+    }
+}
+
+impl<Inner, S, ReqBody> Service<Request<ReqBody>> for MulterBodyService<Inner, S>
+where
+    Inner: Service<Request<ReqBody>>,
+    S: StorageEngine,
+    ReqBody: http_body::Body<Data = Bytes> + Default + Send + 'static,
+    ReqBody::Error: fmt::Display,
+{
+    type Response = Inner::Response;
+    type Error = Inner::Error;
+    type Future = Inner::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let Some(boundary) = request
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| extract_multipart_boundary(value).ok())
+        else {
+            return self.inner.call(request);
+        };
+
+        // The body is handed to the parser only once it was built, so a parser
+        // error leaves the request untouched.
+        let (body_tx, body_rx) = oneshot::channel::<TowerBodyBoxStream>();
+        let deferred_body = Box::pin(
+            body_rx
+                .into_stream()
+                .filter_map(|body| future::ready(body.ok()))
+                .flatten(),
+        ) as TowerBodyBoxStream;
+        let multipart = match self.multer.multipart_from_boundary(boundary, deferred_body) {
+            Ok(multipart) => multipart,
+            Err(err) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %err, "tower: failed to build multipart stream");
+                #[cfg(not(feature = "tracing"))]
+                let _ = err;
+                return self.inner.call(request);
+            }
+        };
+
+        let (mut parts, body) = request.into_parts();
+        let body_stream = Box::pin(body.into_data_stream().map(|item| {
+            item.map_err(|err| ParseError::new(format!("request body stream error: {err}")).into())
+        })) as TowerBodyBoxStream;
+        // The receiver lives inside `multipart`, so the send cannot fail.
+        let _ = body_tx.send(body_stream);
+        let multipart = multipart.with_request_content_length(content_length(&parts.headers));
+        parts
+            .extensions
+            .insert(MultipartExtension(Arc::new(Mutex::new(Some(multipart)))));
+        self.inner
+            .call(Request::from_parts(parts, ReqBody::default()))
+    }
+}
@@ -0,0 +1,81 @@
+//! Warp integration helpers.
+
+use std::{pin::Pin, sync::Arc};
+
+use bytes::{Buf, Bytes};
+use futures::{stream, Stream, StreamExt};
+use warp::{Filter, Rejection};
+
+use crate::{
+    parser::headers::parse_content_length, Multer, MulterError, Multipart, ParseError,
+    StorageEngine,
+};
+
+/// Warp body stream mapped into `multigear` chunk errors.
+pub type WarpBodyStream<S, B> =
+    stream::Map<S, fn(Result<B, warp::Error>) -> Result<Bytes, MulterError>>;
+
+/// Boxed warp body stream used by [`warp_filter`].
+pub type WarpBodyBoxStream =
+    Pin<Box<dyn Stream<Item = Result<Bytes, MulterError>> + Send + 'static>>;
+
+/// Warp multipart type extracted by [`warp_filter`].
+pub type WarpMultipart = Multipart<WarpBodyBoxStream>;
+
+/// Rejection raised by [`warp_filter`] when a request cannot be parsed as multipart.
+///
+/// Recover it with [`Rejection::find`] to build a response, for example
+/// using [`MulterError::http_status`] as the status code.
+#[derive(Debug)]
+pub struct WarpMulterRejection(pub MulterError);
+
+impl warp::reject::Reject for WarpMulterRejection {}
+
+/// Creates a filter extracting a configured [`Multipart`] from the request.
+///
+/// The filter reads the `Content-Type` header for the boundary and streams
+/// the body without buffering it. Failures reject with [`WarpMulterRejection`].
+pub fn warp_filter<S>(
+    multer: Arc<Multer<S>>,
+) -> impl Filter<Extract = (WarpMultipart,), Error = Rejection> + Clone
+where
+    S: StorageEngine,
+{
+    warp::header::optional::<String>("content-type")
+        .and(warp::header::optional::<String>("content-length"))
+        .and(warp::body::stream())
+        .and_then(
+            move |content_type: Option<String>, content_length: Option<String>, body| {
+                let multer = Arc::clone(&multer);
+                async move {
+                    let content_type = content_type.ok_or_else(|| {
+                        warp_rejection(ParseError::new("missing Content-Type header").into())
+                    })?;
+                    let content_length = content_length.as_deref().and_then(parse_content_length);
+                    let body = Box::pin(map_body_stream(body)) as WarpBodyBoxStream;
+                    multer
+                        .multipart_from_content_type(&content_type, body)
+                        .map(|multipart| multipart.with_request_content_length(content_length))
+                        .map_err(warp_rejection)
+                }
+            },
+        )
+}
+
+/// Maps a warp body stream into the stream shape expected by `multigear`.
+pub fn map_body_stream<S, B>(stream: S) -> WarpBodyStream<S, B>
+where
+    S: Stream<Item = Result<B, warp::Error>>,
+    B: Buf,
+{
+    stream.map(warp_item_to_multer::<B>)
+}
+
+fn warp_item_to_multer<B: Buf>(item: Result<B, warp::Error>) -> Result<Bytes, MulterError> {
+    item.map(|mut chunk| chunk.copy_to_bytes(chunk.remaining()))
+        .map_err(|err| ParseError::new(format!("warp body stream error: {err}")).into())
+}
+
+fn warp_rejection(err: MulterError) -> Rejection {
+    warp::reject::custom(WarpMulterRejection(err))
+}
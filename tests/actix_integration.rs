@@ -1,9 +1,13 @@
 #![allow(missing_docs)]
 
 #[cfg(feature = "actix")]
-use actix_web::{http::header, test, web, FromRequest};
+use actix_web::{
+    body::MessageBody, http::header, http::StatusCode, test, web, FromRequest, ResponseError,
+};
 #[cfg(feature = "actix")]
-use multigear::{actix::MulterMiddleware, MemoryStorage, Multer};
+use multigear::{
+    actix::MulterMiddleware, ConfigError, MemoryStorage, Multer, MulterError, StorageError,
+};
 
 #[cfg(feature = "actix")]
 #[actix_web::test]
@@ -44,3 +48,48 @@ async fn parse_method_parses_actix_request_payload() {
 async fn middleware_type_is_constructible() {
     let _middleware = MulterMiddleware;
 }
+
+#[cfg(feature = "actix")]
+#[test]
+fn multer_error_response_uses_error_http_status() {
+    let cases = [
+        (
+            MulterError::BodySizeLimitExceeded { max_body_size: 1 },
+            StatusCode::PAYLOAD_TOO_LARGE,
+        ),
+        (
+            MulterError::MimeTypeNotAllowed {
+                field: "avatar".to_owned(),
+                mime: "text/html".to_owned(),
+            },
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        ),
+        (
+            MulterError::Storage(StorageError::new("disk full")),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ),
+        (MulterError::IncompleteStream, StatusCode::BAD_REQUEST),
+    ];
+
+    for (err, expected) in cases {
+        assert_eq!(err.status_code(), expected);
+        assert_eq!(err.error_response().status(), expected);
+    }
+}
+
+#[cfg(feature = "actix")]
+#[test]
+fn config_error_response_is_internal_server_error() {
+    let err = ConfigError::EmptyFieldsSelector;
+    let response = err.error_response();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let body = response
+        .into_body()
+        .try_into_bytes()
+        .expect("error body should be buffered");
+    assert_eq!(
+        &body[..],
+        b"fields selector must contain at least one field"
+    );
+}
@@ -9,14 +9,22 @@ use std::time::Duration;
 use axum::{
     body::Body,
     extract::FromRequest,
-    http::{header, Request},
+    http::{header, Request, StatusCode},
+    response::IntoResponse,
+    routing::post,
+    Extension, Router,
 };
 #[cfg(feature = "axum")]
 use bytes::Bytes;
 #[cfg(feature = "axum")]
 use futures::channel::mpsc;
 #[cfg(feature = "axum")]
-use multigear::{axum::MulterExtractor, MemoryStorage, Multer};
+use multigear::{
+    axum::{AxumMulterRejection, MulterExtractor, MulterLayer, MulterLayerConfig},
+    ConfigError, MemoryStorage, Multer, MulterError, ProcessedMultipart, StorageError, StoredFile,
+};
+#[cfg(feature = "axum")]
+use tower::ServiceExt;
 
 #[cfg(feature = "axum")]
 #[tokio::test]
@@ -82,3 +90,133 @@ async fn multer_extractor_is_streaming_and_does_not_require_full_body() {
     assert_eq!(part.field_name(), "field");
     assert_eq!(part.text().await.expect("text body should decode"), "value");
 }
+
+#[cfg(feature = "axum")]
+fn layered_router(config: MulterLayerConfig) -> Router {
+    async fn handler(
+        processed: Option<Extension<ProcessedMultipart<StoredFile>>>,
+    ) -> impl IntoResponse {
+        match processed {
+            Some(Extension(processed)) => format!(
+                "files={} text={}",
+                processed.stored_files.len(),
+                processed.text_fields.len()
+            ),
+            None => "skipped".to_owned(),
+        }
+    }
+
+    let multer = Arc::new(Multer::new(MemoryStorage::new()));
+    Router::new()
+        .route("/upload", post(handler))
+        .layer(MulterLayer::new(multer).with_config(config))
+}
+
+#[cfg(feature = "axum")]
+async fn response_text(response: axum::response::Response) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("response body should read");
+    String::from_utf8(bytes.to_vec()).expect("response body should be UTF-8")
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn multer_layer_populates_processed_multipart_extension() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"avatar\"; filename=\"a.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "PNG\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND--\r\n"
+    );
+    let request = Request::builder()
+        .method("POST")
+        .uri("/upload")
+        .header(header::CONTENT_TYPE, "multipart/form-data; boundary=BOUND")
+        .body(Body::from(body))
+        .expect("request should build");
+
+    let response = layered_router(MulterLayerConfig::default())
+        .oneshot(request)
+        .await
+        .expect("router should respond");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response_text(response).await, "files=1 text=1");
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn multer_layer_rejects_or_skips_non_multipart_requests() {
+    let request = || {
+        Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("{}"))
+            .expect("request should build")
+    };
+
+    let rejected = layered_router(MulterLayerConfig::default())
+        .oneshot(request())
+        .await
+        .expect("router should respond");
+    assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+
+    let skipped = layered_router(MulterLayerConfig {
+        skip_on_non_multipart: true,
+    })
+    .oneshot(request())
+    .await
+    .expect("router should respond");
+    assert_eq!(skipped.status(), StatusCode::OK);
+    assert_eq!(response_text(skipped).await, "skipped");
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn rejection_response_uses_error_http_status() {
+    let cases = [
+        (
+            MulterError::FileSizeLimitExceeded {
+                field: "avatar".to_owned(),
+                max_file_size: 1,
+            },
+            StatusCode::PAYLOAD_TOO_LARGE,
+        ),
+        (
+            MulterError::MimeTypeNotAllowed {
+                field: "avatar".to_owned(),
+                mime: "text/html".to_owned(),
+            },
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        ),
+        (
+            MulterError::Storage(StorageError::new("disk full")),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ),
+        (MulterError::IncompleteStream, StatusCode::BAD_REQUEST),
+    ];
+
+    for (err, expected) in cases {
+        let response = AxumMulterRejection(err).into_response();
+        assert_eq!(response.status(), expected);
+    }
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn config_error_responds_with_internal_server_error() {
+    let response = ConfigError::EmptyFieldsSelector.into_response();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        response_text(response).await,
+        "fields selector must contain at least one field"
+    );
+}
@@ -1,8 +1,10 @@
 #![allow(missing_docs)]
 
+use bytes::Bytes;
+use futures::TryStreamExt;
 use multigear::{
-    ConfigError, Field, Limits, Multer, MulterBuilder, MulterConfig, SelectedFieldKind, Selector,
-    UnknownFieldPolicy,
+    ConfigError, Field, Limits, Multer, MulterBuilder, MulterConfig, Multipart,
+    MultipartBodyBuilder, SelectedFieldKind, Selector, UnknownFieldPolicy,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -43,6 +45,7 @@ fn fluent_chaining_sets_expected_configuration() {
             selector: Selector::single("avatar"),
             unknown_field_policy: UnknownFieldPolicy::Reject,
             limits,
+            ..MulterConfig::default()
         }
     );
 }
@@ -168,3 +171,132 @@ fn fields_support_file_and_text_models() {
         other => panic!("expected fields selector, got {other:?}"),
     }
 }
+
+const ENV_VARS: [&str; 6] = [
+    "MULTER_MAX_FILE_SIZE",
+    "MULTER_MAX_FILES",
+    "MULTER_MAX_FIELD_SIZE",
+    "MULTER_MAX_FIELDS",
+    "MULTER_MAX_BODY_SIZE",
+    "MULTER_ALLOWED_MIME_TYPES",
+];
+
+#[test]
+fn from_env_reads_all_limit_variables() {
+    let values = [
+        Some("1024"),
+        Some("3"),
+        Some(" 256 "),
+        Some("8"),
+        Some("4096"),
+        Some("image/*, application/pdf,,"),
+    ];
+    let vars = ENV_VARS.into_iter().zip(values).collect::<Vec<_>>();
+    let builder =
+        temp_env::with_vars(vars, MulterBuilder::from_env).expect("environment should parse");
+
+    assert_eq!(
+        builder.config().limits,
+        Limits {
+            max_file_size: Some(1024),
+            max_files: Some(3),
+            max_field_size: Some(256),
+            max_fields: Some(8),
+            max_parts: None,
+            max_body_size: Some(4096),
+            max_header_size: None,
+            part_read_timeout: None,
+            allowed_mime_types: vec!["image/*".to_owned(), "application/pdf".to_owned()],
+        }
+    );
+}
+
+#[test]
+fn from_env_keeps_defaults_for_missing_variables() {
+    let vars = ENV_VARS
+        .into_iter()
+        .map(|name| (name, (name == "MULTER_MAX_FILES").then_some("2")))
+        .collect::<Vec<_>>();
+    let builder =
+        temp_env::with_vars(vars, MulterBuilder::from_env).expect("environment should parse");
+
+    assert_eq!(
+        builder.config().limits,
+        Limits {
+            max_files: Some(2),
+            ..Limits::default()
+        }
+    );
+}
+
+#[test]
+fn from_env_rejects_unparseable_values() {
+    for (name, value) in [
+        ("MULTER_MAX_FILE_SIZE", "ten"),
+        ("MULTER_MAX_FILES", "-1"),
+        ("MULTER_MAX_BODY_SIZE", ""),
+    ] {
+        let vars = ENV_VARS
+            .into_iter()
+            .map(|var| (var, (var == name).then_some(value)))
+            .collect::<Vec<_>>();
+        let err = temp_env::with_vars(vars, MulterBuilder::from_env)
+            .expect_err("invalid value should fail");
+        assert_eq!(err, ConfigError::InvalidLimitValue { limit: name });
+    }
+}
+
+#[tokio::test]
+async fn multipart_body_builder_produces_exact_bytes_and_stream() {
+    let builder = MultipartBodyBuilder::new()
+        .part("title", Bytes::from_static(b"Holiday"))
+        .file("photo", "a.png", "image/png", Bytes::from_static(b"PNG"))
+        .file_without_content_type("raw", "b.bin", Bytes::from_static(b"\x00\x01"));
+
+    let expected: &[u8] = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"title\"\r\n",
+        "\r\n",
+        "Holiday\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"photo\"; filename=\"a.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "PNG\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"raw\"; filename=\"b.bin\"\r\n",
+        "\r\n",
+        "\x00\x01\r\n",
+        "--BOUND--\r\n"
+    )
+    .as_bytes();
+    assert_eq!(builder.build("BOUND"), expected);
+    assert_eq!(
+        MultipartBodyBuilder::new().build("BOUND"),
+        &b"--BOUND--\r\n"[..]
+    );
+
+    let chunks = builder
+        .stream("BOUND")
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("stream should not fail");
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks.concat(), expected);
+
+    let collected = Multipart::new("BOUND", builder.stream("BOUND"))
+        .expect("multipart should initialize")
+        .parse_all()
+        .await
+        .expect("built body should parse");
+    assert_eq!(collected.text("title"), Some("Holiday"));
+    assert_eq!(collected.file("photo").expect("photo").body, "PNG");
+    assert_eq!(
+        collected
+            .file("raw")
+            .expect("raw")
+            .content_type()
+            .essence_str(),
+        "application/octet-stream"
+    );
+}
@@ -0,0 +1,116 @@
+#![allow(missing_docs)]
+#![cfg(feature = "serde")]
+
+use std::time::Duration;
+
+use multigear::{
+    FilenameStrategy, Limits, MulterConfig, SelectedField, Selector, UnknownFieldAction,
+    UnknownFieldPolicy,
+};
+
+fn sample_config() -> MulterConfig {
+    MulterConfig {
+        selector: Selector::fields([
+            SelectedField::file("avatar")
+                .with_max_count(1)
+                .with_allowed_mime_types(["image/*"]),
+            SelectedField::text("title").with_min_count(1),
+        ]),
+        unknown_field_policy: UnknownFieldPolicy::Redirect {
+            to: "misc".to_owned(),
+        },
+        limits: Limits {
+            max_file_size: Some(1024 * 1024),
+            max_files: Some(4),
+            max_body_size: Some(8 * 1024 * 1024),
+            part_read_timeout: Some(Duration::from_secs(30)),
+            allowed_mime_types: vec!["image/*".to_owned(), "application/pdf".to_owned()],
+            ..Limits::default()
+        },
+        require_at_least_one_file: true,
+        field_count_hint: Some(8),
+        ..MulterConfig::default()
+    }
+}
+
+#[test]
+fn config_round_trips_through_json() {
+    let config = sample_config();
+    let json = serde_json::to_string(&config).expect("config should serialize");
+
+    assert_eq!(
+        MulterConfig::from_json(&json).expect("config should deserialize"),
+        config
+    );
+}
+
+#[test]
+fn config_round_trips_through_toml() {
+    let config = sample_config();
+    let toml = toml::to_string(&config).expect("config should serialize");
+
+    assert_eq!(
+        MulterConfig::from_toml(&toml).expect("config should deserialize"),
+        config
+    );
+}
+
+#[test]
+fn partial_documents_keep_defaults() {
+    let from_json = MulterConfig::from_json(r#"{"limits": {"max_files": 2}}"#)
+        .expect("partial JSON should deserialize");
+    let from_toml = MulterConfig::from_toml("[limits]\nmax_files = 2\n")
+        .expect("partial TOML should deserialize");
+    let expected = MulterConfig {
+        limits: Limits {
+            max_files: Some(2),
+            ..Limits::default()
+        },
+        ..MulterConfig::default()
+    };
+
+    assert_eq!(from_json, expected);
+    assert_eq!(from_toml, expected);
+    assert!(MulterConfig::from_json(r#"{"limits": {"max_files": "two"}}"#).is_err());
+    assert!(MulterConfig::from_toml("limits = 3").is_err());
+}
+
+#[test]
+fn filename_strategy_serializes_builtin_variants_only() {
+    for strategy in [
+        FilenameStrategy::Keep,
+        FilenameStrategy::Random,
+        FilenameStrategy::KeepExtension,
+        FilenameStrategy::Timestamp {
+            format: "%Y%m%d%H%M%S".to_owned(),
+        },
+    ] {
+        let json = serde_json::to_string(&strategy).expect("strategy should serialize");
+        let restored: FilenameStrategy =
+            serde_json::from_str(&json).expect("strategy should deserialize");
+        assert_eq!(restored, strategy);
+    }
+    assert_eq!(
+        serde_json::to_string(&FilenameStrategy::KeepExtension).expect("serialize"),
+        "\"KeepExtension\""
+    );
+
+    let custom = FilenameStrategy::Custom(std::sync::Arc::new(|name| name));
+    assert!(serde_json::to_string(&custom).is_err());
+    assert!(serde_json::from_str::<FilenameStrategy>("\"Custom\"").is_err());
+}
+
+#[test]
+fn callback_policy_serializes_as_callback_and_restores_as_ignore() {
+    let policy = UnknownFieldPolicy::callback(|_| UnknownFieldAction::Reject);
+    let json = serde_json::to_string(&policy).expect("policy should serialize");
+    assert_eq!(json, r#""callback""#);
+
+    let restored: UnknownFieldPolicy =
+        serde_json::from_str(&json).expect("policy should deserialize");
+    assert_eq!(restored, UnknownFieldPolicy::Ignore);
+    assert_eq!(
+        serde_json::to_string(&UnknownFieldPolicy::Reject).expect("policy should serialize"),
+        r#""Reject""#
+    );
+}
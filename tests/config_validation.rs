@@ -1,9 +1,21 @@
 #![allow(missing_docs)]
 
 use multigear::{
-    ConfigError, Limits, MulterBuilder, MulterConfig, SelectedField, Selector, UnknownFieldPolicy,
+    ConfigDiff, ConfigError, Field, Limits, MulterBuilder, MulterConfig, SelectedField,
+    SelectedFieldKind, Selector, UnknownFieldPolicy,
 };
 
+#[test]
+fn selected_field_constructors_state_kind() {
+    assert_eq!(SelectedField::text("m").kind, SelectedFieldKind::Text);
+    assert_eq!(SelectedField::file("m").kind, SelectedFieldKind::File);
+    assert_eq!(SelectedField::file("m"), Field::new("m"));
+
+    #[allow(deprecated)]
+    let legacy = SelectedField::new("m");
+    assert_eq!(legacy, SelectedField::file("m"));
+}
+
 #[test]
 fn rejects_empty_single_selector_name() {
     let config = MulterConfig {
@@ -46,7 +58,7 @@ fn rejects_empty_fields_selector() {
 #[test]
 fn rejects_duplicate_field_names_in_fields_selector() {
     let config = MulterConfig {
-        selector: Selector::fields([SelectedField::new("avatar"), SelectedField::new("avatar")]),
+        selector: Selector::fields([SelectedField::file("avatar"), SelectedField::file("avatar")]),
         ..MulterConfig::default()
     };
 
@@ -93,6 +105,46 @@ fn rejects_part_limit_greater_than_max_body_size() {
     ));
 }
 
+#[test]
+fn rejects_max_parts_below_files_plus_fields() {
+    let limits = Limits {
+        max_files: Some(2),
+        max_fields: Some(3),
+        max_parts: Some(4),
+        ..Limits::default()
+    };
+    let config = MulterConfig {
+        limits: limits.clone(),
+        ..MulterConfig::default()
+    };
+
+    assert_eq!(
+        config.validate(),
+        Err(ConfigError::PartsLimitBelowCombined {
+            max_parts: 4,
+            max_files: 2,
+            max_fields: 3,
+        })
+    );
+
+    for limits in [
+        Limits {
+            max_parts: Some(5),
+            ..limits.clone()
+        },
+        Limits {
+            max_fields: None,
+            ..limits
+        },
+    ] {
+        let config = MulterConfig {
+            limits,
+            ..MulterConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+}
+
 #[test]
 fn rejects_invalid_mime_pattern() {
     let limits = Limits {
@@ -115,7 +167,7 @@ fn rejects_invalid_mime_pattern() {
 #[test]
 fn rejects_invalid_selected_field_mime_pattern() {
     let config = MulterConfig {
-        selector: Selector::fields([SelectedField::new("avatar").allowed_mime_types(["image"])]),
+        selector: Selector::fields([SelectedField::file("avatar").allowed_mime_types(["image"])]),
         ..MulterConfig::default()
     };
 
@@ -140,6 +192,132 @@ fn rejects_invalid_selected_field_max_size() {
     ));
 }
 
+#[test]
+fn rejects_empty_selected_field_extension() {
+    let config = MulterConfig {
+        selector: Selector::fields([
+            SelectedField::file("docs").with_allowed_extensions(["pdf", "."])
+        ]),
+        ..MulterConfig::default()
+    };
+
+    let result = config.validate();
+    assert!(matches!(
+        result,
+        Err(ConfigError::InvalidExtension { name }) if name == "docs"
+    ));
+}
+
+#[test]
+fn rejects_selected_field_min_count_above_max_count() {
+    let config = MulterConfig {
+        selector: Selector::fields([SelectedField::file("docs").max_count(1).min_count(2)]),
+        ..MulterConfig::default()
+    };
+
+    let result = config.validate();
+    assert!(matches!(
+        result,
+        Err(ConfigError::MinCountExceedsMaxCount {
+            name,
+            min_count: 2,
+            max_count: 1,
+        }) if name == "docs"
+    ));
+}
+
+#[test]
+fn required_and_optional_fields_validate_with_other_constraints() {
+    let validate = |field: SelectedField| {
+        MulterConfig {
+            selector: Selector::fields([field]),
+            ..MulterConfig::default()
+        }
+        .validate()
+    };
+
+    let required = SelectedField::file("avatar").required();
+    assert_eq!((required.min_count, required.max_count), (1, Some(1)));
+    let optional = SelectedField::file("avatar").required().optional();
+    assert_eq!((optional.min_count, optional.max_count), (0, Some(1)));
+    assert_eq!(
+        validate(
+            required
+                .clone()
+                .allowed_mime_types(["image/png", "image/*"])
+        ),
+        Ok(())
+    );
+    assert_eq!(validate(optional.allowed_mime_types(["image/*"])), Ok(()));
+
+    assert_eq!(
+        validate(required.clone().min_count(2)),
+        Err(ConfigError::MinCountExceedsMaxCount {
+            name: "avatar".to_owned(),
+            min_count: 2,
+            max_count: 1,
+        })
+    );
+    assert_eq!(
+        validate(required.clone().max_count(0)),
+        Err(ConfigError::InvalidFieldMaxCount {
+            name: "avatar".to_owned(),
+        })
+    );
+    assert_eq!(
+        validate(required.allowed_mime_types(["image"])),
+        Err(ConfigError::InvalidMimePattern {
+            pattern: "image".to_owned(),
+        })
+    );
+    assert_eq!(
+        validate(SelectedField::file(" ").required()),
+        Err(ConfigError::EmptyFieldName)
+    );
+}
+
+#[test]
+fn field_models_convert_required_and_optional_counts() {
+    let file: SelectedField = Field::file("avatar")
+        .required()
+        .allowed_mime_types(["image/*"])
+        .into();
+    assert_eq!(
+        file,
+        SelectedField::file("avatar")
+            .required()
+            .allowed_mime_types(["image/*"])
+    );
+
+    let optional: SelectedField = Field::file("avatar").required().optional().into();
+    assert_eq!(optional, SelectedField::file("avatar").optional());
+
+    let text: SelectedField = Field::text("caption").required().into();
+    assert_eq!((text.min_count, text.max_count), (1, None));
+    let text: SelectedField = Field::text("caption").required().optional().into();
+    assert_eq!(text.min_count, 0);
+
+    let multer = MulterBuilder::new()
+        .fields([
+            Field::file("avatar").required(),
+            Field::text("caption").required(),
+        ])
+        .build();
+    assert!(multer.is_ok());
+}
+
+#[test]
+fn rejects_empty_redirect_target() {
+    let config = MulterConfig {
+        selector: Selector::array("attachment", 4),
+        unknown_field_policy: UnknownFieldPolicy::Redirect { to: " ".to_owned() },
+        ..MulterConfig::default()
+    };
+
+    let result = config.validate();
+    assert!(matches!(result, Err(ConfigError::EmptyFieldName)));
+}
+
 #[test]
 fn builder_validation_surfaces_config_errors() {
     let config = MulterConfig {
@@ -151,3 +329,270 @@ fn builder_validation_surfaces_config_errors() {
     let result = MulterBuilder::new().with_config(config).build_config();
     assert!(matches!(result, Err(ConfigError::EmptyFieldName)));
 }
+
+#[test]
+fn diff_from_default_reports_changed_limits_and_selector() {
+    let config = MulterConfig {
+        selector: Selector::single("avatar"),
+        limits: Limits {
+            max_files: Some(3),
+            ..Limits::default()
+        },
+        ..MulterConfig::default()
+    };
+
+    let diffs = config.diff_from_default();
+    assert_eq!(diffs.len(), 2);
+    assert!(diffs.contains(&ConfigDiff::SelectorChanged {
+        from: Selector::Any,
+        to: Selector::single("avatar"),
+    }));
+    assert!(diffs.contains(&ConfigDiff::LimitChanged {
+        limit: "max_files",
+        from: None,
+        to: Some(3),
+    }));
+    assert!(diffs
+        .iter()
+        .any(|diff| diff.to_string() == "max_files: None -> Some(3)"));
+
+    assert!(MulterConfig::default().diff_from_default().is_empty());
+    assert_eq!(
+        config.diff(&MulterConfig::default()),
+        vec![
+            ConfigDiff::SelectorChanged {
+                from: Selector::single("avatar"),
+                to: Selector::Any,
+            },
+            ConfigDiff::LimitChanged {
+                limit: "max_files",
+                from: Some(3),
+                to: None,
+            },
+        ]
+    );
+}
+
+fn base_config() -> MulterConfig {
+    MulterConfig {
+        selector: Selector::array("photos", 4),
+        unknown_field_policy: UnknownFieldPolicy::Reject,
+        limits: Limits {
+            max_file_size: Some(1024),
+            max_files: Some(4),
+            max_body_size: Some(8192),
+            allowed_mime_types: vec!["image/*".to_owned()],
+            ..Limits::default()
+        },
+        ..MulterConfig::default()
+    }
+}
+
+#[test]
+fn merge_replaces_selector_only_when_override_is_not_default() {
+    let kept = base_config().merge(MulterConfig::default());
+    assert_eq!(kept.selector, Selector::array("photos", 4));
+    assert_eq!(kept.unknown_field_policy, UnknownFieldPolicy::Ignore);
+
+    let replaced = base_config().merge(MulterConfig {
+        selector: Selector::single("avatar"),
+        unknown_field_policy: UnknownFieldPolicy::Reject,
+        ..MulterConfig::default()
+    });
+    assert_eq!(replaced.selector, Selector::single("avatar"));
+    assert_eq!(replaced.unknown_field_policy, UnknownFieldPolicy::Reject);
+    assert_eq!(replaced.limits, base_config().limits);
+}
+
+#[test]
+fn merge_overrides_only_limits_set_in_override() {
+    let merged = base_config().merge(MulterConfig {
+        limits: Limits {
+            max_files: Some(1),
+            max_field_size: Some(256),
+            ..Limits::default()
+        },
+        ..MulterConfig::default()
+    });
+
+    assert_eq!(
+        merged.limits,
+        Limits {
+            max_file_size: Some(1024),
+            max_files: Some(1),
+            max_field_size: Some(256),
+            max_fields: None,
+            max_parts: None,
+            max_body_size: Some(8192),
+            max_header_size: None,
+            part_read_timeout: None,
+            allowed_mime_types: vec!["image/*".to_owned()],
+        }
+    );
+    assert!(merged.validate().is_ok());
+}
+
+#[test]
+fn merge_with_full_override_takes_every_override_value() {
+    let full = MulterConfig {
+        selector: Selector::none(),
+        unknown_field_policy: UnknownFieldPolicy::Redirect {
+            to: "misc".to_owned(),
+        },
+        limits: Limits {
+            max_file_size: Some(1),
+            max_files: Some(2),
+            max_field_size: Some(3),
+            max_fields: Some(4),
+            max_parts: Some(8),
+            max_body_size: Some(5),
+            max_header_size: Some(6),
+            part_read_timeout: Some(std::time::Duration::from_secs(7)),
+            allowed_mime_types: vec!["text/plain".to_owned()],
+        },
+        require_at_least_one_file: true,
+        field_count_hint: Some(7),
+        ..MulterConfig::default()
+    };
+
+    assert_eq!(base_config().merge(full.clone()), full);
+}
+
+#[test]
+fn builder_base_config_seeds_settings_that_later_calls_override() {
+    let multer = MulterBuilder::new()
+        .base_config(base_config())
+        .max_files(2)
+        .build()
+        .expect("config should validate");
+
+    assert_eq!(multer.config().selector, Selector::array("photos", 4));
+    assert_eq!(multer.config().limits.max_files, Some(2));
+    assert_eq!(multer.config().limits.max_file_size, Some(1024));
+}
+
+const LIMIT_NAMES: [&str; 8] = [
+    "max_file_size",
+    "max_files",
+    "max_field_size",
+    "max_fields",
+    "max_parts",
+    "max_body_size",
+    "max_header_size",
+    "part_read_timeout",
+];
+
+fn with_limit(name: &str, value: Option<u64>) -> Limits {
+    let count = value.map(|value| value as usize);
+    let mut limits = Limits::default();
+    match name {
+        "max_file_size" => limits.max_file_size = value,
+        "max_files" => limits.max_files = count,
+        "max_field_size" => limits.max_field_size = value,
+        "max_fields" => limits.max_fields = count,
+        "max_parts" => limits.max_parts = count,
+        "max_body_size" => limits.max_body_size = value,
+        "max_header_size" => limits.max_header_size = count,
+        "part_read_timeout" => {
+            limits.part_read_timeout = value.map(std::time::Duration::from_secs);
+        }
+        _ => unreachable!("unknown limit {name}"),
+    }
+    limits
+}
+
+#[test]
+fn limits_min_keeps_stricter_value_for_every_none_some_combination() {
+    let cases = [
+        (None, None, None),
+        (Some(5), None, Some(5)),
+        (None, Some(5), Some(5)),
+        (Some(5), Some(9), Some(5)),
+        (Some(9), Some(5), Some(5)),
+    ];
+    for name in LIMIT_NAMES {
+        for (a, b, expected) in cases {
+            assert_eq!(
+                Limits::min(with_limit(name, a), with_limit(name, b)),
+                with_limit(name, expected),
+                "{name}: min({a:?}, {b:?})"
+            );
+        }
+    }
+}
+
+#[test]
+fn limits_min_unions_mime_allowlists() {
+    let combined = Limits::min(
+        Limits {
+            allowed_mime_types: vec!["image/*".to_owned(), "application/pdf".to_owned()],
+            ..Limits::default()
+        },
+        Limits {
+            allowed_mime_types: vec!["application/pdf".to_owned(), "text/plain".to_owned()],
+            ..Limits::default()
+        },
+    );
+    assert_eq!(
+        combined.allowed_mime_types,
+        ["image/*", "application/pdf", "text/plain"]
+    );
+}
+
+#[test]
+fn is_stricter_than_compares_limits_set_on_both_sides() {
+    let cases = [
+        (None, None, false),
+        (Some(1), None, false),
+        (None, Some(1), false),
+        (Some(1), Some(2), true),
+        (Some(2), Some(1), false),
+        (Some(1), Some(1), false),
+    ];
+    for name in LIMIT_NAMES {
+        for (a, b, expected) in cases {
+            assert_eq!(
+                with_limit(name, a).is_stricter_than(&with_limit(name, b)),
+                expected,
+                "{name}: {a:?} stricter than {b:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn is_stricter_than_requires_every_shared_limit_to_be_lower() {
+    let route = Limits {
+        max_file_size: Some(1024),
+        max_files: Some(1),
+        ..Limits::default()
+    };
+    let global = Limits {
+        max_file_size: Some(4096),
+        max_files: Some(8),
+        max_body_size: Some(1),
+        ..Limits::default()
+    };
+    assert!(route.is_stricter_than(&global));
+
+    let mixed = Limits {
+        max_files: Some(16),
+        ..route.clone()
+    };
+    assert!(!mixed.is_stricter_than(&global));
+    assert!(!global.is_stricter_than(&mixed));
+}
+
+#[cfg(not(feature = "regex"))]
+#[test]
+fn regex_selector_requires_regex_feature() {
+    let selector = Selector::Regex {
+        pattern: "attachment_[0-9]+".to_owned(),
+        max_count: None,
+    };
+
+    assert!(matches!(
+        selector.validate(),
+        Err(ConfigError::InvalidRegexPattern { .. })
+    ));
+}
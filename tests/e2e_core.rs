@@ -1,10 +1,15 @@
 #![allow(missing_docs)]
 
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
 use bytes::Bytes;
-use futures::stream;
+use futures::{stream, FutureExt};
 use multigear::{
-    Limits, MemoryStorage, Multer, MulterConfig, MulterError, Selector, StorageError,
-    UnknownFieldPolicy,
+    ConfigError, Limits, MemoryStorage, Multer, MulterConfig, MulterError, ProcessedMultipart,
+    SelectedField, Selector, StoredFile, TeeStorageEngine, UnknownFieldPolicy,
 };
 use tokio::io::AsyncWriteExt;
 
@@ -20,6 +25,7 @@ async fn parse_and_store_wires_parser_selector_limits_and_storage() {
             allowed_mime_types: vec!["image/*".to_owned()],
             ..Limits::default()
         },
+        ..MulterConfig::default()
     };
     let multer = Multer::with_config(storage.clone(), config).expect("config should validate");
 
@@ -60,6 +66,139 @@ async fn parse_and_store_wires_parser_selector_limits_and_storage() {
     assert_eq!(bytes, Bytes::from_static(b"PNGDATA"));
 }
 
+#[tokio::test]
+async fn processed_multipart_accessors_support_multi_value_fields() {
+    let multer = Multer::new(MemoryStorage::new());
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"tag\"\r\n",
+        "\r\n",
+        "red\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"photos\"; filename=\"a.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "A\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"tag\"\r\n",
+        "\r\n",
+        "blue\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"photos\"; filename=\"b.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "BB\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let output = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                body.as_bytes(),
+            ))]),
+        )
+        .await
+        .expect("pipeline should succeed");
+
+    assert_eq!(output.text("tag"), Some("red"));
+    assert_eq!(output.all_text("tag").collect::<Vec<_>>(), ["red", "blue"]);
+    assert_eq!(output.text("missing"), None);
+    assert_eq!(output.all_text("missing").count(), 0);
+
+    let first = output.file("photos").expect("file expected");
+    assert_eq!(first.file_name.as_deref(), Some("a.png"));
+    let names = output
+        .all_files("photos")
+        .map(|file| file.file_name.as_deref().unwrap_or_default())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a.png", "b.png"]);
+    assert!(output.file("tag").is_none());
+}
+
+#[tokio::test]
+async fn processed_multipart_reports_size_totals() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"title\"\r\n",
+        "\r\n",
+        "Holiday\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"photos\"; filename=\"a.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "AAAA\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "\r\n",
+        "hi\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"photos\"; filename=\"b.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "BBBBBB\r\n",
+        "--BOUND--\r\n"
+    );
+    let input = || {
+        stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+            body.as_bytes(),
+        ))])
+    };
+
+    let output = Multer::new(MemoryStorage::new())
+        .parse_and_store("BOUND", input())
+        .await
+        .expect("pipeline should succeed");
+    assert_eq!(output.total_file_bytes(), 10);
+    assert_eq!(output.total_text_bytes(), 9);
+    assert_eq!(output.part_count(), 4);
+
+    let tee = TeeStorageEngine::new(MemoryStorage::new(), MemoryStorage::new());
+    let teed = Multer::new(tee)
+        .parse_and_store("BOUND", input())
+        .await
+        .expect("tee pipeline should succeed");
+    assert_eq!(teed.total_file_bytes(), 10);
+
+    let empty = ProcessedMultipart::<StoredFile>::default();
+    assert_eq!(empty.total_file_bytes(), 0);
+    assert_eq!(empty.total_text_bytes(), 0);
+    assert_eq!(empty.part_count(), 0);
+}
+
+#[tokio::test]
+async fn parse_and_store_validates_required_fields_before_returning() {
+    let multer = Multer::builder()
+        .fields([
+            SelectedField::file("avatar"),
+            SelectedField::text("title").with_min_count(1),
+        ])
+        .storage(MemoryStorage::new())
+        .build()
+        .expect("config should validate");
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"title\"\r\n",
+        "\r\n",
+        "\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let err = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                body.as_bytes(),
+            ))]),
+        )
+        .await
+        .expect_err("empty required text field must fail");
+    assert!(matches!(
+        err,
+        MulterError::RequiredFieldMissing { field, min_count: 1 } if field == "title"
+    ));
+}
+
 #[tokio::test]
 async fn multipart_from_content_type_is_framework_agnostic_entry_point() {
     let multer = Multer::new(MemoryStorage::new());
@@ -88,6 +227,71 @@ async fn multipart_from_content_type_is_framework_agnostic_entry_point() {
     assert_eq!(part.field_name(), "field");
 }
 
+#[tokio::test]
+async fn parse_bytes_reads_single_part_body() {
+    let multer = Multer::new(MemoryStorage::new());
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"field\"\r\n",
+        "\r\n",
+        "value\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let mut multipart = multer
+        .parse_bytes("BOUND", Bytes::from_static(body.as_bytes()))
+        .expect("multipart should initialize");
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    assert_eq!(part.field_name(), "field");
+    assert_eq!(part.text().await.expect("text should read"), "value");
+    assert!(multipart
+        .next_part()
+        .await
+        .expect("end should parse")
+        .is_none());
+}
+
+#[tokio::test]
+async fn parse_bytes_and_parse_vec_read_multi_part_bodies() {
+    let multer = Multer::builder()
+        .single("avatar")
+        .storage(MemoryStorage::new())
+        .build()
+        .expect("multer should build");
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"title\"\r\n",
+        "\r\n",
+        "Profile\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"avatar\"; filename=\"a.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "PNG\r\n",
+        "--BOUND--\r\n"
+    );
+
+    for mut multipart in [
+        multer
+            .parse_bytes("BOUND", Bytes::from_static(body.as_bytes()))
+            .expect("bytes should initialize"),
+        multer
+            .parse_vec("BOUND", body.as_bytes().to_vec())
+            .expect("vec should initialize"),
+    ] {
+        let collected = multipart.parse_all().await.expect("body should parse");
+        assert_eq!(collected.text("title"), Some("Profile"));
+        let avatar = collected.file("avatar").expect("avatar expected");
+        assert_eq!(avatar.body, "PNG");
+    }
+
+    assert!(multer.parse_bytes("", Bytes::new()).is_err());
+}
+
 #[tokio::test]
 async fn parse_reader_accepts_async_read_input() {
     let multer = Multer::new(MemoryStorage::new());
@@ -137,14 +341,42 @@ async fn parse_and_store_reports_malformed_stream_regression() {
             ))]),
         )
         .await;
-    let is_expected = match result {
-        Err(MulterError::IncompleteStream) => true,
-        Err(MulterError::Storage(StorageError::Message { message })) => {
-            message.contains("ended unexpectedly")
-        }
-        _ => false,
-    };
-    assert!(is_expected, "unexpected malformed-stream result");
+    assert!(
+        matches!(result, Err(MulterError::IncompleteStream)),
+        "unexpected malformed-stream result"
+    );
+}
+
+#[tokio::test]
+async fn parse_and_store_reports_oversized_file_as_size_error() {
+    let multer = Multer::builder()
+        .storage(MemoryStorage::new())
+        .max_file_size(4)
+        .build()
+        .expect("config should be valid");
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"a.bin\"\r\n",
+        "\r\n",
+        "payload\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let err = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                body.as_bytes(),
+            ))]),
+        )
+        .await
+        .expect_err("oversized file should be rejected");
+
+    assert!(
+        matches!(err, MulterError::FileSizeLimitExceeded { .. }),
+        "unexpected error: {err:?}"
+    );
+    assert_eq!(err.http_status(), 413);
 }
 
 #[tokio::test]
@@ -176,3 +408,321 @@ async fn parse_and_store_respects_unknown_field_policy_regression() {
         Err(MulterError::UnexpectedField { field }) if field == "other"
     ));
 }
+
+#[tokio::test]
+async fn redirect_policy_stores_unknown_file_fields_under_target_name() {
+    let config = MulterConfig {
+        selector: Selector::array("attachment", 10),
+        unknown_field_policy: UnknownFieldPolicy::Redirect {
+            to: "attachment".to_owned(),
+        },
+        ..MulterConfig::default()
+    };
+    let multer = Multer::with_config(MemoryStorage::new(), config).expect("config should validate");
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"unknown_field\"; filename=\"x.bin\"\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"attachment\"; filename=\"y.bin\"\r\n",
+        "\r\n",
+        "world\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let output = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                body.as_bytes(),
+            ))]),
+        )
+        .await
+        .expect("redirected upload should succeed");
+
+    assert_eq!(output.stored_files.len(), 2);
+    assert_eq!(output.stored_files[0].field_name, "attachment");
+    assert_eq!(output.stored_files[0].file_name.as_deref(), Some("x.bin"));
+    assert_eq!(output.stored_files[1].field_name, "attachment");
+}
+
+#[tokio::test]
+async fn redirected_file_fields_count_toward_target_limits() {
+    let config = MulterConfig {
+        selector: Selector::array("attachment", 1),
+        unknown_field_policy: UnknownFieldPolicy::Redirect {
+            to: "attachment".to_owned(),
+        },
+        ..MulterConfig::default()
+    };
+    let multer = Multer::with_config(MemoryStorage::new(), config).expect("config should validate");
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"first\"; filename=\"a.bin\"\r\n",
+        "\r\n",
+        "a\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"second\"; filename=\"b.bin\"\r\n",
+        "\r\n",
+        "b\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let result = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                body.as_bytes(),
+            ))]),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(MulterError::FieldCountLimitExceeded { field, max_count: 1 }) if field == "attachment"
+    ));
+}
+
+#[tokio::test]
+async fn completion_hooks_fire_once_per_successful_parse_and_store() {
+    let sync_calls = Arc::new(AtomicUsize::new(0));
+    let async_files = Arc::new(AtomicUsize::new(0));
+    let sync_counter = Arc::clone(&sync_calls);
+    let async_counter = Arc::clone(&async_files);
+
+    let multer = Multer::builder()
+        .storage(MemoryStorage::new())
+        .on_complete(move |_| {
+            sync_counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .on_complete_async(move |out| {
+            let counter = Arc::clone(&async_counter);
+            let files = out.stored_files.len();
+            async move {
+                counter.fetch_add(files, Ordering::SeqCst);
+            }
+            .boxed()
+        })
+        .build()
+        .expect("builder config");
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND--\r\n"
+    );
+
+    for _ in 0..2 {
+        multer
+            .parse_and_store(
+                "BOUND",
+                stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                    body.as_bytes(),
+                ))]),
+            )
+            .await
+            .expect("pipeline should succeed");
+    }
+
+    let failed = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(b"--BOUND\r\n"))]),
+        )
+        .await;
+    assert!(failed.is_err());
+
+    assert_eq!(sync_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(async_files.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn completion_hooks_receive_non_stored_file_output() {
+    let mirrored = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&mirrored);
+
+    let multer = Multer::builder()
+        .storage(TeeStorageEngine::new(
+            MemoryStorage::new(),
+            MemoryStorage::new(),
+        ))
+        .on_complete(move |out| {
+            let copies = out
+                .stored_files
+                .iter()
+                .filter(|(_, secondary)| secondary.is_some())
+                .count();
+            counter.fetch_add(copies, Ordering::SeqCst);
+        })
+        .build()
+        .expect("builder config");
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND--\r\n"
+    );
+
+    multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                body.as_bytes(),
+            ))]),
+        )
+        .await
+        .expect("pipeline should succeed");
+
+    assert_eq!(mirrored.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn completion_hooks_registered_before_storage_fail_build() {
+    let err = Multer::builder()
+        .on_complete(|_| {})
+        .storage(MemoryStorage::new())
+        .build()
+        .expect_err("hooks for the replaced storage must not be dropped silently");
+
+    assert_eq!(err, ConfigError::CompletionHooksDiscarded);
+}
+
+#[tokio::test]
+async fn field_count_hint_does_not_change_parse_and_store_output() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"a\"\r\n",
+        "\r\n",
+        "one\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"b\"\r\n",
+        "\r\n",
+        "two\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"c\"\r\n",
+        "\r\n",
+        "three\r\n",
+        "--BOUND--\r\n"
+    );
+    let hinted = Multer::builder()
+        .field_count_hint(10)
+        .build()
+        .expect("builder config");
+    assert_eq!(hinted.config().field_count_hint, Some(10));
+    let plain = Multer::builder().build().expect("builder config");
+
+    let mut outputs = Vec::new();
+    for multer in [&hinted, &plain] {
+        let output = multer
+            .parse_and_store(
+                "BOUND",
+                stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                    body.as_bytes(),
+                ))]),
+            )
+            .await
+            .expect("pipeline should succeed");
+        outputs.push(output);
+    }
+
+    assert_eq!(outputs[0], outputs[1]);
+    assert_eq!(outputs[0].text_fields.len(), 3);
+}
+
+#[tokio::test]
+async fn require_file_parts_rejects_text_only_bodies() {
+    let multer = Multer::builder()
+        .storage(MemoryStorage::new())
+        .require_file_parts()
+        .build()
+        .expect("builder config");
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let result = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                body.as_bytes(),
+            ))]),
+        )
+        .await;
+    assert!(matches!(result, Err(MulterError::NoFileParts)));
+
+    let empty = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                b"--BOUND--\r\n",
+            ))]),
+        )
+        .await;
+    assert!(matches!(empty, Err(MulterError::NoFileParts)));
+}
+
+#[tokio::test]
+async fn min_count_requires_specific_file_fields() {
+    let storage = MemoryStorage::new();
+    let multer = Multer::builder()
+        .storage(storage.clone())
+        .fields([
+            SelectedField::file("avatar").min_count(1),
+            SelectedField::file("gallery"),
+            SelectedField::text("note"),
+        ])
+        .build()
+        .expect("builder config");
+    let missing_avatar = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"gallery\"; filename=\"g.png\"\r\n",
+        "\r\n",
+        "img\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let result = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                missing_avatar.as_bytes(),
+            ))]),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(MulterError::RequiredFieldMissing { field, min_count: 1 }) if field == "avatar"
+    ));
+    assert!(
+        storage.is_empty().await,
+        "stored gallery file must be discarded"
+    );
+
+    let with_avatar = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"avatar\"; filename=\"a.png\"\r\n",
+        "\r\n",
+        "img\r\n",
+        "--BOUND--\r\n"
+    );
+    let output = multer
+        .parse_and_store(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                with_avatar.as_bytes(),
+            ))]),
+        )
+        .await
+        .expect("avatar satisfies min_count");
+    assert_eq!(output.stored_files.len(), 1);
+}
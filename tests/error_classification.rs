@@ -0,0 +1,160 @@
+#![allow(missing_docs)]
+
+use multigear::{ConfigError, MulterError, ParseError, StorageError};
+
+fn field() -> String {
+    "upload".to_owned()
+}
+
+#[test]
+fn size_limit_errors_map_to_payload_too_large() {
+    let errors = [
+        MulterError::FileSizeLimitExceeded {
+            field: field(),
+            max_file_size: 10,
+        },
+        MulterError::FieldSizeLimitExceeded {
+            field: field(),
+            max_field_size: 10,
+        },
+        MulterError::BodySizeLimitExceeded { max_body_size: 10 },
+    ];
+
+    for err in errors {
+        assert_eq!(err.http_status(), 413, "{err}");
+        assert!(err.is_client_error(), "{err}");
+        assert!(!err.is_server_error(), "{err}");
+    }
+}
+
+#[test]
+fn mime_errors_map_to_unsupported_media_type() {
+    let err = MulterError::MimeTypeNotAllowed {
+        field: field(),
+        mime: "text/html".to_owned(),
+    };
+    assert_eq!(err.http_status(), 415);
+    assert!(err.is_client_error());
+}
+
+#[test]
+fn request_errors_map_to_bad_request() {
+    let errors = [
+        MulterError::Parse(ParseError::new("malformed headers")),
+        MulterError::UnexpectedField { field: field() },
+        MulterError::FieldCountLimitExceeded {
+            field: field(),
+            max_count: 1,
+        },
+        MulterError::FilesLimitExceeded { max_files: 1 },
+        MulterError::FieldsLimitExceeded { max_fields: 1 },
+        MulterError::PartsLimitExceeded { max_parts: 1 },
+        MulterError::ExtensionNotAllowed {
+            field: field(),
+            extension: "exe".to_owned(),
+        },
+        MulterError::NoFileParts,
+        MulterError::RequiredFieldMissing {
+            field: field(),
+            min_count: 1,
+        },
+        MulterError::Deserialize {
+            field: field(),
+            message: "expected object".to_owned(),
+        },
+        MulterError::IncompleteStream,
+    ];
+
+    for err in errors {
+        assert_eq!(err.http_status(), 400, "{err}");
+        assert!(err.is_client_error(), "{err}");
+        assert!(!err.is_server_error(), "{err}");
+    }
+}
+
+#[test]
+fn config_and_storage_errors_map_to_internal_server_error() {
+    let errors = [
+        MulterError::Config(ConfigError::EmptyFieldName),
+        MulterError::Storage(StorageError::new("disk full")),
+        MulterError::Storage(StorageError::CapacityExceeded { used: 5, max: 5 }),
+        MulterError::Storage(StorageError::InsufficientDiskSpace {
+            available: 1,
+            required: 2,
+        }),
+        MulterError::Storage(StorageError::GlobalCapacityExceeded {
+            stored: 5,
+            incoming: 1,
+            max: 5,
+        }),
+        MulterError::Storage(StorageError::FileAlreadyExists {
+            path: "upload.txt".into(),
+        }),
+        MulterError::Storage(StorageError::WriteVerificationFailed {
+            expected: "aa".to_owned(),
+            actual: "bb".to_owned(),
+        }),
+        MulterError::from(std::io::Error::other("broken pipe")),
+    ];
+
+    for err in errors {
+        assert_eq!(err.http_status(), 500, "{err}");
+        assert!(err.is_server_error(), "{err}");
+        assert!(!err.is_client_error(), "{err}");
+    }
+}
+
+#[test]
+fn every_variant_clones_into_an_equal_error() {
+    let errors = [
+        MulterError::Config(ConfigError::EmptyFieldName),
+        MulterError::Parse(ParseError::new("bad header")),
+        MulterError::Storage(StorageError::new("disk full")),
+        MulterError::UnexpectedField { field: field() },
+        MulterError::FieldCountLimitExceeded {
+            field: field(),
+            max_count: 1,
+        },
+        MulterError::FileSizeLimitExceeded {
+            field: field(),
+            max_file_size: 10,
+        },
+        MulterError::FieldSizeLimitExceeded {
+            field: field(),
+            max_field_size: 10,
+        },
+        MulterError::FilesLimitExceeded { max_files: 1 },
+        MulterError::FieldsLimitExceeded { max_fields: 1 },
+        MulterError::PartsLimitExceeded { max_parts: 1 },
+        MulterError::BodySizeLimitExceeded { max_body_size: 10 },
+        MulterError::MimeTypeNotAllowed {
+            field: field(),
+            mime: "text/html".to_owned(),
+        },
+        MulterError::ExtensionNotAllowed {
+            field: field(),
+            extension: "exe".to_owned(),
+        },
+        MulterError::NoFileParts,
+        MulterError::RequiredFieldMissing {
+            field: field(),
+            min_count: 1,
+        },
+        MulterError::Deserialize {
+            field: field(),
+            message: "expected object".to_owned(),
+        },
+        MulterError::IncompleteStream,
+        MulterError::Timeout { elapsed_ms: 5 },
+    ];
+
+    for err in &errors {
+        assert_eq!(&err.clone(), err);
+    }
+    assert_eq!(errors[16].clone(), MulterError::IncompleteStream);
+    assert_ne!(errors[0], errors[1]);
+
+    let shared = std::sync::Arc::new(errors[6].clone());
+    let handle = std::sync::Arc::clone(&shared);
+    assert_eq!(handle.http_status(), 413);
+}
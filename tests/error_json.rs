@@ -0,0 +1,68 @@
+#![allow(missing_docs)]
+
+use multigear::{MulterError, ParseError, StorageError};
+use serde_json::Value;
+
+#[test]
+fn to_json_emits_expected_keys_for_field_errors() {
+    let err = MulterError::FileSizeLimitExceeded {
+        field: "avatar".to_owned(),
+        max_file_size: 1024,
+    };
+    let json = parse(&err.to_json());
+
+    assert_eq!(json["error_code"], "file_size_limit_exceeded");
+    assert_eq!(json["message"], err.to_string());
+    assert_eq!(json["field"], "avatar");
+    assert_eq!(json["http_status"], 413);
+}
+
+#[test]
+fn to_json_uses_null_field_when_not_applicable() {
+    let err = MulterError::Storage(StorageError::new("disk full"));
+    let json = parse(&err.to_json());
+
+    assert_eq!(json["error_code"], "storage_error");
+    assert!(json["field"].is_null());
+    assert_eq!(json["http_status"], 500);
+}
+
+#[test]
+fn to_json_escapes_message_contents() {
+    let err = MulterError::Parse(ParseError::new("bad \"quote\" \\ line\r\nnext\u{1}"));
+    let json = parse(&err.to_json());
+
+    assert_eq!(json["message"], "bad \"quote\" \\ line\r\nnext\u{1}");
+    assert_eq!(json["http_status"], 400);
+}
+
+#[test]
+fn to_json_pretty_matches_compact_representation() {
+    let errors = [
+        MulterError::UnexpectedField {
+            field: "other".to_owned(),
+        },
+        MulterError::MimeTypeNotAllowed {
+            field: "doc".to_owned(),
+            mime: "text/html".to_owned(),
+        },
+        MulterError::IncompleteStream,
+    ];
+
+    for err in errors {
+        let pretty = err.to_json_pretty();
+        assert!(pretty.contains('\n'));
+        assert_eq!(parse(&pretty), parse(&err.to_json()));
+    }
+}
+
+fn parse(json: &str) -> Value {
+    let value: Value = serde_json::from_str(json).expect("error JSON should be valid");
+    let object = value.as_object().expect("error JSON should be an object");
+    assert_eq!(object.len(), 4);
+    assert!(object["error_code"].is_string());
+    assert!(object["message"].is_string());
+    assert!(object["field"].is_string() || object["field"].is_null());
+    assert!(object["http_status"].is_u64());
+    value
+}
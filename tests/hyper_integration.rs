@@ -10,7 +10,11 @@ use http_body_util::{BodyExt, Full};
 #[cfg(feature = "hyper")]
 use hyper::{header, service::Service, Request, Response};
 #[cfg(feature = "hyper")]
-use multigear::{extract_boundary, hyper::MulterService, MemoryStorage, Multer, StoredFile};
+use multigear::{
+    extract_boundary,
+    hyper::{MulterHandler, MulterService},
+    MemoryStorage, Multer, MultipartBodyBuilder, ProcessedMultipart,
+};
 
 #[cfg(feature = "hyper")]
 #[tokio::test]
@@ -54,10 +58,10 @@ async fn parse_stream_accepts_hyper_data_stream() {
 #[tokio::test]
 async fn multer_service_stores_file_parts_and_calls_handler() {
     let multer = Arc::new(Multer::new(MemoryStorage::new()));
-    let service = MulterService::new(multer, |saved_files: Vec<StoredFile>| async move {
+    let service = MulterService::new(multer, |processed: ProcessedMultipart| async move {
         Ok::<_, std::io::Error>(Response::new(Full::new(Bytes::from(format!(
             "{}",
-            saved_files.len()
+            processed.stored_files.len()
         )))))
     });
 
@@ -89,7 +93,7 @@ async fn multer_service_stores_file_parts_and_calls_handler() {
 #[tokio::test]
 async fn multer_service_rejects_requests_without_content_type() {
     let multer = Arc::new(Multer::new(MemoryStorage::new()));
-    let service = MulterService::new(multer, |_saved_files: Vec<StoredFile>| async move {
+    let service = MulterService::new(multer, |_processed: ProcessedMultipart| async move {
         Ok::<_, std::io::Error>(Response::new(Full::new(Bytes::from_static(b"ok"))))
     });
 
@@ -103,3 +107,47 @@ async fn multer_service_rejects_requests_without_content_type() {
         .expect_err("service should fail");
     assert!(err.to_string().contains("missing Content-Type"));
 }
+
+#[cfg(feature = "hyper")]
+#[tokio::test]
+async fn multer_service_passes_text_fields_to_handler() {
+    let multer = Arc::new(Multer::new(MemoryStorage::new()));
+    let handler: MulterHandler<MemoryStorage, Full<Bytes>, std::io::Error> =
+        Box::new(|processed| {
+            Box::pin(async move {
+                let summary = format!(
+                    "title={} tags={} files={}",
+                    processed.text("title").unwrap_or("<none>"),
+                    processed.all_text("tag").collect::<Vec<_>>().join("+"),
+                    processed.stored_files.len()
+                );
+                Ok(Response::new(Full::new(Bytes::from(summary))))
+            })
+        });
+    let service: MulterService<MemoryStorage, _> = MulterService::new(multer, handler);
+
+    let body = MultipartBodyBuilder::new()
+        .part("title", "Holiday")
+        .part("tag", "beach")
+        .file("photo", "a.png", "image/png", "PNG")
+        .part("tag", "sun")
+        .build("BOUND");
+    let request = Request::builder()
+        .header(header::CONTENT_TYPE, "multipart/form-data; boundary=BOUND")
+        .body(Full::new(body))
+        .expect("request should build");
+
+    let response = service
+        .clone()
+        .call(request)
+        .await
+        .expect("service should succeed");
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("response body should collect")
+        .to_bytes();
+
+    assert_eq!(body.as_ref(), b"title=Holiday tags=beach+sun files=1");
+}
@@ -1,9 +1,12 @@
 #![allow(missing_docs)]
 
+use std::time::Duration;
+
 use bytes::Bytes;
-use futures::{channel::mpsc, stream};
+use futures::{channel::mpsc, stream, StreamExt};
 use multigear::{
-    Limits, MulterConfig, MulterError, Multipart, SelectedField, Selector, UnknownFieldPolicy,
+    Limits, MulterConfig, MulterError, Multipart, MultipartBodyBuilder, SelectedField, Selector,
+    UnknownFieldPolicy,
 };
 
 #[tokio::test]
@@ -121,6 +124,44 @@ async fn enforces_max_fields() {
     ));
 }
 
+#[tokio::test]
+async fn enforces_max_parts_across_files_and_text_fields() {
+    let config = MulterConfig {
+        selector: Selector::single("avatar"),
+        unknown_field_policy: UnknownFieldPolicy::Ignore,
+        limits: Limits {
+            max_files: Some(2),
+            max_fields: Some(2),
+            max_parts: Some(4),
+            ..Limits::default()
+        },
+        ..MulterConfig::default()
+    };
+    let body = multipart_body(&[
+        part("title", None, None, "one"),
+        part("avatar", Some("a.png"), Some("image/png"), "png"),
+        part("ignored", Some("b.bin"), None, "skip"),
+        part("note", None, None, "two"),
+        part("extra", None, None, "three"),
+    ]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
+        .expect("multipart should initialize");
+
+    let mut accepted = Vec::new();
+    let err = loop {
+        match multipart.next_part().await {
+            Ok(Some(part)) => accepted.push(part.field_name().to_owned()),
+            Ok(None) => panic!("fifth part must exceed the limit"),
+            Err(err) => break err,
+        }
+    };
+    assert_eq!(accepted, ["title", "avatar", "note"]);
+    assert!(matches!(
+        err,
+        MulterError::PartsLimitExceeded { max_parts: 4 }
+    ));
+}
+
 #[tokio::test]
 async fn enforces_max_body_size() {
     let config = config_with_limits(Limits {
@@ -143,6 +184,129 @@ async fn enforces_max_body_size() {
     ));
 }
 
+#[tokio::test]
+async fn enforces_max_header_size_on_unterminated_header_block() {
+    let config = config_with_limits(Limits {
+        max_header_size: Some(1024),
+        ..Limits::default()
+    });
+    let opening = stream::iter([Ok(Bytes::from_static(
+        b"--BOUND\r\nContent-Disposition: form-data; name=\"a\"\r\n",
+    ))]);
+    let headers =
+        stream::repeat_with(|| Ok(Bytes::from_static(b"X-Padding: aaaaaaaaaaaaaaaa\r\n")));
+    let mut multipart = Multipart::with_config("BOUND", opening.chain(headers), config)
+        .expect("multipart should initialize");
+
+    let err = multipart
+        .next_part()
+        .await
+        .expect_err("endless header block must be rejected");
+    assert!(
+        matches!(&err, MulterError::Parse(parse) if parse.to_string() == "part header block exceeded maximum size"),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn max_header_size_accepts_header_blocks_within_limit() {
+    let config = config_with_limits(Limits {
+        max_header_size: Some(256),
+        ..Limits::default()
+    });
+    let body = multipart_body(&[part("upload", Some("a.txt"), Some("text/plain"), "ok")]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
+        .expect("multipart should initialize");
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    assert_eq!(part.field_name(), "upload");
+}
+
+#[tokio::test]
+async fn part_read_timeout_fails_slow_producers() {
+    let config = config_with_limits(Limits {
+        part_read_timeout: Some(Duration::from_millis(50)),
+        ..Limits::default()
+    });
+    let (tx, rx) = mpsc::unbounded::<Result<Bytes, MulterError>>();
+    let producer = tokio::spawn(async move {
+        tx.unbounded_send(Ok(Bytes::from_static(
+            b"--BOUND\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\none\r\n",
+        )))
+        .expect("send first part");
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let _ = tx.unbounded_send(Ok(Bytes::from_static(
+            b"--BOUND\r\nContent-Disposition: form-data; name=\"b\"\r\n\r\ntwo\r\n--BOUND--\r\n",
+        )));
+    });
+
+    let mut multipart =
+        Multipart::with_config("BOUND", rx, config).expect("multipart should initialize");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("first part should arrive in time")
+        .expect("part expected");
+    assert_eq!(part.field_name(), "a");
+
+    let err = multipart
+        .next_part()
+        .await
+        .expect_err("stalled producer must time out");
+    assert!(matches!(err, MulterError::Timeout { elapsed_ms: 50 }));
+    assert_eq!(err.http_status(), 408);
+    assert!(multipart
+        .next_part()
+        .await
+        .expect("failed parser yields no further parts")
+        .is_none());
+    producer.abort();
+}
+
+#[tokio::test]
+async fn part_read_timeout_fails_slow_producers_on_stream_path() {
+    let config = config_with_limits(Limits {
+        part_read_timeout: Some(Duration::from_millis(50)),
+        ..Limits::default()
+    });
+    let (tx, rx) = mpsc::unbounded::<Result<Bytes, MulterError>>();
+    let producer = tokio::spawn(async move {
+        tx.unbounded_send(Ok(Bytes::from_static(
+            b"--BOUND\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\none\r\n",
+        )))
+        .expect("send first part");
+        tx.unbounded_send(Ok(Bytes::from_static(
+            b"--BOUND\r\nContent-Disposition: form-data; name=\"b\"\r\n\r\ntw",
+        )))
+        .expect("send start of second part");
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let _ = tx.unbounded_send(Ok(Bytes::from_static(b"o\r\n--BOUND--\r\n")));
+    });
+
+    let mut parts = Multipart::with_config("BOUND", rx, config)
+        .expect("multipart should initialize")
+        .into_stream();
+    let first = parts
+        .next()
+        .await
+        .expect("first item expected")
+        .expect("first part should arrive in time");
+    assert_eq!(first.field_name(), "a");
+
+    let err = parts
+        .next()
+        .await
+        .expect("second item expected")
+        .expect_err("stalled body must time out");
+    assert!(matches!(err, MulterError::Timeout { elapsed_ms: 50 }));
+    assert!(parts.next().await.is_none());
+    producer.abort();
+}
+
 #[tokio::test]
 async fn enforces_allowed_mime_types_with_wildcard() {
     let config = config_with_limits(Limits {
@@ -213,7 +377,7 @@ async fn fails_early_before_terminal_boundary_for_large_file_chunks() {
 #[tokio::test]
 async fn per_field_mime_rules_override_broader_global_allowlist() {
     let config = MulterConfig {
-        selector: Selector::fields([SelectedField::new("docs")
+        selector: Selector::fields([SelectedField::file("docs")
             .max_count(1)
             .allowed_mime_types(["application/pdf"])]),
         unknown_field_policy: UnknownFieldPolicy::Reject,
@@ -221,6 +385,7 @@ async fn per_field_mime_rules_override_broader_global_allowlist() {
             allowed_mime_types: vec!["application/*".to_owned()],
             ..Limits::default()
         },
+        ..MulterConfig::default()
     };
 
     let body = multipart_body(&[part("docs", Some("a.json"), Some("application/json"), "{}")]);
@@ -238,7 +403,7 @@ async fn per_field_mime_rules_override_broader_global_allowlist() {
 #[tokio::test]
 async fn global_mime_rules_still_apply_when_field_rule_allows() {
     let config = MulterConfig {
-        selector: Selector::fields([SelectedField::new("docs")
+        selector: Selector::fields([SelectedField::file("docs")
             .max_count(1)
             .allowed_mime_types(["application/pdf"])]),
         unknown_field_policy: UnknownFieldPolicy::Reject,
@@ -246,6 +411,7 @@ async fn global_mime_rules_still_apply_when_field_rule_allows() {
             allowed_mime_types: vec!["image/*".to_owned()],
             ..Limits::default()
         },
+        ..MulterConfig::default()
     };
 
     let body = multipart_body(&[part("docs", Some("a.pdf"), Some("application/pdf"), "pdf")]);
@@ -260,12 +426,60 @@ async fn global_mime_rules_still_apply_when_field_rule_allows() {
     ));
 }
 
+#[tokio::test]
+async fn per_field_extension_rules_are_enforced_independently() {
+    let config = MulterConfig {
+        selector: Selector::fields([
+            SelectedField::file("docs").with_allowed_extensions(["pdf"]),
+            SelectedField::file("images").with_allowed_extensions([".png", "JPG"]),
+        ]),
+        unknown_field_policy: UnknownFieldPolicy::Reject,
+        limits: Limits::default(),
+        ..MulterConfig::default()
+    };
+
+    let body = multipart_body(&[
+        part("docs", Some("report.PDF"), None, "pdf"),
+        part("images", Some("photo.jpg"), None, "jpg"),
+        part("images", Some("report.pdf"), None, "pdf"),
+    ]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
+        .expect("multipart should initialize");
+
+    let first = multipart
+        .next_part()
+        .await
+        .expect("first item expected")
+        .expect("pdf should be accepted for docs");
+    assert_eq!(first.field_name(), "docs");
+    drop(first);
+
+    let second = multipart
+        .next_part()
+        .await
+        .expect("second item expected")
+        .expect("jpg should be accepted for images");
+    assert_eq!(second.field_name(), "images");
+    drop(second);
+
+    let err = multipart
+        .next_part()
+        .await
+        .expect_err("pdf should be rejected for images");
+    assert!(matches!(
+        err,
+        MulterError::ExtensionNotAllowed { field, extension }
+        if field == "images" && extension == "pdf"
+    ));
+}
+
 #[tokio::test]
 async fn enforces_per_field_text_size_limit() {
     let config = MulterConfig {
         selector: Selector::fields([SelectedField::text("meta").max_size(4)]),
         unknown_field_policy: UnknownFieldPolicy::Reject,
         limits: Limits::default(),
+        ..MulterConfig::default()
     };
     let body = multipart_body(&[part("meta", None, None, "hello")]);
     let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
@@ -295,6 +509,7 @@ async fn fields_selector_rejects_unknown_text_fields() {
         selector: Selector::fields([SelectedField::text("meta")]),
         unknown_field_policy: UnknownFieldPolicy::Reject,
         limits: Limits::default(),
+        ..MulterConfig::default()
     };
     let body = multipart_body(&[part("other", None, None, "value")]);
     let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
@@ -315,6 +530,7 @@ fn config_with_limits(limits: Limits) -> MulterConfig {
         selector: Selector::any(),
         unknown_field_policy: UnknownFieldPolicy::Reject,
         limits,
+        ..MulterConfig::default()
     }
 }
 
@@ -328,32 +544,18 @@ fn part<'a>(
 }
 
 fn multipart_body(parts: &[(&str, Option<&str>, Option<&str>, &str)]) -> Vec<u8> {
-    let mut out = Vec::new();
+    let mut builder = MultipartBodyBuilder::new();
     for (field, file_name, content_type, body) in parts {
-        out.extend_from_slice(b"--BOUND\r\n");
-        match file_name {
-            Some(file_name) => {
-                let disposition = format!(
-                    "Content-Disposition: form-data; name=\"{field}\"; filename=\"{file_name}\"\r\n"
-                );
-                out.extend_from_slice(disposition.as_bytes());
-                if let Some(content_type) = content_type {
-                    let header = format!("Content-Type: {content_type}\r\n");
-                    out.extend_from_slice(header.as_bytes());
-                }
-                out.extend_from_slice(b"\r\n");
-            }
-            None => {
-                let disposition =
-                    format!("Content-Disposition: form-data; name=\"{field}\"\r\n\r\n");
-                out.extend_from_slice(disposition.as_bytes());
+        let body = Bytes::copy_from_slice(body.as_bytes());
+        builder = match (file_name, content_type) {
+            (Some(file_name), Some(content_type)) => {
+                builder.file(field, file_name, content_type, body)
             }
-        }
-        out.extend_from_slice(body.as_bytes());
-        out.extend_from_slice(b"\r\n");
+            (Some(file_name), None) => builder.file_without_content_type(field, file_name, body),
+            (None, _) => builder.part(field, body),
+        };
     }
-    out.extend_from_slice(b"--BOUND--\r\n");
-    out
+    builder.build("BOUND").to_vec()
 }
 
 fn bytes_stream(body: Vec<u8>) -> impl futures::Stream<Item = Result<Bytes, MulterError>> {
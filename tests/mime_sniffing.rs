@@ -0,0 +1,271 @@
+#![allow(missing_docs)]
+
+use std::path::{Path, PathBuf};
+
+use bytes::Bytes;
+use futures::stream;
+use multigear::{
+    DiskStorage, MemoryStorage, Multer, MulterError, Multipart, MultipartBodyBuilder,
+    SelectedField, StorageEngine, TeeStorageEngine, UnknownFieldPolicy,
+};
+use uuid::Uuid;
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR\x00\x00\x00\x01\x00\x00\x00\x01";
+const PDF: &[u8] = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n1 0 obj\n<<>>\nendobj\n";
+const ZIP: &[u8] = b"PK\x03\x04\x14\x00\x00\x00\x08\x00\x00\x00\x00\x00";
+
+#[tokio::test]
+async fn sniffs_png_pdf_and_zip_without_content_type() {
+    let root = temp_root();
+    let multer = Multer::new(sniffing_storage(&root));
+
+    let body = multipart_body(&[
+        ("image", "photo", None, PNG),
+        ("doc", "report", None, PDF),
+        ("bundle", "files", Some("application/octet-stream"), ZIP),
+    ]);
+    let output = multer
+        .parse_and_store("BOUND", bytes_stream(body))
+        .await
+        .expect("parse and store should succeed");
+
+    let types = output
+        .stored_files
+        .iter()
+        .map(|file| file.content_type.essence_str().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(types, ["image/png", "application/pdf", "application/zip"]);
+
+    let stored = tokio::fs::read(output.stored_files[0].path.as_ref().expect("disk path"))
+        .await
+        .expect("stored file should read");
+    assert_eq!(stored, PNG);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn sniffing_preserves_bytes_split_across_chunks() {
+    let root = temp_root();
+    let storage = sniffing_storage(&root);
+
+    let mut payload = PDF.to_vec();
+    payload.extend(std::iter::repeat(b'x').take(2048));
+    let chunks = payload
+        .chunks(3)
+        .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+        .collect::<Vec<Result<Bytes, MulterError>>>();
+
+    let stored = storage
+        .store(
+            "doc",
+            Some("report"),
+            "application/octet-stream",
+            Box::pin(stream::iter(chunks)),
+        )
+        .await
+        .expect("store should succeed");
+    assert_eq!(stored.content_type.essence_str(), "application/pdf");
+    assert_eq!(stored.size, payload.len() as u64);
+    let written = tokio::fs::read(stored.path.expect("disk path"))
+        .await
+        .expect("stored file should read");
+    assert_eq!(written, payload);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn sniffed_type_is_checked_against_global_allowlist() {
+    let root = temp_root();
+    let multer = Multer::builder()
+        .storage(sniffing_storage(&root))
+        .allowed_mime_types(["image/*"])
+        .build()
+        .expect("config should be valid");
+
+    let accepted = multer
+        .parse_and_store(
+            "BOUND",
+            bytes_stream(multipart_body(&[("image", "photo", None, PNG)])),
+        )
+        .await
+        .expect("sniffed png should pass image allowlist");
+    assert_eq!(
+        accepted.stored_files[0].content_type.essence_str(),
+        "image/png"
+    );
+
+    let err = multer
+        .parse_and_store(
+            "BOUND",
+            bytes_stream(multipart_body(&[("bundle", "files", None, ZIP)])),
+        )
+        .await
+        .expect_err("sniffed zip should be rejected");
+    assert!(matches!(
+        err,
+        MulterError::MimeTypeNotAllowed { field, mime }
+        if field == "bundle" && mime == "application/zip"
+    ));
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn sniffed_type_is_checked_against_field_allowlist() {
+    let root = temp_root();
+    let multer = Multer::builder()
+        .storage(sniffing_storage(&root))
+        .fields([SelectedField::file("doc").allowed_mime_types(["application/pdf"])])
+        .unknown_field_policy(UnknownFieldPolicy::Reject)
+        .build()
+        .expect("config should be valid");
+
+    multer
+        .parse_and_store(
+            "BOUND",
+            bytes_stream(multipart_body(&[("doc", "report", None, PDF)])),
+        )
+        .await
+        .expect("sniffed pdf should pass field allowlist");
+
+    let err = multer
+        .parse_and_store(
+            "BOUND",
+            bytes_stream(multipart_body(&[("doc", "report", None, PNG)])),
+        )
+        .await
+        .expect_err("sniffed png should be rejected");
+    assert!(matches!(
+        err,
+        MulterError::MimeTypeNotAllowed { field, mime }
+        if field == "doc" && mime == "image/png"
+    ));
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn sniffing_backend_inside_tee_is_checked_against_allowlist() {
+    let root = temp_root();
+    let multer = Multer::builder()
+        .storage(TeeStorageEngine::new(
+            sniffing_storage(&root),
+            MemoryStorage::new(),
+        ))
+        .allowed_mime_types(["image/*"])
+        .build()
+        .expect("config should be valid");
+
+    let accepted = multer
+        .parse_and_store(
+            "BOUND",
+            bytes_stream(multipart_body(&[("image", "photo", None, PNG)])),
+        )
+        .await
+        .expect("sniffed png should pass image allowlist");
+    let (primary, secondary) = &accepted.stored_files[0];
+    assert_eq!(primary.content_type.essence_str(), "image/png");
+    assert_eq!(
+        secondary
+            .as_ref()
+            .map(|file| file.content_type.essence_str()),
+        Some("image/png")
+    );
+
+    let err = multer
+        .parse_and_store(
+            "BOUND",
+            bytes_stream(multipart_body(&[("bundle", "files", None, ZIP)])),
+        )
+        .await
+        .expect_err("sniffed zip should be rejected");
+    assert!(matches!(
+        err,
+        MulterError::MimeTypeNotAllowed { field, mime }
+        if field == "bundle" && mime == "application/zip"
+    ));
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn generic_type_is_rejected_up_front_without_sniffing() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .build()
+        .expect("builder should succeed");
+    let multer = Multer::builder()
+        .storage(storage)
+        .allowed_mime_types(["image/*"])
+        .build()
+        .expect("config should be valid");
+
+    let body = multipart_body(&[("image", "photo", None, PNG)]);
+    let mut multipart = multer
+        .multipart_from_boundary("BOUND", bytes_stream(body))
+        .expect("multipart should initialize");
+    let err = multipart
+        .next_part()
+        .await
+        .expect_err("octet-stream should fail the allowlist");
+    assert!(matches!(
+        err,
+        MulterError::MimeTypeNotAllowed { mime, .. } if mime == "application/octet-stream"
+    ));
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn declared_specific_type_is_not_overridden() {
+    let root = temp_root();
+    let multer = Multer::new(sniffing_storage(&root));
+
+    let body = multipart_body(&[("doc", "notes.txt", Some("text/plain"), PNG)]);
+    let mut multipart =
+        Multipart::new("BOUND", bytes_stream(body)).expect("multipart should initialize");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let stored = multer.store(part).await.expect("store should succeed");
+    assert_eq!(stored.content_type, mime::TEXT_PLAIN);
+
+    cleanup(root).await;
+}
+
+fn sniffing_storage(root: &Path) -> DiskStorage {
+    DiskStorage::builder()
+        .destination(root)
+        .sniff_mime(true)
+        .build()
+        .expect("builder should succeed")
+}
+
+fn temp_root() -> PathBuf {
+    std::env::temp_dir().join(format!("multigear-test-{}", Uuid::new_v4()))
+}
+
+async fn cleanup(path: PathBuf) {
+    let _ = tokio::fs::remove_dir_all(path).await;
+}
+
+fn multipart_body(parts: &[(&str, &str, Option<&str>, &[u8])]) -> Vec<u8> {
+    let mut builder = MultipartBodyBuilder::new();
+    for (field, file_name, content_type, body) in parts {
+        let body = Bytes::copy_from_slice(body);
+        builder = match content_type {
+            Some(content_type) => builder.file(field, file_name, content_type, body),
+            None => builder.file_without_content_type(field, file_name, body),
+        };
+    }
+    builder.build("BOUND").to_vec()
+}
+
+fn bytes_stream(body: Vec<u8>) -> impl futures::Stream<Item = Result<Bytes, MulterError>> {
+    stream::iter([Ok(Bytes::from(body))])
+}
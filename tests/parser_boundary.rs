@@ -1,6 +1,12 @@
 #![allow(missing_docs)]
 
+use bytes::Bytes;
+use futures::stream;
 use multigear::parser::boundary::extract_multipart_boundary;
+use multigear::parser::MultipartStream;
+use multigear::{
+    is_valid_multipart_boundary, validate_multipart_boundary, MulterError, MAX_BOUNDARY_LEN,
+};
 
 #[test]
 fn extracts_boundary_from_content_type() {
@@ -57,6 +63,136 @@ fn rejects_malformed_percent_encoding_in_boundary() {
     assert_err_contains(&err.to_string(), "percent-encoding");
 }
 
+#[test]
+fn extracts_quoted_boundary_containing_spaces() {
+    let boundary = extract_multipart_boundary("multipart/form-data; boundary=\"my boundary\"")
+        .expect("boundary with inner spaces should parse");
+    assert_eq!(boundary, "my boundary");
+}
+
+#[test]
+fn extracts_boundary_with_all_rfc2046_special_characters() {
+    let boundary = extract_multipart_boundary("multipart/form-data; boundary=\"a'()+_,-./:=?z\"")
+        .expect("special characters should parse");
+    assert_eq!(boundary, "a'()+_,-./:=?z");
+}
+
+#[test]
+fn accepts_boundary_of_exactly_seventy_characters() {
+    let max_boundary = "b".repeat(70);
+    let header = format!("multipart/form-data; boundary={max_boundary}");
+    let boundary = extract_multipart_boundary(&header).expect("70 characters should parse");
+    assert_eq!(boundary, max_boundary);
+}
+
+#[test]
+fn accepts_single_character_boundary() {
+    let boundary = extract_multipart_boundary("multipart/form-data; boundary=X").expect("boundary");
+    assert_eq!(boundary, "X");
+}
+
+#[test]
+fn ignores_extra_parameters_after_boundary() {
+    let boundary = extract_multipart_boundary("multipart/form-data; boundary=X; charset=utf-8")
+        .expect("boundary should parse");
+    assert_eq!(boundary, "X");
+}
+
+#[test]
+fn boundary_parameter_name_is_case_insensitive() {
+    let boundary = extract_multipart_boundary("multipart/form-data; BOUNDARY=X").expect("boundary");
+    assert_eq!(boundary, "X");
+}
+
+#[test]
+fn media_type_is_case_insensitive() {
+    let boundary = extract_multipart_boundary("MULTIPART/FORM-DATA; boundary=X").expect("boundary");
+    assert_eq!(boundary, "X");
+}
+
+#[test]
+fn validate_multipart_boundary_accepts_valid_tokens() {
+    assert_eq!(validate_multipart_boundary("abc123"), Ok(()));
+    assert_eq!(validate_multipart_boundary(&"a".repeat(70)), Ok(()));
+    assert_eq!(validate_multipart_boundary("a'()+_,-./:=? z"), Ok(()));
+    assert!(is_valid_multipart_boundary(
+        "----WebKitFormBoundary7MA4YWxk"
+    ));
+}
+
+#[test]
+fn validate_multipart_boundary_rejects_invalid_tokens() {
+    let err = validate_multipart_boundary(&"a".repeat(71)).expect_err("too long");
+    assert_err_contains(&err.to_string(), "cannot exceed 70");
+
+    let err = validate_multipart_boundary("").expect_err("empty");
+    assert_err_contains(&err.to_string(), "cannot be empty");
+
+    let err = validate_multipart_boundary("abc@123").expect_err("invalid characters");
+    assert_err_contains(&err.to_string(), "invalid characters");
+
+    assert!(!is_valid_multipart_boundary(""));
+    assert!(!is_valid_multipart_boundary("trailing "));
+    assert!(!is_valid_multipart_boundary("line\r\nbreak"));
+}
+
+/// Deterministic xorshift generator so failures reproduce without a seed file.
+struct Xorshift(u64);
+
+impl Xorshift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+fn random_boundary(rng: &mut Xorshift) -> String {
+    const BOUNDARY_CHARS: &[u8] = b"abcXYZ0189'()+_,-./:=? ";
+    let len = rng.below(MAX_BOUNDARY_LEN + 6);
+    (0..len)
+        .map(|_| match rng.below(10) {
+            0 => char::from(rng.below(128) as u8),
+            1 => ['é', '\u{2014}', '\u{1f600}'][rng.below(3)],
+            _ => char::from(BOUNDARY_CHARS[rng.below(BOUNDARY_CHARS.len())]),
+        })
+        .collect()
+}
+
+#[test]
+fn content_type_and_stream_validation_agree() {
+    let mut rng = Xorshift(0x9e37_79b9_7f4a_7c15);
+    let mut accepted = 0;
+
+    for _ in 0..5_000 {
+        let boundary = random_boundary(&mut rng);
+        // Percent-encode every byte so the header round-trips to `boundary` exactly.
+        let encoded = boundary
+            .bytes()
+            .map(|byte| format!("%{byte:02X}"))
+            .collect::<String>();
+        let extracted =
+            extract_multipart_boundary(&format!("multipart/form-data; boundary={encoded}"));
+        let streamed = MultipartStream::new(
+            boundary.clone(),
+            stream::empty::<Result<Bytes, MulterError>>(),
+        );
+
+        assert_eq!(extracted.is_ok(), streamed.is_ok(), "{boundary:?}");
+        if let Ok(extracted) = extracted {
+            assert_eq!(extracted, boundary);
+            accepted += 1;
+        }
+    }
+
+    assert!(accepted > 100, "generator should produce valid boundaries");
+}
+
 fn assert_err_contains(actual: &str, expected_fragment: &str) {
     assert!(
         actual.contains(expected_fragment),
@@ -2,7 +2,7 @@
 
 use http::{header, HeaderMap, HeaderValue};
 use multigear::parser::headers::{
-    parse_content_disposition, parse_part_content_type, parse_part_headers,
+    parse_content_disposition, parse_part_content_type, parse_part_headers, TransferEncoding,
 };
 
 #[test]
@@ -72,6 +72,73 @@ fn parse_part_headers_extracts_core_values() {
     assert_eq!(parsed.content_type.essence_str(), "image/png");
 }
 
+#[test]
+fn parse_part_headers_uses_last_content_disposition() {
+    let mut headers = HeaderMap::new();
+    headers.append(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("form-data; name=\"first\"; filename=\"first.txt\""),
+    );
+    headers.append(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("form-data; name=\"second\""),
+    );
+
+    let parsed = parse_part_headers(&headers).expect("part headers should parse");
+    assert_eq!(parsed.field_name, "second");
+    assert_eq!(parsed.file_name, None);
+    assert_eq!(
+        parsed
+            .headers
+            .get_all(header::CONTENT_DISPOSITION)
+            .iter()
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn parse_part_headers_keeps_custom_raw_headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("form-data; name=\"upload\"; filename=\"a.bin\""),
+    );
+    headers.insert("x-checksum", HeaderValue::from_static("sha256=abc123"));
+
+    let parsed = parse_part_headers(&headers).expect("part headers should parse");
+    assert_eq!(
+        parsed
+            .headers
+            .get("X-Checksum")
+            .and_then(|value| value.to_str().ok()),
+        Some("sha256=abc123")
+    );
+    assert_eq!(parsed.headers, headers);
+}
+
+#[test]
+fn parse_part_headers_reads_content_transfer_encoding() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("form-data; name=\"upload\""),
+    );
+    let parsed = parse_part_headers(&headers).expect("part headers should parse");
+    assert_eq!(parsed.transfer_encoding, TransferEncoding::Identity);
+
+    for (value, expected) in [
+        (" Base64 ", TransferEncoding::Base64),
+        ("quoted-printable", TransferEncoding::QuotedPrintable),
+        ("8bit", TransferEncoding::Identity),
+        ("x-unknown", TransferEncoding::Identity),
+    ] {
+        headers.insert("content-transfer-encoding", HeaderValue::from_static(value));
+        let parsed = parse_part_headers(&headers).expect("part headers should parse");
+        assert_eq!(parsed.transfer_encoding, expected, "{value}");
+    }
+}
+
 #[test]
 fn rejects_missing_content_disposition_header() {
     let headers = HeaderMap::new();
@@ -104,6 +171,61 @@ fn rejects_malformed_percent_encoding_in_filename_parameter() {
     assert_err_contains(&err.to_string(), "percent-encoding");
 }
 
+fn latin1_disposition_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_bytes(b"form-data; name=\"upload\"; filename=\"caf\xe9.txt\"")
+            .expect("obs-text bytes are valid header values"),
+    );
+    headers
+}
+
+#[cfg(feature = "iso-8859-1")]
+#[test]
+fn decodes_iso_8859_1_filename_parameter() {
+    let parsed = parse_part_headers(&latin1_disposition_headers()).expect("headers should parse");
+    assert_eq!(parsed.file_name.as_deref(), Some("café.txt"));
+
+    let disposition =
+        parse_content_disposition("form-data; name=\"upload\"; filename=\"caf%E9.txt\"")
+            .expect("percent-encoded ISO-8859-1 should decode");
+    assert_eq!(disposition.filename.as_deref(), Some("café.txt"));
+
+    let disposition = parse_content_disposition("form-data; name=\"upload\"; filename=\"é.txt\"")
+        .expect("UTF-8 filename should parse");
+    assert_eq!(disposition.filename.as_deref(), Some("é.txt"));
+}
+
+#[cfg(feature = "iso-8859-1")]
+#[tokio::test]
+async fn multipart_decodes_iso_8859_1_filename() {
+    use bytes::Bytes;
+    use futures::stream;
+    use multigear::{MulterError, Multipart};
+
+    let body = b"--BOUND\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"caf\xe9.txt\"\r\n\r\ndata\r\n--BOUND--\r\n".as_slice();
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(body))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    assert_eq!(part.file_name(), Some("café.txt"));
+}
+
+#[cfg(not(feature = "iso-8859-1"))]
+#[test]
+fn rejects_non_ascii_filename_without_iso_8859_1_feature() {
+    let err = parse_part_headers(&latin1_disposition_headers()).expect_err("must fail");
+    assert_err_contains(&err.to_string(), "must be ASCII");
+
+    let err = parse_content_disposition("form-data; name=\"upload\"; filename=\"caf%E9.txt\"")
+        .expect_err("must fail");
+    assert_err_contains(&err.to_string(), "not valid UTF-8");
+}
+
 fn assert_err_contains(actual: &str, expected_fragment: &str) {
     assert!(
         actual.contains(expected_fragment),
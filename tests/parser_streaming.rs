@@ -1,8 +1,20 @@
 #![allow(missing_docs)]
 
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
 use bytes::Bytes;
-use futures::{channel::mpsc, stream, StreamExt};
-use multigear::{MulterError, Multipart, ParseError};
+use futures::{channel::mpsc, future, stream, StreamExt, TryStreamExt};
+use multigear::{
+    parser::MultipartStream, Limits, MulterConfig, MulterError, Multipart, MultipartState,
+    ParseError,
+};
+use tokio::time::timeout;
 
 #[tokio::test]
 async fn parses_chunked_stream_and_yields_parts() {
@@ -131,6 +143,92 @@ async fn reports_malformed_boundary_as_parse_error() {
     ));
 }
 
+#[tokio::test]
+async fn waits_for_boundary_suffix_split_across_chunks() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"alpha\"\r\n",
+        "\r\n",
+        "one\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"beta\"\r\n",
+        "\r\n",
+        "two\r\n",
+        "--BOUND--\r\n"
+    );
+
+    for chunk_size in 1..=8 {
+        let chunks: Vec<_> = body
+            .as_bytes()
+            .chunks(chunk_size)
+            .map(|chunk| Ok::<Bytes, MulterError>(Bytes::copy_from_slice(chunk)))
+            .collect();
+        let mut multipart =
+            Multipart::new("BOUND", stream::iter(chunks)).expect("boundary should be valid");
+
+        let mut values = Vec::new();
+        while let Some(mut part) = multipart.next_part().await.expect("part should parse") {
+            values.push(part.text().await.expect("body should read"));
+        }
+        assert_eq!(values, ["one", "two"], "chunk size {chunk_size}");
+    }
+}
+
+#[tokio::test]
+async fn completes_part_when_chunk_ends_exactly_at_boundary() {
+    let first_chunk = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"alpha\"\r\n",
+        "\r\n",
+        "one\r\n",
+        "--BOUND\r\n"
+    );
+    let second_chunk = concat!(
+        "Content-Disposition: form-data; name=\"beta\"\r\n",
+        "\r\n",
+        "two\r\n",
+        "--BOUND--\r\n"
+    );
+
+    // The sender stays alive so the upstream never reports completion.
+    let (tx, rx) = mpsc::unbounded::<Result<Bytes, MulterError>>();
+    let mut multipart = Multipart::new("BOUND", rx).expect("boundary should be valid");
+    let wait = Duration::from_secs(5);
+
+    tx.unbounded_send(Ok(Bytes::from_static(first_chunk.as_bytes())))
+        .expect("send first chunk");
+    let mut first = multipart
+        .next_part()
+        .await
+        .expect("first part should parse")
+        .expect("first item should exist");
+    let first = timeout(wait, first.text())
+        .await
+        .expect("first part should finish without more input")
+        .expect("first body should read");
+    assert_eq!(first, "one");
+
+    tx.unbounded_send(Ok(Bytes::from_static(second_chunk.as_bytes())))
+        .expect("send second chunk");
+    let mut second = multipart
+        .next_part()
+        .await
+        .expect("second part should parse")
+        .expect("second item should exist");
+    let second = timeout(wait, second.text())
+        .await
+        .expect("second part should finish without more input")
+        .expect("second body should read");
+    assert_eq!(second, "two");
+
+    let next = timeout(wait, multipart.next_part())
+        .await
+        .expect("terminal boundary should end the stream")
+        .expect("stream should finish");
+    assert!(next.is_none());
+    drop(tx);
+}
+
 #[tokio::test]
 async fn reports_incomplete_terminal_boundary() {
     let body = concat!(
@@ -233,3 +331,745 @@ async fn streams_large_body_before_terminal_boundary_arrives() {
 
     assert_eq!(total, 256 * 1024);
 }
+
+#[tokio::test]
+async fn drain_all_consumes_remaining_parts_from_mid_first_part() {
+    let first_body = "a".repeat(64);
+    let body = format!(
+        concat!(
+            "--BOUND\r\n",
+            "Content-Disposition: form-data; name=\"upload\"; filename=\"a.bin\"\r\n",
+            "\r\n",
+            "{}\r\n",
+            "--BOUND\r\n",
+            "Content-Disposition: form-data; name=\"note\"\r\n",
+            "\r\n",
+            "hello\r\n",
+            "--BOUND\r\n",
+            "Content-Disposition: form-data; name=\"other\"; filename=\"b.bin\"\r\n",
+            "\r\n",
+            "world!\r\n",
+            "--BOUND--\r\n"
+        ),
+        first_body
+    );
+    let total_body_bytes = (first_body.len() + "hello".len() + "world!".len()) as u64;
+
+    let chunks = split_bytes(body.as_bytes(), &[90, 16, 16, 16]);
+    let stream = stream::iter(chunks.into_iter().map(Ok::<Bytes, MulterError>));
+    let mut multipart = Multipart::new("BOUND", stream).expect("boundary should be valid");
+
+    let mut first = multipart
+        .next_part()
+        .await
+        .expect("first part should parse")
+        .expect("first item should exist");
+    let read = {
+        let mut body = first.stream();
+        body.next()
+            .await
+            .expect("chunk should exist")
+            .expect("chunk should parse")
+            .len() as u64
+    };
+    assert!(read < first_body.len() as u64);
+    drop(first);
+
+    let stats = multipart.drain_all().await.expect("drain should succeed");
+    assert_eq!(stats.parts_drained, 3);
+    assert_eq!(read + stats.bytes_discarded, total_body_bytes);
+    assert!(multipart
+        .next_part()
+        .await
+        .expect("stream should finish")
+        .is_none());
+}
+
+#[tokio::test]
+async fn boundary_accessors_return_original_boundary() {
+    let raw = MultipartStream::new(
+        "----WebKitFormBoundary7MA4",
+        stream::empty::<Result<Bytes, MulterError>>(),
+    )
+    .expect("boundary should be valid");
+    assert_eq!(raw.boundary(), "----WebKitFormBoundary7MA4");
+
+    let body = concat!(
+        "--my boundary\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n",
+        "\r\n",
+        "file-body\r\n",
+        "--my boundary\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "\r\n",
+        "text-body\r\n",
+        "--my boundary--\r\n"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("my boundary", input).expect("boundary should be valid");
+    assert_eq!(multipart.boundary(), "my boundary");
+
+    let mut file = multipart
+        .next_part()
+        .await
+        .expect("file part should parse")
+        .expect("file part expected");
+    assert_eq!(file.text().await.expect("file body"), "file-body");
+    assert_eq!(multipart.boundary(), "my boundary");
+
+    let mut text = multipart
+        .next_part()
+        .await
+        .expect("text part should parse")
+        .expect("text part expected");
+    assert_eq!(text.text().await.expect("text body"), "text-body");
+    assert_eq!(multipart.boundary(), "my boundary");
+}
+
+#[tokio::test]
+async fn progress_counters_track_bytes_and_parts() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"alpha\"\r\n",
+        "\r\n",
+        "one\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"beta\"; filename=\"b.txt\"\r\n",
+        "\r\n",
+        "two\r\n",
+        "--BOUND--\r\n"
+    );
+    let chunks = split_bytes(body.as_bytes(), &[5, 11, 17, 3, 64]);
+    let stream = stream::iter(chunks.into_iter().map(Ok::<Bytes, MulterError>));
+    let mut multipart = Multipart::new("BOUND", stream).expect("boundary should be valid");
+    assert_eq!(multipart.bytes_received(), 0);
+
+    let mut first = multipart
+        .next_part()
+        .await
+        .expect("first part should parse")
+        .expect("first part expected");
+    first.bytes().await.expect("first body");
+    assert_eq!(multipart.parts_started(), 1);
+    assert_eq!(multipart.parts_completed(), 1);
+
+    let second = multipart
+        .next_part()
+        .await
+        .expect("second part should parse")
+        .expect("second part expected");
+    drop(second);
+    assert_eq!(multipart.parts_started(), 2);
+    assert_eq!(multipart.parts_completed(), 1);
+
+    assert!(multipart
+        .next_part()
+        .await
+        .expect("end should parse")
+        .is_none());
+    assert_eq!(multipart.parts_started(), 2);
+    assert_eq!(multipart.parts_completed(), 2);
+    assert_eq!(multipart.bytes_received(), body.len() as u64);
+}
+
+#[tokio::test]
+async fn received_body_bytes_grows_monotonically_per_chunk() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"a.bin\"\r\n",
+        "\r\n",
+        "0123456789abcdefghij\r\n",
+        "--BOUND--\r\n"
+    );
+    let chunks = split_bytes(body.as_bytes(), &[7; 16]);
+    let chunk_count = chunks.len();
+    let stream = stream::iter(chunks.into_iter().map(Ok::<Bytes, MulterError>));
+    let mut raw = MultipartStream::new("BOUND", stream).expect("boundary should be valid");
+    assert_eq!(raw.received_body_bytes(), 0);
+
+    let mut observed = Vec::new();
+    future::poll_fn(|cx| raw.poll_next_part_headers(cx))
+        .await
+        .expect("headers should parse")
+        .expect("part expected");
+    observed.push(raw.received_body_bytes());
+    while future::poll_fn(|cx| raw.poll_next_part_chunk(cx))
+        .await
+        .expect("chunk should parse")
+        .is_some()
+    {
+        observed.push(raw.received_body_bytes());
+    }
+    assert!(future::poll_fn(|cx| raw.poll_next_part_headers(cx))
+        .await
+        .expect("end should parse")
+        .is_none());
+    observed.push(raw.received_body_bytes());
+
+    assert!(
+        observed.windows(2).all(|pair| pair[0] <= pair[1]),
+        "{observed:?}"
+    );
+    assert!(observed.first() < observed.last(), "{observed:?}");
+    assert!(
+        observed.len() > 2,
+        "body should arrive over {chunk_count} chunks"
+    );
+    assert_eq!(observed.last().copied(), Some(body.len() as u64));
+}
+
+#[tokio::test]
+async fn body_limit_reports_max_body_size() {
+    let config = MulterConfig {
+        limits: Limits {
+            max_body_size: Some(4096),
+            ..Limits::default()
+        },
+        ..MulterConfig::default()
+    };
+    let multipart = Multipart::with_config(
+        "BOUND",
+        stream::empty::<Result<Bytes, MulterError>>(),
+        config,
+    )
+    .expect("config should be valid");
+    assert_eq!(multipart.body_limit(), Some(4096));
+
+    let unlimited = Multipart::new("BOUND", stream::empty::<Result<Bytes, MulterError>>())
+        .expect("boundary should be valid");
+    assert_eq!(unlimited.body_limit(), None);
+}
+
+const INTERLEAVED: &str = concat!(
+    "--BOUND\r\n",
+    "Content-Disposition: form-data; name=\"title\"\r\n",
+    "\r\n",
+    "Holiday\r\n",
+    "--BOUND\r\n",
+    "Content-Disposition: form-data; name=\"photo\"; filename=\"a.png\"\r\n",
+    "\r\n",
+    "PNG-A\r\n",
+    "--BOUND\r\n",
+    "Content-Disposition: form-data; name=\"caption\"\r\n",
+    "\r\n",
+    "Beach\r\n",
+    "--BOUND\r\n",
+    "Content-Disposition: form-data; name=\"photo\"; filename=\"b.png\"\r\n",
+    "\r\n",
+    "PNG-B\r\n",
+    "--BOUND--\r\n"
+);
+
+fn interleaved_multipart(
+) -> Multipart<impl futures::Stream<Item = Result<Bytes, MulterError>> + Unpin> {
+    let chunks = split_bytes(INTERLEAVED.as_bytes(), &[9; 64]);
+    Multipart::new(
+        "BOUND",
+        stream::iter(chunks.into_iter().map(Ok::<Bytes, MulterError>)),
+    )
+    .expect("boundary should be valid")
+}
+
+#[tokio::test]
+async fn next_text_part_skips_interleaved_file_parts() {
+    let mut multipart = interleaved_multipart();
+    let mut texts = Vec::new();
+    while let Some(mut part) = multipart.next_text_part().await.expect("text part") {
+        assert!(part.file_name().is_none());
+        let name = part.field_name().to_owned();
+        texts.push((name, part.text().await.expect("text body")));
+    }
+
+    assert_eq!(
+        texts,
+        [
+            ("title".to_owned(), "Holiday".to_owned()),
+            ("caption".to_owned(), "Beach".to_owned())
+        ]
+    );
+    assert!(multipart.is_complete());
+    assert_eq!(multipart.parts_completed(), 4);
+}
+
+#[tokio::test]
+async fn next_file_part_skips_interleaved_text_parts() {
+    let mut multipart = interleaved_multipart();
+    let mut files = Vec::new();
+    while let Some(mut part) = multipart.next_file_part().await.expect("file part") {
+        let name = part.file_name().expect("file name").to_owned();
+        files.push((name, part.text().await.expect("file body")));
+    }
+
+    assert_eq!(
+        files,
+        [
+            ("a.png".to_owned(), "PNG-A".to_owned()),
+            ("b.png".to_owned(), "PNG-B".to_owned())
+        ]
+    );
+    assert!(multipart.is_complete());
+}
+
+#[tokio::test]
+async fn kind_filtered_reads_can_be_mixed() {
+    let mut multipart = interleaved_multipart();
+
+    let file = multipart
+        .next_file_part()
+        .await
+        .expect("file part")
+        .expect("first file expected");
+    assert_eq!(file.file_name(), Some("a.png"));
+    drop(file);
+
+    let mut text = multipart
+        .next_text_part()
+        .await
+        .expect("text part")
+        .expect("caption expected");
+    assert_eq!(text.field_name(), "caption");
+    assert_eq!(text.text().await.expect("text body"), "Beach");
+    drop(text);
+
+    assert!(multipart
+        .next_text_part()
+        .await
+        .expect("end should parse")
+        .is_none());
+}
+
+#[tokio::test]
+async fn is_complete_tracks_terminal_boundary() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"alpha\"\r\n",
+        "\r\n",
+        "one\r\n",
+        "--BOUND--\r\n"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    assert!(!multipart.is_complete());
+    assert_eq!(multipart.remaining_parts_hint(), usize::MAX);
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    drop(part);
+    assert!(!multipart.is_complete());
+
+    assert!(multipart.next_part().await.expect("end").is_none());
+    assert!(multipart.is_complete());
+    assert_eq!(multipart.remaining_parts_hint(), 0);
+}
+
+#[tokio::test]
+async fn dropping_multipart_mid_part_releases_source() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"alpha\"\r\n",
+        "\r\n",
+        "one\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"beta\"\r\n",
+        "\r\n",
+        "two\r\n",
+        "--BOUND--\r\n"
+    );
+    let guard = Arc::new(());
+    let source_guard = Arc::clone(&guard);
+    let input = stream::iter(
+        body.as_bytes()
+            .chunks(16)
+            .map(|chunk| Ok::<Bytes, MulterError>(Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>(),
+    )
+    .map(move |chunk| {
+        let _ = &source_guard;
+        chunk
+    });
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    assert_eq!(part.field_name(), "alpha");
+    drop(part);
+    assert!(!multipart.is_complete());
+    assert_eq!(Arc::strong_count(&guard), 2);
+
+    drop(multipart);
+    assert_eq!(Arc::strong_count(&guard), 1);
+}
+
+#[tokio::test]
+async fn multipart_resumes_after_dropped_next_part_future() {
+    let (mut tx, rx) = mpsc::channel::<Result<Bytes, MulterError>>(4);
+    let mut multipart = Multipart::new("BOUND", rx).expect("boundary should be valid");
+
+    tx.try_send(Ok(Bytes::from_static(
+        b"--BOUND\r\nContent-Disposition: form-da",
+    )))
+    .expect("send first chunk");
+    timeout(Duration::from_millis(20), multipart.next_part())
+        .await
+        .expect_err("headers are incomplete");
+
+    tx.try_send(Ok(Bytes::from_static(
+        b"ta; name=\"alpha\"\r\n\r\none\r\n--BOUND--\r\n",
+    )))
+    .expect("send second chunk");
+    drop(tx);
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    assert_eq!(part.field_name(), "alpha");
+    assert_eq!(part.text().await.expect("text body"), "one");
+    assert!(multipart.next_part().await.expect("end").is_none());
+}
+
+#[tokio::test]
+async fn debug_output_summarizes_multipart_state() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"alpha\"\r\n",
+        "\r\n",
+        "one\r\n",
+        "--BOUND--\r\n"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    assert_eq!(multipart.state(), MultipartState::Active);
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    drop(part);
+    assert_eq!(multipart.state(), MultipartState::Draining);
+
+    let debug = format!("{multipart:?}");
+    assert!(debug.contains("parts_yielded: 1"), "{debug}");
+    assert!(debug.contains("boundary: \"BOUND\""), "{debug}");
+    assert!(debug.contains("state: Draining"), "{debug}");
+
+    assert!(multipart.next_part().await.expect("end").is_none());
+    assert_eq!(multipart.state(), MultipartState::Done);
+    assert!(format!("{multipart:?}").contains("state: Done"));
+}
+
+#[tokio::test]
+async fn multipart_can_be_consumed_as_buffered_part_stream() {
+    let body = concat!(
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"alpha\"\r\n",
+        "\r\n",
+        "one\r\n",
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"beta\"; filename=\"b.txt\"\r\n",
+        "Content-Type: text/plain\r\n",
+        "\r\n",
+        "two\r\n",
+        "--XBOUND--\r\n"
+    );
+
+    let chunks = split_bytes(body.as_bytes(), &[5, 11, 2, 17, 3, 40, 1, 64, 128]);
+    let input = stream::iter(chunks.clone().into_iter().map(Ok::<Bytes, MulterError>));
+    let multipart = Multipart::new("XBOUND", input).expect("boundary should be valid");
+    let parts = multipart
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("stream should parse");
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].field_name(), "alpha");
+    assert_eq!(parts[0].body, Bytes::from_static(b"one"));
+    assert_eq!(parts[1].file_name(), Some("b.txt"));
+    assert_eq!(parts[1].body, Bytes::from_static(b"two"));
+
+    let input = stream::iter(chunks.into_iter().map(Ok::<Bytes, MulterError>));
+    let multipart = Multipart::new("XBOUND", input).expect("boundary should be valid");
+    let file_names = multipart
+        .filter_map(|result| async move {
+            result
+                .ok()
+                .and_then(|part| part.file_name().map(ToOwned::to_owned))
+        })
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(file_names, vec!["b.txt".to_owned()]);
+}
+
+#[tokio::test]
+async fn buffered_part_stream_surfaces_parse_errors() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"alpha\"\r\n",
+        "\r\n",
+        "unterminated"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+
+    let err = multipart
+        .next()
+        .await
+        .expect("an item should be yielded")
+        .expect_err("truncated body must fail");
+    assert!(matches!(err, MulterError::IncompleteStream), "{err:?}");
+    assert!(multipart.next().await.is_none());
+}
+
+#[tokio::test]
+async fn into_stream_yields_owned_parts_for_concurrent_processing() {
+    let body = concat!(
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"a\"; filename=\"a.txt\"\r\n",
+        "\r\n",
+        "one\r\n",
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"b\"; filename=\"b.txt\"\r\n",
+        "\r\n",
+        "two\r\n",
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"c\"\r\n",
+        "\r\n",
+        "three\r\n",
+        "--XBOUND--\r\n"
+    );
+    let chunks = split_bytes(body.as_bytes(), &[9, 4, 30, 2, 60, 11, 256]);
+
+    let input = stream::iter(chunks.clone().into_iter().map(Ok::<Bytes, MulterError>));
+    let parts = Multipart::new("XBOUND", input)
+        .expect("boundary should be valid")
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("stream should parse");
+    let names = parts
+        .iter()
+        .map(|part| part.field_name().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a", "b", "c"]);
+
+    let input = stream::iter(chunks.into_iter().map(Ok::<Bytes, MulterError>));
+    let total = Arc::new(AtomicUsize::new(0));
+    Multipart::new("XBOUND", input)
+        .expect("boundary should be valid")
+        .into_stream()
+        .for_each_concurrent(2, |part| {
+            let total = Arc::clone(&total);
+            async move {
+                let part = part.expect("part should parse");
+                let handle = tokio::spawn(async move { part.body.len() });
+                total.fetch_add(handle.await.expect("task"), Ordering::SeqCst);
+            }
+        })
+        .await;
+    assert_eq!(total.load(Ordering::SeqCst), 11);
+}
+
+#[tokio::test]
+async fn parse_all_collects_text_and_file_parts_by_name() {
+    let body = concat!(
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"title\"\r\n",
+        "\r\n",
+        "Holiday\r\n",
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"photos\"; filename=\"a.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "first\r\n",
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"tag\"\r\n",
+        "\r\n",
+        "beach\r\n",
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"photos\"; filename=\"b.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "second\r\n",
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"tag\"\r\n",
+        "\r\n",
+        "sun\r\n",
+        "--XBOUND--\r\n"
+    );
+    let chunks = split_bytes(body.as_bytes(), &[7, 19, 3, 64, 5, 128]);
+    let input = stream::iter(chunks.into_iter().map(Ok::<Bytes, MulterError>));
+    let mut multipart = Multipart::new("XBOUND", input).expect("boundary should be valid");
+
+    let collected = multipart.parse_all().await.expect("body should parse");
+    assert_eq!(multipart.state(), MultipartState::Done);
+
+    assert_eq!(collected.text("title"), Some("Holiday"));
+    assert_eq!(collected.text("tag"), Some("beach"));
+    assert_eq!(
+        collected.all_text("tag").collect::<Vec<_>>(),
+        ["beach", "sun"]
+    );
+    assert_eq!(collected.text("missing"), None);
+
+    let first = collected.file("photos").expect("photo expected");
+    assert_eq!(first.file_name(), Some("a.png"));
+    assert_eq!(first.body(), &Bytes::from_static(b"first"));
+    let bodies = collected
+        .all_files("photos")
+        .map(|part| part.body().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(bodies, ["first", "second"]);
+    assert!(collected.file("title").is_none());
+    assert_eq!(collected.text_fields.len(), 3);
+    assert_eq!(collected.file_parts.len(), 2);
+}
+
+#[tokio::test]
+async fn parse_all_surfaces_incomplete_stream() {
+    let body = concat!(
+        "--XBOUND\r\n",
+        "Content-Disposition: form-data; name=\"title\"\r\n",
+        "\r\n",
+        "Holiday"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("XBOUND", input).expect("boundary should be valid");
+
+    let err = multipart
+        .parse_all()
+        .await
+        .expect_err("truncated body must fail");
+    assert!(matches!(err, MulterError::IncompleteStream), "{err:?}");
+}
+
+fn peek_body() -> &'static str {
+    concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"kind\"\r\n",
+        "\r\n",
+        "avatar\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"upload\"; filename=\"a.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "PNG\r\n",
+        "--BOUND--\r\n"
+    )
+}
+
+fn peek_multipart() -> Multipart<impl futures::Stream<Item = Result<Bytes, MulterError>> + Unpin> {
+    let chunks = split_bytes(peek_body().as_bytes(), &[7, 19, 23]);
+    let stream = stream::iter(chunks.into_iter().map(Ok::<Bytes, MulterError>));
+    Multipart::new("BOUND", stream).expect("boundary should be valid")
+}
+
+#[tokio::test]
+async fn peek_next_headers_then_next_part_yields_the_same_part() {
+    let mut multipart = peek_multipart();
+
+    let peeked = multipart
+        .peek_next_headers()
+        .await
+        .expect("peek should parse")
+        .expect("headers expected")
+        .clone();
+    assert_eq!(peeked.field_name, "kind");
+    assert_eq!(multipart.parts_started(), 1);
+
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    assert_eq!(part.parsed_headers(), &peeked);
+    assert_eq!(part.text().await.expect("text should read"), "avatar");
+    assert_eq!(multipart.parts_started(), 1, "headers are not parsed again");
+
+    let mut file = multipart
+        .next_part()
+        .await
+        .expect("file part should parse")
+        .expect("file part expected");
+    assert_eq!(file.file_name(), Some("a.png"));
+    assert_eq!(file.bytes().await.expect("body should read"), "PNG");
+    assert!(multipart
+        .peek_next_headers()
+        .await
+        .expect("end should parse")
+        .is_none());
+}
+
+#[tokio::test]
+async fn repeated_peeks_return_the_same_headers() {
+    let mut multipart = peek_multipart();
+
+    let first = multipart
+        .peek_next_headers()
+        .await
+        .expect("peek should parse")
+        .expect("headers expected")
+        .clone();
+    let second = multipart
+        .peek_next_headers()
+        .await
+        .expect("second peek should parse")
+        .expect("headers expected")
+        .clone();
+    assert_eq!(first, second);
+    assert_eq!(multipart.parts_started(), 1);
+    assert_eq!(multipart.parts_yielded(), 1);
+
+    let mut parts = multipart.into_stream();
+    let buffered = parts
+        .try_next()
+        .await
+        .expect("buffered part should parse")
+        .expect("buffered part expected");
+    assert_eq!(buffered.field_name(), "kind");
+    assert_eq!(buffered.body, "avatar");
+}
+
+#[tokio::test]
+async fn peeked_part_can_be_skipped() {
+    let mut multipart = peek_multipart();
+
+    let field_name = multipart
+        .peek_next_headers()
+        .await
+        .expect("peek should parse")
+        .map(|headers| headers.field_name.clone());
+    assert_eq!(field_name.as_deref(), Some("kind"));
+
+    drop(multipart.next_part().await.expect("part should parse"));
+    let upload = multipart
+        .peek_next_headers()
+        .await
+        .expect("peek should skip the unread body")
+        .expect("headers expected");
+    assert_eq!(upload.field_name, "upload");
+
+    let stats = multipart.drain_all().await.expect("drain should succeed");
+    assert_eq!(stats.parts_drained, 1);
+    assert_eq!(stats.bytes_discarded, 3);
+    assert!(multipart
+        .next_part()
+        .await
+        .expect("stream should finish")
+        .is_none());
+}
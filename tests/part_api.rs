@@ -2,7 +2,8 @@
 
 use bytes::Bytes;
 use futures::{stream, TryStreamExt};
-use multigear::{MulterError, Multipart, ParseError};
+use multigear::{BufferedPart, MemoryStorage, Multer, MulterError, Multipart, ParseError};
+use tokio::io::{AsyncReadExt, BufWriter};
 
 #[tokio::test]
 async fn exposes_metadata_accessors() {
@@ -39,6 +40,93 @@ async fn exposes_metadata_accessors() {
     assert_eq!(part.size_hint(), Some(3));
 }
 
+#[tokio::test]
+async fn exposes_custom_raw_part_headers() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"upload\"; filename=\"a.txt\"\r\n",
+        "X-Upload-Context: batch-001\r\n",
+        "Content-ID: <part-1@example.com>\r\n",
+        "\r\n",
+        "abc\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part expected")
+        .expect("part should parse");
+
+    assert_eq!(
+        part.headers()
+            .get("x-upload-context")
+            .and_then(|value| value.to_str().ok()),
+        Some("batch-001")
+    );
+    assert_eq!(
+        part.parsed_headers()
+            .headers
+            .get("content-id")
+            .and_then(|value| value.to_str().ok()),
+        Some("<part-1@example.com>")
+    );
+}
+
+#[tokio::test]
+async fn raw_header_accessors_return_ascii_values() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"upload\"; filename=\"a.txt\"\r\n",
+        "Content-Length: 3\r\n",
+        "X-Upload-Token: tok-42\r\n",
+        "X-Label: caf\u{e9}\r\n",
+        "\r\n",
+        "abc\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part expected")
+        .expect("part should parse");
+
+    assert_eq!(
+        part.raw_header("Content-Disposition"),
+        Some("form-data; name=\"upload\"; filename=\"a.txt\"")
+    );
+    assert_eq!(part.raw_header("content-length"), Some("3"));
+    assert_eq!(part.raw_header("X-UPLOAD-TOKEN"), Some("tok-42"));
+    assert_eq!(
+        part.raw_header("x-label"),
+        None,
+        "non-ASCII values are skipped"
+    );
+    assert_eq!(part.raw_header("x-missing"), None);
+
+    let headers = part.raw_headers().collect::<Vec<_>>();
+    assert_eq!(
+        headers,
+        [
+            (
+                "content-disposition",
+                "form-data; name=\"upload\"; filename=\"a.txt\""
+            ),
+            ("content-length", "3"),
+            ("x-upload-token", "tok-42"),
+        ]
+    );
+}
+
 #[tokio::test]
 async fn bytes_are_single_pass() {
     let input_body =
@@ -116,6 +204,520 @@ async fn text_rejects_non_utf8_payloads() {
     ));
 }
 
+#[tokio::test]
+async fn take_headers_leaves_body_readable() {
+    let input_body =
+        "--BOUND\r\nContent-Disposition: form-data; name=\"field\"\r\n\r\nhello\r\n--BOUND--\r\n";
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        input_body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part expected")
+        .expect("part should parse");
+
+    let headers = part.take_headers();
+    assert_eq!(&headers, part.parsed_headers());
+    assert_eq!(part.text().await.expect("body should read"), "hello");
+}
+
+#[tokio::test]
+async fn into_owned_headers_splits_headers_from_body() {
+    let input_body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"upload\"; filename=\"a.txt\"\r\n",
+        "\r\n",
+        "owned-body\r\n",
+        "--BOUND--\r\n"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        input_body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part expected")
+        .expect("part should parse");
+
+    let (headers, body) = part.into_owned_headers().await.expect("body should read");
+    assert!(multipart.next_part().await.expect("end").is_none());
+    let logger = tokio::spawn(async move { headers.file_name });
+    let chunks = tokio::spawn(body.try_collect::<Vec<_>>())
+        .await
+        .expect("task should join")
+        .expect("body should read");
+
+    assert_eq!(chunks, vec![Bytes::from_static(b"owned-body")]);
+    assert_eq!(
+        logger.await.expect("task should join").as_deref(),
+        Some("a.txt")
+    );
+}
+
+#[tokio::test]
+async fn buffered_parts_outlive_the_multipart_stream() {
+    let input_body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "Content-Type: text/plain\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"upload\"; filename=\"a.bin\"\r\n",
+        "Content-Type: application/octet-stream\r\n",
+        "\r\n",
+        "\u{1}\u{2}\r\n",
+        "--BOUND--\r\n"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        input_body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+
+    let mut parts: Vec<BufferedPart> = Vec::new();
+    while let Some(part) = multipart.next_part().await.expect("part should parse") {
+        parts.push(part.into_buffered().await.expect("body should buffer"));
+    }
+    drop(multipart);
+
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].field_name(), "note");
+    assert_eq!(parts[0].file_name(), None);
+    assert_eq!(parts[0].content_type().essence_str(), "text/plain");
+    assert_eq!(parts[0].text().expect("utf-8 text"), "hello");
+
+    assert_eq!(parts[1].field_name(), "upload");
+    assert_eq!(parts[1].file_name(), Some("a.bin"));
+    assert_eq!(parts[1].content_type(), &mime::APPLICATION_OCTET_STREAM);
+    assert_eq!(parts[1].body(), &Bytes::from_static(&[1, 2]));
+}
+
+#[tokio::test]
+async fn into_reader_yields_same_bytes_as_bytes() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"upload\"; filename=\"a.bin\"\r\n",
+        "\r\n",
+        "streamed through an AsyncRead adapter\r\n",
+        "--BOUND--\r\n"
+    );
+    let chunked = || {
+        stream::iter(
+            body.as_bytes()
+                .chunks(7)
+                .map(|chunk| Ok::<Bytes, MulterError>(Bytes::copy_from_slice(chunk)))
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let mut multipart = Multipart::new("BOUND", chunked()).expect("boundary should be valid");
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let expected = part.bytes().await.expect("bytes");
+
+    let mut multipart = Multipart::new("BOUND", chunked()).expect("boundary should be valid");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let mut read = Vec::new();
+    part.into_reader()
+        .read_to_end(&mut read)
+        .await
+        .expect("read_to_end should succeed");
+
+    assert_eq!(read, expected.to_vec());
+
+    let mut multipart = Multipart::new("BOUND", chunked()).expect("boundary should be valid");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let mut copied = Vec::new();
+    let written = tokio::io::copy(&mut part.into_reader(), &mut copied)
+        .await
+        .expect("copy should succeed");
+    assert_eq!(written, expected.len() as u64);
+    assert_eq!(copied, expected.to_vec());
+}
+
+#[tokio::test]
+async fn into_reader_propagates_parse_errors_as_io_errors() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"upload\"; filename=\"a.bin\"\r\n",
+        "\r\n",
+        "truncated"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+
+    let err = part
+        .into_reader()
+        .read_to_end(&mut Vec::new())
+        .await
+        .expect_err("truncated body must fail");
+    let inner = err
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<MulterError>())
+        .expect("io error should wrap MulterError");
+    assert!(matches!(inner, MulterError::IncompleteStream), "{inner:?}");
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn json_deserializes_body_and_reports_field_on_failure() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Meta {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"meta\"\r\n",
+        "Content-Type: application/json\r\n",
+        "\r\n",
+        "{\"title\":\"Holiday\",\"tags\":[\"beach\",\"sun\"]}\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"broken\"\r\n",
+        "\r\n",
+        "{\"title\":1}\r\n",
+        "--BOUND--\r\n"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let meta: Meta = part.json().await.expect("json should deserialize");
+    assert_eq!(
+        meta,
+        Meta {
+            title: "Holiday".to_owned(),
+            tags: vec!["beach".to_owned(), "sun".to_owned()],
+        }
+    );
+    assert_already_consumed(part.json::<Meta>().await.expect_err("second read fails"));
+
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let err = part
+        .json::<Meta>()
+        .await
+        .expect_err("invalid shape must fail");
+    assert!(
+        matches!(&err, MulterError::Deserialize { field, .. } if field == "broken"),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn form_urlencoded_pairs_decodes_body() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"query\"\r\n",
+        "Content-Type: application/x-www-form-urlencoded\r\n",
+        "\r\n",
+        "name=Jane+Doe&city=S%C3%A3o%20Paulo&empty=&flag\r\n",
+        "--BOUND--\r\n"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+
+    let pairs = part
+        .form_urlencoded_pairs()
+        .await
+        .expect("pairs should parse");
+    let expected = [
+        ("name", "Jane Doe"),
+        ("city", "São Paulo"),
+        ("empty", ""),
+        ("flag", ""),
+    ]
+    .map(|(key, value)| (key.to_owned(), value.to_owned()));
+    assert_eq!(pairs, expected);
+
+    assert_already_consumed(
+        part.form_urlencoded_pairs()
+            .await
+            .expect_err("second read fails"),
+    );
+}
+
+#[tokio::test]
+async fn copy_to_writes_body_across_chunks() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"upload\"; filename=\"notes.txt\"\r\n",
+        "\r\n",
+        "first line\nsecond line\r\n",
+        "--BOUND--\r\n"
+    );
+    let input = stream::iter(
+        body.as_bytes()
+            .chunks(7)
+            .map(|chunk| Ok::<Bytes, MulterError>(Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>(),
+    );
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+
+    let mut writer = BufWriter::new(Vec::new());
+    let copied = part
+        .copy_to(&mut writer)
+        .await
+        .expect("copy should succeed");
+    assert_eq!(copied, 22);
+    assert_eq!(writer.into_inner(), b"first line\nsecond line");
+
+    assert_already_consumed(
+        part.copy_to(&mut BufWriter::new(Vec::new()))
+            .await
+            .expect_err("second copy fails"),
+    );
+}
+
+#[tokio::test]
+async fn part_size_hint_prefers_part_content_length() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"a.bin\"\r\n",
+        "Content-Length: 3\r\n",
+        "\r\n",
+        "abc\r\n",
+        "--BOUND--\r\n"
+    );
+    let multer = Multer::builder()
+        .single("file")
+        .storage(MemoryStorage::new())
+        .build()
+        .expect("multer should build");
+    let mut multipart = multer
+        .multipart_from_boundary("BOUND", bytes_from(body))
+        .expect("multipart should initialize")
+        .with_request_content_length(Some(body.len() as u64));
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+
+    assert_eq!(part.request_content_length(), Some(body.len() as u64));
+    assert_eq!(multer.part_size_hint(&part), Some(3));
+}
+
+#[tokio::test]
+async fn part_size_hint_falls_back_to_request_length_for_single_file() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "\r\n",
+        "hi\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"a.bin\"\r\n",
+        "\r\n",
+        "abc\r\n",
+        "--BOUND--\r\n"
+    );
+    let request_length = Some(body.len() as u64);
+    let single = Multer::builder()
+        .single("file")
+        .storage(MemoryStorage::new())
+        .build()
+        .expect("multer should build");
+    let any = Multer::new(MemoryStorage::new());
+
+    let mut multipart = single
+        .multipart_from_boundary("BOUND", bytes_from(body))
+        .expect("multipart should initialize")
+        .with_request_content_length(request_length);
+    let text = multipart
+        .next_part()
+        .await
+        .expect("text part should parse")
+        .expect("text part expected");
+    assert_eq!(
+        single.part_size_hint(&text),
+        None,
+        "text parts never fall back"
+    );
+    drop(text);
+    let file = multipart
+        .next_part()
+        .await
+        .expect("file part should parse")
+        .expect("file part expected");
+    assert_eq!(single.part_size_hint(&file), request_length);
+    assert_eq!(
+        any.part_size_hint(&file),
+        None,
+        "many files may share the body"
+    );
+    drop(file);
+
+    let mut multipart = single
+        .multipart_from_boundary("BOUND", bytes_from(body))
+        .expect("multipart should initialize");
+    multipart
+        .next_part()
+        .await
+        .expect("text part")
+        .expect("text");
+    let file = multipart
+        .next_part()
+        .await
+        .expect("file part should parse")
+        .expect("file part expected");
+    assert_eq!(
+        single.part_size_hint(&file),
+        None,
+        "no request length injected"
+    );
+}
+
+fn bytes_from(
+    body: &'static str,
+) -> impl futures::Stream<Item = Result<Bytes, MulterError>> + Unpin {
+    stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))])
+}
+
+#[cfg(feature = "encoding")]
+async fn decode_with_content_type(
+    content_type: Option<&str>,
+    payload: &[u8],
+) -> (Option<String>, Result<String, MulterError>) {
+    let mut bytes = b"--BOUND\r\nContent-Disposition: form-data; name=\"note\"\r\n".to_vec();
+    if let Some(content_type) = content_type {
+        bytes.extend_from_slice(format!("Content-Type: {content_type}\r\n").as_bytes());
+    }
+    bytes.extend_from_slice(b"\r\n");
+    bytes.extend_from_slice(payload);
+    bytes.extend_from_slice(b"\r\n--BOUND--\r\n");
+
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from(bytes))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let charset = part.charset().map(str::to_owned);
+    (charset, part.decode_text().await)
+}
+
+#[tokio::test]
+async fn charset_reads_content_type_parameter() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "Content-Type: text/plain; charset=ISO-8859-1\r\n",
+        "\r\n",
+        "x\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"plain\"\r\n",
+        "\r\n",
+        "y\r\n",
+        "--BOUND--\r\n"
+    );
+    let input = stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        body.as_bytes(),
+    ))]);
+    let mut multipart = Multipart::new("BOUND", input).expect("boundary should be valid");
+
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    assert_eq!(part.charset(), Some("iso-8859-1"));
+    part.drain().await.expect("drain should succeed");
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    assert_eq!(part.charset(), None);
+}
+
+#[cfg(feature = "encoding")]
+#[tokio::test]
+async fn decode_text_uses_declared_charset() {
+    let (charset, text) =
+        decode_with_content_type(Some("text/plain; charset=utf-8"), "Grüße".as_bytes()).await;
+    assert_eq!(charset.as_deref(), Some("utf-8"));
+    assert_eq!(text.expect("utf-8 should decode"), "Grüße");
+
+    let (_, text) =
+        decode_with_content_type(Some("text/plain; charset=iso-8859-1"), b"Gr\xfc\xdfe").await;
+    assert_eq!(text.expect("latin-1 should decode"), "Grüße");
+
+    let (_, text) = decode_with_content_type(
+        Some("text/plain; charset=windows-1252"),
+        b"\x80 5 \x93ok\x94",
+    )
+    .await;
+    assert_eq!(
+        text.expect("windows-1252 should decode"),
+        "€ 5 \u{201c}ok\u{201d}"
+    );
+}
+
+#[cfg(feature = "encoding")]
+#[tokio::test]
+async fn decode_text_falls_back_to_utf8() {
+    let (charset, text) = decode_with_content_type(None, "héllo".as_bytes()).await;
+    assert_eq!(charset, None);
+    assert_eq!(text.expect("utf-8 fallback should decode"), "héllo");
+
+    let (_, text) =
+        decode_with_content_type(Some("text/plain; charset=x-unknown"), "héllo".as_bytes()).await;
+    assert_eq!(text.expect("unknown labels fall back to utf-8"), "héllo");
+
+    let (_, text) = decode_with_content_type(None, b"fo\x80").await;
+    assert!(matches!(
+        text.expect_err("invalid utf-8 should fail"),
+        MulterError::Parse(_)
+    ));
+}
+
 fn assert_already_consumed(err: MulterError) {
     assert!(
         err.to_string().contains("already consumed"),
@@ -0,0 +1,135 @@
+#![allow(missing_docs)]
+#![cfg(feature = "rocket")]
+
+use std::sync::Arc;
+
+use multigear::{
+    rocket::{MulterFairing, MulterForm},
+    MemoryStorage, Multer,
+};
+use rocket::{
+    http::{ContentType, Status},
+    local::asynchronous::Client,
+    Build, Rocket,
+};
+
+#[rocket::post("/upload", data = "<form>")]
+fn upload(form: MulterForm<MemoryStorage>) -> String {
+    let files = form
+        .file_parts
+        .iter()
+        .map(|part| {
+            format!(
+                "{}={}",
+                part.field_name(),
+                String::from_utf8_lossy(&part.body)
+            )
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "title={} files=[{}]",
+        form.text("title").unwrap_or("<none>"),
+        files.join(",")
+    )
+}
+
+fn build_rocket(multer: Multer<MemoryStorage>) -> Rocket<Build> {
+    rocket::build()
+        .attach(MulterFairing::new(Arc::new(multer)))
+        .mount("/", rocket::routes![upload])
+}
+
+fn multipart_content_type() -> ContentType {
+    ContentType::new("multipart", "form-data").with_params(("boundary", "BOUND"))
+}
+
+const BODY: &str = concat!(
+    "--BOUND\r\n",
+    "Content-Disposition: form-data; name=\"title\"\r\n",
+    "\r\n",
+    "Holiday\r\n",
+    "--BOUND\r\n",
+    "Content-Disposition: form-data; name=\"photo\"; filename=\"a.png\"\r\n",
+    "Content-Type: image/png\r\n",
+    "\r\n",
+    "PNG\r\n",
+    "--BOUND--\r\n"
+);
+
+#[rocket::async_test]
+async fn form_guard_collects_multipart_body() {
+    let multer = Multer::builder()
+        .single("photo")
+        .storage(MemoryStorage::new())
+        .build()
+        .expect("multer should build");
+    let client = Client::tracked(build_rocket(multer))
+        .await
+        .expect("rocket should launch");
+
+    let response = client
+        .post("/upload")
+        .header(multipart_content_type())
+        .body(BODY)
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.into_string().await.as_deref(),
+        Some("title=Holiday files=[photo=PNG]")
+    );
+}
+
+#[rocket::async_test]
+async fn form_guard_maps_multer_errors_to_status() {
+    let multer = Multer::builder()
+        .single("photo")
+        .max_file_size(2)
+        .storage(MemoryStorage::new())
+        .build()
+        .expect("multer should build");
+    let client = Client::tracked(build_rocket(multer))
+        .await
+        .expect("rocket should launch");
+
+    let response = client
+        .post("/upload")
+        .header(multipart_content_type())
+        .body(BODY)
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+}
+
+#[rocket::async_test]
+async fn form_guard_forwards_non_multipart_requests() {
+    let client = Client::tracked(build_rocket(Multer::new(MemoryStorage::new())))
+        .await
+        .expect("rocket should launch");
+
+    let response = client
+        .post("/upload")
+        .header(ContentType::JSON)
+        .body("{\"ok\":true}")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnsupportedMediaType);
+}
+
+#[rocket::async_test]
+async fn form_guard_requires_managed_multer() {
+    let rocket = rocket::build().mount("/", rocket::routes![upload]);
+    let client = Client::tracked(rocket).await.expect("rocket should launch");
+
+    let response = client
+        .post("/upload")
+        .header(multipart_content_type())
+        .body(BODY)
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::InternalServerError);
+}
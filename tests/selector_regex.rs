@@ -0,0 +1,105 @@
+#![allow(missing_docs)]
+#![cfg(feature = "regex")]
+
+use bytes::Bytes;
+use futures::stream;
+use multigear::{
+    ConfigError, MulterConfig, MulterError, Multipart, MultipartBodyBuilder, Selector,
+    UnknownFieldPolicy,
+};
+
+fn config(max_count: Option<usize>) -> MulterConfig {
+    MulterConfig {
+        selector: Selector::Regex {
+            pattern: "attachment_[0-9]+".to_owned(),
+            max_count,
+        },
+        unknown_field_policy: UnknownFieldPolicy::Reject,
+        ..MulterConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn regex_selector_accepts_matching_file_fields() {
+    let body = multipart_body(&[
+        ("attachment_1", Some("a.txt"), "one"),
+        ("note", None, "text fields are unaffected"),
+        ("attachment_22", Some("b.txt"), "two"),
+    ]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config(None))
+        .expect("multipart should initialize");
+
+    let mut names = Vec::new();
+    while let Some(part) = multipart.next_part().await.expect("part should pass") {
+        names.push(part.field_name().to_owned());
+    }
+    assert_eq!(names, ["attachment_1", "note", "attachment_22"]);
+}
+
+#[tokio::test]
+async fn regex_selector_rejects_non_matching_file_fields() {
+    for field in ["attachment_", "attachment_1x", "my_attachment_1", "avatar"] {
+        let body = multipart_body(&[(field, Some("a.txt"), "one")]);
+        let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config(None))
+            .expect("multipart should initialize");
+
+        let err = multipart
+            .next_part()
+            .await
+            .expect_err("field must not match");
+        assert!(
+            matches!(&err, MulterError::UnexpectedField { field: name } if name == field),
+            "{field}: {err:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn regex_selector_caps_total_matching_files() {
+    let body = multipart_body(&[
+        ("attachment_1", Some("a.txt"), "one"),
+        ("attachment_2", Some("b.txt"), "two"),
+        ("attachment_3", Some("c.txt"), "three"),
+    ]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config(Some(2)))
+        .expect("multipart should initialize");
+
+    multipart.next_part().await.expect("first should pass");
+    multipart.next_part().await.expect("second should pass");
+    let err = multipart.next_part().await.expect_err("third exceeds cap");
+    assert!(matches!(
+        err,
+        MulterError::FieldCountLimitExceeded { field, max_count: 2 } if field == "attachment_3"
+    ));
+}
+
+#[test]
+fn invalid_regex_pattern_fails_config_validation() {
+    let config = MulterConfig {
+        selector: Selector::regex("attachment_[0-9"),
+        ..MulterConfig::default()
+    };
+
+    let err = config.validate().expect_err("pattern must not compile");
+    assert!(matches!(
+        err,
+        ConfigError::InvalidRegexPattern { ref pattern, .. } if pattern == "attachment_[0-9"
+    ));
+    assert!(Multipart::with_config("BOUND", bytes_stream(Vec::new()), config).is_err());
+}
+
+fn multipart_body(parts: &[(&str, Option<&str>, &str)]) -> Vec<u8> {
+    let mut builder = MultipartBodyBuilder::new();
+    for (field, file_name, body) in parts {
+        let body = Bytes::copy_from_slice(body.as_bytes());
+        builder = match file_name {
+            Some(file_name) => builder.file(field, file_name, "application/octet-stream", body),
+            None => builder.part(field, body),
+        };
+    }
+    builder.build("BOUND").to_vec()
+}
+
+fn bytes_stream(body: Vec<u8>) -> impl futures::Stream<Item = Result<Bytes, MulterError>> {
+    stream::iter([Ok(Bytes::from(body))])
+}
@@ -1,9 +1,12 @@
 #![allow(missing_docs)]
 
+use std::sync::{Arc, Mutex};
+
 use bytes::Bytes;
 use futures::stream;
 use multigear::{
-    MulterConfig, MulterError, Multipart, SelectedField, Selector, UnknownFieldPolicy,
+    MulterConfig, MulterError, Multipart, MultipartBodyBuilder, SelectedField, Selector,
+    UnknownFieldAction, UnknownFieldPolicy,
 };
 
 #[tokio::test]
@@ -59,12 +62,57 @@ async fn array_selector_rejects_unknown_file_field() {
     ));
 }
 
+#[tokio::test]
+async fn callback_policy_decides_per_unknown_field() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&seen);
+    let config = MulterConfig {
+        selector: Selector::fields([SelectedField::file("doc")]),
+        unknown_field_policy: UnknownFieldPolicy::callback(move |name| {
+            recorded.lock().expect("lock").push(name.to_owned());
+            match name {
+                "skip" => UnknownFieldAction::Ignore,
+                name if name.starts_with('_') => UnknownFieldAction::IgnoreAndLog,
+                _ => UnknownFieldAction::Reject,
+            }
+        }),
+        ..MulterConfig::default()
+    };
+    let body = multipart_body(&[
+        ("_meta", Some("m.json"), "{}"),
+        ("skip", Some("s.txt"), "skipped"),
+        ("_note", None, "logged"),
+        ("doc", Some("a.txt"), "kept"),
+        ("extra", Some("x.txt"), "rejected"),
+    ]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
+        .expect("multipart should initialize");
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("item expected")
+        .expect("known field should pass selector");
+    assert_eq!(part.field_name(), "doc");
+    drop(part);
+
+    let err = multipart.next_part().await.expect_err("item expected");
+    assert!(matches!(
+        err,
+        MulterError::UnexpectedField { field } if field == "extra"
+    ));
+    assert_eq!(
+        *seen.lock().expect("lock"),
+        ["_meta", "skip", "_note", "extra"]
+    );
+}
+
 #[tokio::test]
 async fn fields_selector_enforces_per_field_max_counts() {
     let config = MulterConfig {
         selector: Selector::fields([
-            SelectedField::new("docs").with_max_count(1),
-            SelectedField::new("images").with_max_count(2),
+            SelectedField::file("docs").with_max_count(1),
+            SelectedField::file("images").with_max_count(2),
         ]),
         unknown_field_policy: UnknownFieldPolicy::Reject,
         ..MulterConfig::default()
@@ -116,6 +164,55 @@ async fn fields_selector_enforces_per_field_max_counts() {
     ));
 }
 
+#[tokio::test]
+async fn fields_selector_checks_extensions_even_when_mime_matches() {
+    let config = MulterConfig {
+        selector: Selector::fields([SelectedField::file("photos")
+            .max_count(3)
+            .allowed_mime_types(["application/octet-stream"])
+            .allowed_extensions(["png", ".JPG"])]),
+        unknown_field_policy: UnknownFieldPolicy::Reject,
+        ..MulterConfig::default()
+    };
+    let body = multipart_body(&[
+        ("photos", Some("a.PNG"), "one"),
+        ("photos", Some("b.jpg"), "two"),
+        ("photos", Some("c.png.exe"), "three"),
+    ]);
+
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config.clone())
+        .expect("multipart should initialize");
+    for expected in ["a.PNG", "b.jpg"] {
+        let part = multipart
+            .next_part()
+            .await
+            .expect("allowed extension should pass")
+            .expect("part expected");
+        assert_eq!(part.file_name(), Some(expected));
+    }
+    let err = multipart
+        .next_part()
+        .await
+        .expect_err("spoofed extension must fail");
+    assert!(matches!(
+        err,
+        MulterError::ExtensionNotAllowed { field, extension }
+            if field == "photos" && extension == "exe"
+    ));
+
+    let body = multipart_body(&[("photos", Some("README"), "four")]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
+        .expect("multipart should initialize");
+    let err = multipart
+        .next_part()
+        .await
+        .expect_err("missing extension must fail");
+    assert!(matches!(
+        err,
+        MulterError::ExtensionNotAllowed { extension, .. } if extension.is_empty()
+    ));
+}
+
 #[tokio::test]
 async fn none_selector_with_ignore_policy_skips_files_but_keeps_text_fields() {
     let config = MulterConfig {
@@ -169,29 +266,144 @@ async fn any_selector_accepts_all_file_fields() {
     assert_eq!(names, vec!["a", "b"]);
 }
 
+#[test]
+fn for_form_matches_manually_built_fields_selector() {
+    let built = Selector::for_form(|form| {
+        form.text("title")
+            .file_with_count("photos", 3)
+            .file_with_mime("avatar", ["image/png", "image/jpeg"])
+    });
+    let manual = Selector::fields([
+        SelectedField::text("title"),
+        SelectedField::file("photos").max_count(3),
+        SelectedField::file("avatar").allowed_mime_types(["image/png", "image/jpeg"]),
+    ]);
+
+    assert_eq!(built, manual);
+    assert!(built.validate().is_ok());
+}
+
+#[tokio::test]
+async fn validate_required_fields_reports_missing_minimums() {
+    let config = MulterConfig {
+        selector: Selector::fields([
+            SelectedField::file("docs").with_min_count(2),
+            SelectedField::text("title").with_min_count(1),
+        ]),
+        ..MulterConfig::default()
+    };
+    let body = multipart_body(&[("title", None, "report"), ("docs", Some("a.pdf"), "one")]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
+        .expect("multipart should initialize");
+
+    while let Some(mut part) = multipart.next_part().await.expect("part should parse") {
+        part.drain().await.expect("drain part");
+    }
+
+    let err = multipart
+        .validate_required_fields()
+        .expect_err("docs appeared once but requires two");
+    assert!(matches!(
+        err,
+        MulterError::RequiredFieldMissing { field, min_count: 2 } if field == "docs"
+    ));
+}
+
+#[tokio::test]
+async fn validate_required_fields_counts_text_and_file_parts() {
+    let config = MulterConfig {
+        selector: Selector::fields([
+            SelectedField::file("docs").with_min_count(2),
+            SelectedField::text("title").with_min_count(1),
+        ]),
+        ..MulterConfig::default()
+    };
+    let body = multipart_body(&[
+        ("docs", Some("a.pdf"), "one"),
+        ("title", None, "report"),
+        ("docs", Some("b.pdf"), "two"),
+    ]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
+        .expect("multipart should initialize");
+
+    assert!(matches!(
+        multipart.validate_required_fields(),
+        Err(MulterError::RequiredFieldMissing { field, .. }) if field == "docs"
+    ));
+    while let Some(mut part) = multipart.next_part().await.expect("part should parse") {
+        part.drain().await.expect("drain part");
+    }
+
+    multipart
+        .validate_required_fields()
+        .expect("all minimums are satisfied");
+}
+
+#[tokio::test]
+async fn validate_required_fields_rejects_empty_required_text_field() {
+    let config = MulterConfig {
+        selector: Selector::fields([
+            SelectedField::file("docs"),
+            SelectedField::text("title").with_min_count(1),
+            SelectedField::text("note"),
+        ]),
+        ..MulterConfig::default()
+    };
+    let body = multipart_body(&[
+        ("title", None, ""),
+        ("note", None, ""),
+        ("docs", Some("a.pdf"), "one"),
+    ]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config.clone())
+        .expect("multipart should initialize");
+    while let Some(mut part) = multipart.next_part().await.expect("part should parse") {
+        part.drain().await.expect("drain part");
+    }
+    assert!(matches!(
+        multipart.validate_required_fields(),
+        Err(MulterError::RequiredFieldMissing { field, min_count: 1 }) if field == "title"
+    ));
+
+    let body = multipart_body(&[("title", None, ""), ("title", None, "report")]);
+    let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
+        .expect("multipart should initialize");
+    while let Some(mut part) = multipart.next_part().await.expect("part should parse") {
+        part.text().await.expect("read text");
+    }
+    multipart
+        .validate_required_fields()
+        .expect("a non-empty value satisfies the minimum");
+}
+
+#[tokio::test]
+async fn validate_required_fields_is_noop_for_any_and_none_selectors() {
+    for selector in [Selector::any(), Selector::none()] {
+        let config = MulterConfig {
+            selector,
+            ..MulterConfig::default()
+        };
+        let body = multipart_body(&[("title", None, "")]);
+        let mut multipart = Multipart::with_config("BOUND", bytes_stream(body), config)
+            .expect("multipart should initialize");
+        while let Some(mut part) = multipart.next_part().await.expect("part should parse") {
+            part.drain().await.expect("drain part");
+        }
+        multipart
+            .validate_required_fields()
+            .expect("nothing to validate");
+    }
+}
+
 fn multipart_body(parts: &[(&str, Option<&str>, &str)]) -> Vec<u8> {
-    let mut out = Vec::new();
+    let mut builder = MultipartBodyBuilder::new();
     for (field, file_name, body) in parts {
-        out.extend_from_slice(b"--BOUND\r\n");
-        match file_name {
-            Some(file_name) => {
-                let disposition = format!(
-                    "Content-Disposition: form-data; name=\"{field}\"; filename=\"{file_name}\"\r\n"
-                );
-                out.extend_from_slice(disposition.as_bytes());
-                out.extend_from_slice(b"Content-Type: application/octet-stream\r\n\r\n");
-            }
-            None => {
-                let disposition =
-                    format!("Content-Disposition: form-data; name=\"{field}\"\r\n\r\n");
-                out.extend_from_slice(disposition.as_bytes());
-            }
-        }
-        out.extend_from_slice(body.as_bytes());
-        out.extend_from_slice(b"\r\n");
+        let body = Bytes::copy_from_slice(body.as_bytes());
+        builder = match file_name {
+            Some(file_name) => builder.file(field, file_name, "application/octet-stream", body),
+            None => builder.part(field, body),
+        };
     }
-    out.extend_from_slice(b"--BOUND--\r\n");
-    out
+    builder.build("BOUND").to_vec()
 }
 
 fn bytes_stream(body: Vec<u8>) -> impl futures::Stream<Item = Result<Bytes, MulterError>> {
@@ -4,7 +4,10 @@ use std::{collections::HashMap, sync::Arc};
 
 use bytes::Bytes;
 use futures::{stream, StreamExt};
-use multigear::{BoxStream, Multer, MulterError, Multipart, StorageEngine, StorageError};
+use multigear::{
+    BoxStream, BoxedStorage, DiskStorage, DynStorageEngine, HybridStorage, MemoryStorage, Multer,
+    MulterError, Multipart, NoopStorage, StorageEngine, StorageError, TeePolicy, TeeStorageEngine,
+};
 use tokio::sync::RwLock;
 
 #[derive(Debug, Clone, Default)]
@@ -121,3 +124,367 @@ async fn custom_storage_works_with_parse_and_store_pipeline() {
         vec![("note".to_owned(), "two".to_owned())]
     );
 }
+
+#[tokio::test]
+async fn tee_storage_delivers_identical_bytes_to_both_backends() {
+    let storage = TeeStorageEngine::new(MapStorage::default(), MemoryStorage::new());
+    let multer = Multer::new(storage.clone());
+
+    let payload = (0..32 * 1024).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let mut body =
+        b"--BOUND\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.bin\"\r\n\r\n"
+            .to_vec();
+    body.extend_from_slice(&payload);
+    body.extend_from_slice(b"\r\n--BOUND--\r\n");
+    let chunks = body
+        .chunks(1000)
+        .map(|chunk| Ok::<Bytes, MulterError>(Bytes::copy_from_slice(chunk)))
+        .collect::<Vec<_>>();
+
+    let mut multipart = Multipart::new("BOUND", stream::iter(chunks)).expect("multipart");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let (primary, secondary) = multer.store(part).await.expect("tee store should succeed");
+    let secondary = secondary.expect("fail-fast tee always returns both outputs");
+
+    let primary_bytes = storage
+        .primary()
+        .items
+        .read()
+        .await
+        .get(&primary.key)
+        .cloned()
+        .expect("primary bytes");
+    let secondary_bytes = storage
+        .secondary()
+        .get(&secondary.storage_key)
+        .await
+        .expect("secondary bytes");
+    assert_eq!(primary_bytes, Bytes::from(payload));
+    assert_eq!(primary_bytes, secondary_bytes);
+    assert_eq!(primary.size, secondary.size);
+}
+
+#[tokio::test]
+async fn tee_policy_controls_secondary_failures() {
+    let fail_fast = TeeStorageEngine::new(MemoryStorage::new(), NoopStorage);
+    let err = fail_fast
+        .store("file", Some("a.bin"), "application/octet-stream", chunks())
+        .await
+        .expect_err("fail-fast surfaces secondary errors");
+    assert!(
+        err.to_string().contains("secondary storage failed"),
+        "{err}"
+    );
+
+    let best_effort =
+        TeeStorageEngine::new(MemoryStorage::new(), NoopStorage).with_policy(TeePolicy::BestEffort);
+    let (primary, secondary) = best_effort
+        .store("file", Some("a.bin"), "application/octet-stream", chunks())
+        .await
+        .expect("best effort succeeds when the primary succeeds");
+    assert!(secondary.is_none());
+    assert_eq!(
+        best_effort.primary().get(&primary.storage_key).await,
+        Some(Bytes::from_static(b"hello"))
+    );
+
+    let err = TeeStorageEngine::new(NoopStorage, MemoryStorage::new())
+        .with_policy(TeePolicy::BestEffort)
+        .store("file", Some("a.bin"), "application/octet-stream", chunks())
+        .await
+        .expect_err("primary failures always fail the store");
+    assert!(err.to_string().contains("primary storage failed"), "{err}");
+}
+
+#[tokio::test]
+async fn fail_fast_tee_discards_primary_copy_when_secondary_fails() {
+    let tee = TeeStorageEngine::new(MemoryStorage::new(), MemoryStorage::with_capacity(2));
+    let err = tee
+        .store("file", Some("a.bin"), "application/octet-stream", chunks())
+        .await
+        .expect_err("secondary capacity is exceeded");
+
+    assert!(
+        err.to_string().contains("secondary storage failed"),
+        "{err}"
+    );
+    assert!(
+        tee.primary().is_empty().await,
+        "primary copy must be discarded"
+    );
+}
+
+#[tokio::test]
+async fn best_effort_tee_discards_secondary_copy_when_primary_fails() {
+    let tee = TeeStorageEngine::new(MemoryStorage::with_capacity(2), MemoryStorage::new())
+        .with_policy(TeePolicy::BestEffort);
+    let err = tee
+        .store("file", Some("a.bin"), "application/octet-stream", chunks())
+        .await
+        .expect_err("primary capacity is exceeded");
+
+    assert!(err.to_string().contains("primary storage failed"), "{err}");
+    assert!(
+        tee.secondary().is_empty().await,
+        "secondary copy must be discarded"
+    );
+}
+
+fn runtime_multer(use_disk: bool, root: &std::path::Path) -> Multer<BoxedStorage> {
+    let builder = Multer::builder().single("file");
+    let builder = if use_disk {
+        builder.boxed_storage(
+            DiskStorage::builder()
+                .destination(root)
+                .build()
+                .expect("disk storage should build"),
+        )
+    } else {
+        builder.boxed_storage(MemoryStorage::new())
+    };
+    builder.build().expect("multer should build")
+}
+
+#[tokio::test]
+async fn boxed_storage_selects_backend_at_runtime() {
+    let root = std::env::temp_dir().join(format!("multigear-boxed-{}", uuid::Uuid::new_v4()));
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n",
+        "Content-Type: text/plain\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND--\r\n"
+    );
+
+    for use_disk in [true, false] {
+        let multer = Arc::new(runtime_multer(use_disk, &root));
+        let output = multer
+            .parse_and_store(
+                "BOUND",
+                stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                    body.as_bytes(),
+                ))]),
+            )
+            .await
+            .expect("pipeline should succeed");
+
+        let stored = &output.stored_files[0];
+        assert_eq!(stored.size, 5);
+        assert_eq!(stored.path.is_some(), use_disk, "{stored:?}");
+    }
+
+    let _ = tokio::fs::remove_dir_all(&root).await;
+}
+
+#[tokio::test]
+async fn dyn_storage_engines_route_by_name() {
+    let root = std::env::temp_dir().join(format!("multigear-dyn-{}", uuid::Uuid::new_v4()));
+    let mut routes: HashMap<String, Box<dyn DynStorageEngine>> = HashMap::new();
+    routes.insert("memory".to_owned(), MemoryStorage::new().into_boxed());
+    routes.insert(
+        "disk".to_owned(),
+        DiskStorage::builder()
+            .destination(&root)
+            .build()
+            .expect("disk storage should build")
+            .into_boxed(),
+    );
+
+    for (route, on_disk) in [("memory", false), ("disk", true)] {
+        let stored = routes[route]
+            .store_dyn("file", Some("a.txt"), "text/plain", Some(5), chunks())
+            .await
+            .expect("store should succeed");
+        assert_eq!(stored.size, 5);
+        assert_eq!(stored.path.is_some(), on_disk, "{route}: {stored:?}");
+    }
+
+    let memory = routes.remove("memory").expect("memory route");
+    let storage = BoxedStorage::from(memory);
+    assert!(!storage.sniffs_mime());
+    let stored = storage
+        .store("file", None, "text/plain", chunks())
+        .await
+        .expect("boxed store should succeed");
+    assert_eq!(stored.size, 5);
+
+    let _ = tokio::fs::remove_dir_all(&root).await;
+}
+
+#[derive(Debug, Default)]
+struct MetaStorage {
+    seen: std::sync::Mutex<Vec<Option<HashMap<String, String>>>>,
+}
+
+#[async_trait::async_trait]
+impl StorageEngine for MetaStorage {
+    type Output = ();
+    type Error = StorageError;
+
+    async fn store(
+        &self,
+        _field_name: &str,
+        _file_name: Option<&str>,
+        _content_type: &str,
+        _stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.seen.lock().expect("lock").push(None);
+        Ok(())
+    }
+
+    async fn store_full(
+        &self,
+        _field_name: &str,
+        _file_name: Option<&str>,
+        _content_type: &str,
+        _size_hint: Option<u64>,
+        extra: &HashMap<String, String>,
+        _stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.seen.lock().expect("lock").push(Some(extra.clone()));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn store_with_meta_forwards_extra_metadata() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n",
+        "X-Upload-Session: abc123\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"file\"; filename=\"b.txt\"\r\n",
+        "\r\n",
+        "world\r\n",
+        "--BOUND--\r\n"
+    );
+    let multer = Multer::new(MetaStorage::default());
+    let mut multipart = multer
+        .multipart_from_boundary(
+            "BOUND",
+            stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+                body.as_bytes(),
+            ))]),
+        )
+        .expect("multipart should initialize");
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("first part should parse")
+        .expect("first part expected");
+    let session = part
+        .headers()
+        .get("x-upload-session")
+        .and_then(|value| value.to_str().ok())
+        .expect("session header")
+        .to_owned();
+    multer
+        .store_with_meta(part, HashMap::from([("session".to_owned(), session)]))
+        .await
+        .expect("store with meta should succeed");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("second part should parse")
+        .expect("second part expected");
+    multer.store(part).await.expect("store should succeed");
+
+    let seen = multer.storage().seen.lock().expect("lock").clone();
+    assert_eq!(
+        seen,
+        vec![
+            Some(HashMap::from([("session".to_owned(), "abc123".to_owned())])),
+            None
+        ]
+    );
+}
+
+#[tokio::test]
+async fn store_full_defaults_to_store_sized() {
+    let storage = BoxedStorage::new(MemoryStorage::new());
+    let extra = HashMap::from([("session".to_owned(), "abc123".to_owned())]);
+
+    let stored = storage
+        .store_full(
+            "file",
+            Some("a.txt"),
+            "text/plain",
+            Some(5),
+            &extra,
+            chunks(),
+        )
+        .await
+        .expect("default store_full should delegate to store_sized");
+
+    assert_eq!(stored.size, 5);
+    assert_eq!(stored.file_name.as_deref(), Some("a.txt"));
+
+    let capped = MemoryStorage::new().with_global_cap(4);
+    let err = capped
+        .store_full(
+            "file",
+            Some("a.txt"),
+            "text/plain",
+            Some(64),
+            &extra,
+            chunks(),
+        )
+        .await
+        .expect_err("the size hint should reach the global cap precheck");
+    assert!(
+        matches!(
+            err,
+            StorageError::GlobalCapacityExceeded { incoming: 64, .. }
+        ),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn wrapper_engines_forward_store_full() {
+    let extra = HashMap::from([("session".to_owned(), "abc123".to_owned())]);
+    let expected = vec![Some(extra.clone())];
+
+    let tee = TeeStorageEngine::new(MetaStorage::default(), MetaStorage::default());
+    tee.store_full("file", Some("a.txt"), "text/plain", None, &extra, chunks())
+        .await
+        .expect("tee store_full should succeed");
+    assert_eq!(*tee.primary().seen.lock().expect("lock"), expected);
+    assert_eq!(*tee.secondary().seen.lock().expect("lock"), expected);
+
+    let hybrid = HybridStorage::new(MetaStorage::default(), MetaStorage::default());
+    hybrid
+        .store_full("file", Some("a.txt"), "text/plain", None, &extra, chunks())
+        .await
+        .expect("hybrid store_full should succeed");
+    assert_eq!(*hybrid.primary().seen.lock().expect("lock"), expected);
+    assert_eq!(*hybrid.cache().seen.lock().expect("lock"), expected);
+}
+
+fn chunks() -> BoxStream<'static, Result<Bytes, MulterError>> {
+    Box::pin(stream::iter([
+        Ok(Bytes::from_static(b"hel")),
+        Ok(Bytes::from_static(b"lo")),
+    ]))
+}
+
+#[test]
+fn io_errors_convert_into_storage_errors() {
+    let err: MulterError = std::io::Error::new(std::io::ErrorKind::NotFound, "test").into();
+    assert!(matches!(
+        err,
+        MulterError::Storage(StorageError::Message { message }) if message == "test"
+    ));
+
+    let storage_err: StorageError =
+        std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied").into();
+    assert_eq!(storage_err, StorageError::new("denied"));
+}
@@ -1,12 +1,24 @@
 #![allow(missing_docs)]
 
+use std::collections::HashSet;
+use std::io;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+use std::time::Duration;
 
 use bytes::Bytes;
-use futures::{channel::mpsc, stream, SinkExt};
-use multigear::storage::disk::sanitize_filename;
-use multigear::{DiskStorage, FilenameStrategy, Multer, MulterError, Multipart};
+use chrono::Timelike;
+use futures::{channel::mpsc, stream, FutureExt, SinkExt, StreamExt, TryStreamExt};
+use multigear::storage::disk::{
+    exists, list_directory, list_directory_filtered, sanitize_filename,
+};
+use multigear::{
+    CollisionPolicy, CreateMode, DigestAlgorithm, DiskStorage, FilenameStrategy, Multer,
+    MulterError, Multipart, MultipartBodyBuilder, SelectedField, StorageEngine, StorageError,
+};
 use uuid::Uuid;
 
 type ObservedFileMeta = Option<(String, Option<String>, String)>;
@@ -83,12 +95,490 @@ async fn random_strategy_generates_distinct_paths() {
     cleanup(root).await;
 }
 
+#[tokio::test]
+async fn subdirectory_options_organize_files_by_field_and_date() {
+    let root = temp_root();
+    let store = |storage: DiskStorage, field: &'static str| async move {
+        storage
+            .store(
+                field,
+                Some("a.txt"),
+                "text/plain",
+                Box::pin(stream::iter([Ok(Bytes::from_static(b"data"))])),
+            )
+            .await
+            .expect("store should succeed")
+    };
+    let today = chrono::Utc::now().format("%Y/%m/%d").to_string();
+
+    let by_date = DiskStorage::builder()
+        .destination(&root)
+        .subdirectory_by_date("%Y/%m/%d")
+        .build()
+        .expect("builder should succeed");
+    let stored = store(by_date, "docs").await;
+    let path = stored.path.expect("disk storage should return path");
+    assert_eq!(path.parent(), Some(root.join(&today).as_path()));
+    assert_eq!(stored.storage_key, path.to_string_lossy());
+
+    let by_field = DiskStorage::builder()
+        .destination(&root)
+        .subdirectory_by_field(true)
+        .build()
+        .expect("builder should succeed");
+    let path = store(by_field, "../avatar").await.path.expect("path");
+    assert_eq!(path.parent(), Some(root.join("avatar").as_path()));
+
+    let combined = DiskStorage::builder()
+        .destination(&root)
+        .subdirectory_by_field(true)
+        .subdirectory_by_date("%Y/%m/%d")
+        .build()
+        .expect("builder should succeed");
+    let path = store(combined, "photos").await.path.expect("path");
+    assert_eq!(
+        path.parent(),
+        Some(root.join("photos").join(&today).as_path())
+    );
+    assert_eq!(tokio::fs::read(&path).await.expect("read file"), b"data");
+
+    let err = DiskStorage::builder()
+        .destination(&root)
+        .subdirectory_by_date("%Y/%Q")
+        .build()
+        .expect_err("invalid date format must be rejected");
+    assert!(err.to_string().contains("date format"));
+
+    cleanup(root).await;
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[tokio::test]
+async fn compute_digest_matches_independent_hash_of_written_bytes() {
+    use sha2::Digest as _;
+
+    let root = temp_root();
+    for (algorithm, use_copy_buf) in [
+        (DigestAlgorithm::Sha256, false),
+        (DigestAlgorithm::Sha256, true),
+        (DigestAlgorithm::Md5, false),
+    ] {
+        let storage = DiskStorage::builder()
+            .destination(&root)
+            .filename(FilenameStrategy::Random)
+            .compute_digest(algorithm)
+            .use_copy_buf(use_copy_buf)
+            .build()
+            .expect("builder should succeed");
+        let stored = storage
+            .store(
+                "upload",
+                Some("data.bin"),
+                "application/octet-stream",
+                Box::pin(stream::iter([
+                    Ok(Bytes::from_static(b"hello ")),
+                    Ok(Bytes::from_static(b"digest ")),
+                    Ok(Bytes::from_static(b"world")),
+                ])),
+            )
+            .await
+            .expect("store should succeed");
+        let path = stored
+            .path
+            .clone()
+            .expect("disk storage should return path");
+        let written = tokio::fs::read(&path).await.expect("read file");
+        assert_eq!(written, b"hello digest world");
+
+        let expected = match algorithm {
+            DigestAlgorithm::Sha256 => hex(&sha2::Sha256::digest(&written)),
+            DigestAlgorithm::Md5 => hex(&md5::Md5::digest(&written)),
+            DigestAlgorithm::None => unreachable!(),
+        };
+        assert_eq!(stored.digest.as_deref(), Some(expected.as_str()));
+        assert_eq!(stored.storage_key, path.to_string_lossy());
+    }
+
+    let plain = DiskStorage::builder()
+        .destination(&root)
+        .build()
+        .expect("builder should succeed");
+    let stored = store_single(&plain, "no digest")
+        .await
+        .expect("store should succeed");
+    assert!(stored.digest.is_none());
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn verify_write_passes_for_intact_files() {
+    let root = temp_root();
+    for (algorithm, atomic) in [(DigestAlgorithm::None, false), (DigestAlgorithm::Md5, true)] {
+        let storage = DiskStorage::builder()
+            .destination(&root)
+            .compute_digest(algorithm)
+            .atomic(atomic)
+            .verify_write(true)
+            .build()
+            .expect("builder should succeed");
+
+        let stored = store_single(&storage, "verified bytes")
+            .await
+            .expect("verified store should succeed");
+
+        let path = stored.path.expect("disk path");
+        assert_eq!(
+            tokio::fs::read(&path).await.expect("read file"),
+            b"verified bytes"
+        );
+        assert_eq!(
+            stored.digest.is_some(),
+            algorithm != DigestAlgorithm::None,
+            "verification must not leak a digest that was not requested"
+        );
+    }
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn verify_write_rejects_and_removes_file_corrupted_during_store() {
+    for atomic in [false, true] {
+        let root = temp_root();
+        let storage = DiskStorage::builder()
+            .destination(&root)
+            .filename(FilenameStrategy::Keep)
+            .atomic(atomic)
+            .compute_digest(DigestAlgorithm::Sha256)
+            .verify_write(true)
+            .build()
+            .expect("builder should succeed");
+        // Before the last chunk is handed out, append bytes behind the storage's
+        // back to whatever file it is writing, so the read-back cannot match.
+        let corrupt_root = root.clone();
+        let chunks = [Bytes::from(vec![b'a'; 4096]), Bytes::from(vec![b'b'; 64])];
+        let input = stream::iter(chunks).enumerate().map(move |(index, chunk)| {
+            if index == 1 {
+                append_to_files_in(&corrupt_root, &[b'x'; 16 * 1024]);
+            }
+            Ok::<Bytes, MulterError>(chunk)
+        });
+
+        let err = storage
+            .store("upload", Some("file.txt"), "text/plain", Box::pin(input))
+            .await
+            .expect_err("corrupted file must fail verification");
+        match err {
+            StorageError::WriteVerificationFailed { expected, actual } => {
+                assert_ne!(actual, expected, "atomic: {atomic}");
+            }
+            other => panic!("unexpected error (atomic: {atomic}): {other:?}"),
+        }
+        assert!(
+            directory_entries(&root).await.is_empty(),
+            "no output or temporary file may remain (atomic: {atomic})"
+        );
+
+        cleanup(root).await;
+    }
+}
+
+fn append_to_files_in(root: &std::path::Path, bytes: &[u8]) {
+    use std::io::Write as _;
+
+    for entry in std::fs::read_dir(root).expect("read root") {
+        let path = entry.expect("directory entry").path();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(bytes))
+            .expect("append to file");
+    }
+}
+
+#[tokio::test]
+async fn storage_key_from_digest_is_content_addressed() {
+    use sha2::Digest as _;
+
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::Random)
+        .compute_digest(DigestAlgorithm::Sha256)
+        .storage_key_from_digest(true)
+        .build()
+        .expect("builder should succeed");
+
+    let first = store_single(&storage, "same bytes")
+        .await
+        .expect("store should succeed");
+    let second = store_single(&storage, "same bytes")
+        .await
+        .expect("store should succeed");
+    let expected = hex(&sha2::Sha256::digest(b"same bytes"));
+    assert_eq!(first.storage_key, expected);
+    assert_eq!(second.storage_key, expected);
+    assert_ne!(first.path, second.path);
+
+    let err = DiskStorage::builder()
+        .destination(&root)
+        .storage_key_from_digest(true)
+        .build()
+        .expect_err("key from digest requires an algorithm");
+    assert!(err.to_string().contains("digest"));
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn keep_extension_strategy_randomizes_stem() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::KeepExtension)
+        .build()
+        .expect("builder should succeed");
+
+    let cases = [
+        (Some("archive.tar.gz"), Some("gz")),
+        (Some("photo.PNG"), Some("PNG")),
+        (Some("README"), None),
+        (Some(".bashrc"), None),
+        (Some("trailing."), None),
+        (Some("weird.ex/t"), None),
+        (None, None),
+    ];
+    for (file_name, expected) in cases {
+        let stored = storage
+            .store(
+                "upload",
+                file_name,
+                "application/octet-stream",
+                Box::pin(stream::iter([Ok(Bytes::from_static(b"data"))])),
+            )
+            .await
+            .expect("store should succeed");
+        let path = stored.path.expect("disk storage should return path");
+        let output = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("utf-8 file name");
+
+        assert_eq!(
+            path.extension().and_then(|ext| ext.to_str()),
+            expected,
+            "{file_name:?} -> {output}"
+        );
+        assert!(!output.contains("archive") && !output.contains("README"));
+        assert!(!output.contains(".tar."));
+    }
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn timestamp_strategy_uses_utc_time_stem_and_keeps_extension() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::Timestamp {
+            format: "%Y%m%d%H%M%S%3f".to_owned(),
+        })
+        .build()
+        .expect("builder should succeed");
+
+    let before = chrono::Utc::now().naive_utc();
+    let mut stored = Vec::new();
+    for file_name in [Some("report.PDF"), Some("README"), None] {
+        let file = storage
+            .store(
+                "upload",
+                file_name,
+                "application/octet-stream",
+                Box::pin(stream::iter([Ok(Bytes::from_static(b"data"))])),
+            )
+            .await
+            .expect("store should succeed");
+        stored.push(file.path.expect("disk storage should return path"));
+    }
+    let after = chrono::Utc::now().naive_utc();
+
+    let extensions = stored
+        .iter()
+        .map(|path| path.extension().and_then(|ext| ext.to_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(extensions, [Some("PDF"), None, None]);
+    for path in &stored {
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .expect("utf-8 stem");
+        let timestamp = stem.split('-').next().expect("timestamp prefix");
+        assert_eq!(timestamp.len(), 17, "{stem}");
+        let parsed = chrono::NaiveDateTime::parse_from_str(timestamp, "%Y%m%d%H%M%S%3f")
+            .expect("stem should parse as a timestamp");
+        assert!(parsed >= before.with_nanosecond(0).expect("valid time") && parsed <= after);
+    }
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn timestamp_strategy_collisions_get_suffix_and_formats_are_validated() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::Timestamp {
+            format: "%Y".to_owned(),
+        })
+        .build()
+        .expect("builder should succeed");
+
+    let first = store_single(&storage, "one").await.expect("first store");
+    let second = store_single(&storage, "two").await.expect("second store");
+    let first = first.path.expect("first path");
+    let second = second.path.expect("second path");
+    assert_ne!(first, second);
+    let year = chrono::Utc::now().format("%Y").to_string();
+    assert!(second
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with(&format!("{year}-"))));
+
+    for format in ["", "%Q"] {
+        let err = DiskStorage::builder()
+            .destination(&root)
+            .filename(FilenameStrategy::Timestamp {
+                format: format.to_owned(),
+            })
+            .build()
+            .expect_err("invalid format must fail");
+        assert!(
+            err.to_string().contains("timestamp filename format"),
+            "{err}"
+        );
+    }
+
+    cleanup(root).await;
+}
+
+fn keep_storage(root: &std::path::Path, policy: CollisionPolicy) -> DiskStorage {
+    DiskStorage::builder()
+        .destination(root)
+        .filename(FilenameStrategy::Keep)
+        .on_collision(policy)
+        .build()
+        .expect("builder should succeed")
+}
+
+#[tokio::test]
+async fn suffix_collision_policy_keeps_both_files() {
+    let root = temp_root();
+    let storage = keep_storage(&root, CollisionPolicy::Suffix);
+
+    let first = store_single(&storage, "one").await.expect("first store");
+    let second = store_single(&storage, "two").await.expect("second store");
+    let first = first.path.expect("first path");
+    let second = second.path.expect("second path");
+    assert_eq!(first, root.join("file.txt"));
+    assert_ne!(first, second);
+    assert_eq!(
+        tokio::fs::read_to_string(&first).await.expect("first file"),
+        "one"
+    );
+    assert_eq!(
+        tokio::fs::read_to_string(&second)
+            .await
+            .expect("second file"),
+        "two"
+    );
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn create_failure_names_the_output_path() {
+    let root = temp_root();
+    let blocked = root.join("file.txt");
+    tokio::fs::create_dir_all(&blocked)
+        .await
+        .expect("create blocking directory");
+    let storage = keep_storage(&root, CollisionPolicy::Overwrite);
+
+    let err = store_single(&storage, "payload")
+        .await
+        .expect_err("a directory cannot be opened as a file");
+    let message = err.to_string();
+    assert!(
+        message.contains("failed to create output file"),
+        "{message}"
+    );
+    assert!(
+        message.contains(&blocked.display().to_string()),
+        "{message}"
+    );
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn overwrite_collision_policy_replaces_existing_file() {
+    let root = temp_root();
+    let storage = keep_storage(&root, CollisionPolicy::Overwrite);
+
+    store_single(&storage, "a longer first payload")
+        .await
+        .expect("first store");
+    let second = store_single(&storage, "two").await.expect("second store");
+    let path = second.path.expect("second path");
+    assert_eq!(path, root.join("file.txt"));
+    assert_eq!(tokio::fs::read_to_string(&path).await.expect("file"), "two");
+    assert_eq!(directory_entries(&root).await.len(), 1);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn error_collision_policy_rejects_existing_file() {
+    let root = temp_root();
+    let storage = keep_storage(&root, CollisionPolicy::Error);
+
+    store_single(&storage, "one").await.expect("first store");
+    let err = storage
+        .store(
+            "upload",
+            Some("file.txt"),
+            "text/plain",
+            Box::pin(stream::iter([Ok(Bytes::from_static(b"two"))])),
+        )
+        .await
+        .expect_err("collision must fail");
+    assert_eq!(
+        err,
+        StorageError::FileAlreadyExists {
+            path: root.join("file.txt")
+        }
+    );
+    assert_eq!(
+        tokio::fs::read_to_string(root.join("file.txt"))
+            .await
+            .expect("file"),
+        "one"
+    );
+
+    cleanup(root).await;
+}
+
 #[tokio::test]
 async fn custom_strategy_applies_transform() {
     let root = temp_root();
     let storage = DiskStorage::builder()
         .destination(&root)
-        .custom_filename(|incoming| format!("prefix-{incoming}"))
+        .filename_transform(|incoming| format!("prefix-{incoming}"))
         .build()
         .expect("builder should succeed");
     let multer = Multer::new(storage);
@@ -114,6 +604,98 @@ async fn custom_strategy_applies_transform() {
     cleanup(root).await;
 }
 
+#[tokio::test]
+async fn filename_prefix_and_suffix_wrap_sanitized_name() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::Keep)
+        .filename_prefix("tenant-7_")
+        .filename_suffix("-v2")
+        .build()
+        .expect("builder should succeed");
+    let multer = Multer::new(storage);
+
+    let body = multipart_body(&[
+        ("doc", "my report.pdf", "application/pdf", "payload"),
+        ("doc", "README", "text/plain", "readme"),
+    ]);
+    let mut multipart =
+        Multipart::new("BOUND", bytes_stream(body)).expect("multipart should initialize");
+    let mut names = Vec::new();
+    while let Some(part) = multipart.next_part().await.expect("part should parse") {
+        let stored = multer.store(part).await.expect("store should succeed");
+        let path = stored.path.expect("disk path");
+        names.push(path.file_name().expect("file name").to_owned());
+    }
+
+    assert_eq!(names, ["tenant-7_my_report-v2.pdf", "tenant-7_README-v2"]);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn filename_prefix_applies_alone_and_is_sanitized() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::Keep)
+        .filename_prefix("../up load ")
+        .build()
+        .expect("builder should succeed");
+
+    let stored = store_single(&storage, "payload")
+        .await
+        .expect("store should succeed");
+    let path = stored.path.expect("disk path");
+
+    assert_eq!(path.parent(), Some(root.as_path()));
+    assert_eq!(
+        path.file_name().and_then(|name| name.to_str()),
+        Some("up_load_file.txt")
+    );
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn async_custom_strategy_applies_transform_with_metadata() {
+    let root = temp_root();
+    let counter = Arc::new(AtomicU64::new(0));
+    let transform_counter = Arc::clone(&counter);
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .custom_filename_async(move |incoming, meta| {
+            let counter = Arc::clone(&transform_counter);
+            let field_name = meta.field_name.clone();
+            async move {
+                let index = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                format!("{field_name}-{index}-{incoming}")
+            }
+            .boxed()
+        })
+        .build()
+        .expect("builder should succeed");
+
+    let mut names = Vec::new();
+    for payload in ["one", "two"] {
+        let stored = store_single(&storage, payload).await.expect("store");
+        let name = stored
+            .path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .and_then(|value| value.to_str())
+            .expect("valid filename")
+            .to_owned();
+        names.push(name);
+    }
+
+    assert_eq!(names, vec!["upload-1-file.txt", "upload-2-file.txt"]);
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+    cleanup(root).await;
+}
+
 #[tokio::test]
 async fn disk_filter_can_reject_files_before_write() {
     let root = temp_root();
@@ -146,6 +728,63 @@ async fn disk_filter_can_reject_files_before_write() {
     cleanup(root).await;
 }
 
+#[tokio::test]
+async fn async_filter_composes_with_sync_filter() {
+    let root = temp_root();
+    let scanned = Arc::new(Mutex::new(Vec::new()));
+    let scanner_log = Arc::clone(&scanned);
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::Keep)
+        .filter(|meta| meta.file_name.as_deref() != Some("blocked.txt"))
+        .async_filter(move |meta| {
+            let scanner_log = Arc::clone(&scanner_log);
+            async move {
+                // Mock virus scanner: yields like a network call, then flags "eicar" names.
+                tokio::task::yield_now().await;
+                let file_name = meta.file_name.unwrap_or_default();
+                let clean = !file_name.contains("eicar");
+                scanner_log
+                    .lock()
+                    .expect("lock should succeed")
+                    .push(file_name);
+                clean
+            }
+        })
+        .build()
+        .expect("builder should succeed");
+
+    let store = |file_name: &'static str| {
+        storage.store(
+            "upload",
+            Some(file_name),
+            "text/plain",
+            Box::pin(stream::iter([Ok(Bytes::from_static(b"data"))])),
+        )
+    };
+
+    let stored = store("clean.txt").await.expect("clean file should store");
+    assert!(stored.path.expect("path").exists());
+
+    let err = store("eicar.txt")
+        .await
+        .expect_err("async filter should reject file");
+    assert!(err.to_string().contains("async filter rejected"));
+    assert!(!root.join("eicar.txt").exists());
+
+    let err = store("blocked.txt")
+        .await
+        .expect_err("sync filter should reject file");
+    assert!(err.to_string().contains("filter rejected"));
+    assert_eq!(
+        *scanned.lock().expect("lock should succeed"),
+        ["clean.txt", "eicar.txt"],
+        "sync filter runs first and short-circuits the scanner"
+    );
+
+    cleanup(root).await;
+}
+
 #[tokio::test]
 async fn disk_filter_receives_core_file_metadata() {
     let root = temp_root();
@@ -198,10 +837,677 @@ async fn disk_filter_receives_core_file_metadata() {
     cleanup(root).await;
 }
 
-#[test]
-fn sanitize_filename_rejects_traversal_and_null_bytes() {
-    let traversal = sanitize_filename("../../etc/passwd");
-    assert!(!traversal.contains(".."));
+#[tokio::test]
+async fn create_mode_always_create_creates_missing_and_reuses_existing_root() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .with_create_mode(CreateMode::AlwaysCreate)
+        .build()
+        .expect("builder should succeed");
+
+    let first = store_single(&storage, "one")
+        .await
+        .expect("missing root is created");
+    assert!(first.path.expect("path").starts_with(&root));
+    let second = store_single(&storage, "two")
+        .await
+        .expect("existing root is reused");
+    assert!(second.path.expect("path").starts_with(&root));
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn create_mode_must_exist_rejects_missing_root() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .with_create_mode(CreateMode::MustExist)
+        .build()
+        .expect("builder should succeed");
+
+    let err = store_single(&storage, "one")
+        .await
+        .expect_err("missing root should be rejected");
+    assert!(err.to_string().contains("does not exist"));
+    assert!(!tokio::fs::try_exists(&root)
+        .await
+        .expect("try_exists should succeed"));
+
+    tokio::fs::create_dir_all(&root).await.expect("create root");
+    let stored = store_single(&storage, "two")
+        .await
+        .expect("existing root should be accepted");
+    assert_eq!(stored.size, 3);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn create_mode_create_if_absent_creates_root_once() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .with_create_mode(CreateMode::CreateIfAbsent)
+        .build()
+        .expect("builder should succeed");
+
+    store_single(&storage, "one")
+        .await
+        .expect("missing root is created");
+    assert!(tokio::fs::try_exists(&root)
+        .await
+        .expect("try_exists should succeed"));
+    store_single(&storage, "two")
+        .await
+        .expect("newly created root is reused");
+
+    let mut entries = tokio::fs::read_dir(&root).await.expect("read root");
+    let mut count = 0;
+    while entries.next_entry().await.expect("next entry").is_some() {
+        count += 1;
+    }
+    assert_eq!(count, 2);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn copy_buf_and_chunked_writes_report_identical_byte_counts() {
+    for use_copy_buf in [true, false] {
+        let root = temp_root();
+        let storage = DiskStorage::builder()
+            .destination(&root)
+            .use_copy_buf(use_copy_buf)
+            .build()
+            .expect("builder should succeed");
+        let multer = Multer::new(storage);
+
+        let mut chunks = vec![Ok::<Bytes, MulterError>(Bytes::from_static(
+            b"--BOUND\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"frag.bin\"\r\n\r\n",
+        ))];
+        for index in 0..1000u32 {
+            chunks.push(Ok(Bytes::from(vec![(index % 251) as u8; 7])));
+        }
+        chunks.push(Ok(Bytes::from_static(b"\r\n--BOUND--\r\n")));
+
+        let mut multipart =
+            Multipart::new("BOUND", stream::iter(chunks)).expect("multipart should initialize");
+        let part = multipart
+            .next_part()
+            .await
+            .expect("part should parse")
+            .expect("part expected");
+        let stored = multer.store(part).await.expect("store should succeed");
+        let path = stored.path.expect("disk storage should return a path");
+
+        assert_eq!(stored.size, 7000, "use_copy_buf = {use_copy_buf}");
+        let written = tokio::fs::read(&path).await.expect("read file");
+        assert_eq!(written.len(), 7000);
+        assert!(written
+            .chunks(7)
+            .enumerate()
+            .all(|(index, chunk)| chunk.iter().all(|byte| *byte == (index % 251) as u8)));
+
+        cleanup(root).await;
+    }
+}
+
+#[tokio::test]
+async fn copy_buf_surfaces_stream_errors_as_read_failures() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .use_copy_buf(true)
+        .build()
+        .expect("builder should succeed");
+    let multer = Multer::new(storage);
+
+    let chunks = vec![
+        Ok::<Bytes, MulterError>(Bytes::from_static(
+            b"--BOUND\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"a.bin\"\r\n\r\n",
+        )),
+        Ok(Bytes::from(vec![b'a'; 128])),
+        Err(MulterError::IncompleteStream),
+    ];
+    let mut multipart =
+        Multipart::new("BOUND", stream::iter(chunks)).expect("multipart should initialize");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+
+    let err = multer
+        .store(part)
+        .await
+        .expect_err("stream failure should abort store");
+    assert!(matches!(err, MulterError::IncompleteStream), "{err}");
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn write_retries_configuration_stores_files() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .write_retries(3)
+        .retry_initial_delay(Duration::from_millis(5))
+        .build()
+        .expect("builder should succeed");
+
+    let stored = store_single(&storage, "retry").await.expect("store");
+    let path = stored.path.expect("disk storage should return a path");
+    assert_eq!(tokio::fs::read(&path).await.expect("read file"), b"retry");
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn lists_directory_entries_and_filters_by_extension() {
+    let root = temp_root();
+    tokio::fs::create_dir_all(&root).await.expect("create root");
+    for name in ["a.txt", "b.TXT", "c.png"] {
+        tokio::fs::write(root.join(name), b"data")
+            .await
+            .expect("write file");
+    }
+
+    assert!(exists(&root).await.expect("exists"));
+    assert!(!exists(root.join("missing.txt")).await.expect("exists"));
+
+    let mut all = list_directory(&root)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("listing should succeed");
+    all.sort();
+    assert_eq!(
+        all,
+        vec![root.join("a.txt"), root.join("b.TXT"), root.join("c.png")]
+    );
+
+    let mut text = list_directory_filtered(&root, ".txt")
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("filtered listing should succeed");
+    text.sort();
+    assert_eq!(text, vec![root.join("a.txt"), root.join("b.TXT")]);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn listing_missing_directory_yields_single_error() {
+    let entries = list_directory(temp_root()).collect::<Vec<_>>().await;
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].is_err());
+}
+
+#[tokio::test]
+async fn max_total_files_rejects_stores_beyond_the_limit() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .max_total_files(2)
+        .build()
+        .expect("builder should succeed");
+
+    store_single(&storage, "one").await.expect("first store");
+    store_single(&storage.clone(), "two")
+        .await
+        .expect("clones share the limit");
+    assert_eq!(storage.files_stored(), 2);
+
+    let err = store_single(&storage, "three")
+        .await
+        .expect_err("third store must fail");
+    assert!(
+        err.to_string().contains("total file count limit exceeded"),
+        "{err}"
+    );
+    assert_eq!(storage.files_stored(), 2);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn max_concurrent_writes_bounds_open_files() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .max_concurrent_writes(5)
+        .build()
+        .expect("builder should succeed");
+    let open = Arc::new(AtomicU64::new(0));
+    let peak = Arc::new(AtomicU64::new(0));
+
+    let tasks = (0..50)
+        .map(|index| {
+            let storage = storage.clone();
+            let open = Arc::clone(&open);
+            let peak = Arc::clone(&peak);
+            tokio::spawn(async move {
+                // The body is first polled once the output file is open and
+                // ends right before it is closed.
+                let body = stream::unfold(0, move |step| {
+                    let open = Arc::clone(&open);
+                    let peak = Arc::clone(&peak);
+                    async move {
+                        match step {
+                            0 => {
+                                let now = open.fetch_add(1, Ordering::SeqCst) + 1;
+                                peak.fetch_max(now, Ordering::SeqCst);
+                            }
+                            3 => {
+                                open.fetch_sub(1, Ordering::SeqCst);
+                                return None;
+                            }
+                            _ => {}
+                        }
+                        tokio::time::sleep(Duration::from_millis(2)).await;
+                        Some((Ok(Bytes::from_static(b"chunk")), step + 1))
+                    }
+                });
+                storage
+                    .store(
+                        "upload",
+                        Some(&format!("file-{index}.bin")),
+                        "application/octet-stream",
+                        Box::pin(body.fuse()),
+                    )
+                    .await
+            })
+        })
+        .collect::<Vec<_>>();
+    for task in tasks {
+        let stored = task
+            .await
+            .expect("task should join")
+            .expect("store should succeed");
+        assert_eq!(stored.size, 15);
+    }
+
+    assert_eq!(peak.load(Ordering::SeqCst), 5);
+    assert_eq!(open.load(Ordering::SeqCst), 0);
+    assert_eq!(storage.files_stored(), 50);
+    assert!(DiskStorage::builder()
+        .destination(&root)
+        .max_concurrent_writes(0)
+        .build()
+        .is_err());
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn check_disk_space_rejects_files_larger_than_free_space() {
+    let root = temp_root();
+    let probes = Arc::new(AtomicU64::new(0));
+    let observed = Arc::clone(&probes);
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .check_disk_space(true)
+        .disk_space_probe(move || {
+            observed.fetch_add(1, Ordering::SeqCst);
+            10
+        })
+        .build()
+        .expect("builder should succeed");
+    let store = |size_hint| {
+        storage.store_sized(
+            "upload",
+            Some("a.bin"),
+            "application/octet-stream",
+            size_hint,
+            Box::pin(stream::iter([Ok(Bytes::from_static(b"data"))])),
+        )
+    };
+
+    let err = store(Some(100))
+        .await
+        .expect_err("oversized file must fail");
+    assert_eq!(
+        err,
+        StorageError::InsufficientDiskSpace {
+            available: 10,
+            required: 100,
+        }
+    );
+    assert!(directory_entries(&root).await.is_empty());
+    assert_eq!(storage.files_stored(), 0);
+
+    store(Some(4)).await.expect("file within free space");
+    store(None)
+        .await
+        .expect("missing size hint skips the check");
+    assert_eq!(probes.load(Ordering::SeqCst), 2);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn disk_space_probe_is_ignored_unless_check_is_enabled() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .disk_space_probe(|| 0)
+        .build()
+        .expect("builder should succeed");
+
+    storage
+        .store_sized(
+            "upload",
+            Some("a.bin"),
+            "application/octet-stream",
+            Some(4),
+            Box::pin(stream::iter([Ok(Bytes::from_static(b"data"))])),
+        )
+        .await
+        .expect("store should succeed");
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn check_disk_space_queries_the_filesystem_by_default() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .check_disk_space(true)
+        .build()
+        .expect("builder should succeed");
+    let store = |size_hint| {
+        storage.store_sized(
+            "upload",
+            Some("a.bin"),
+            "application/octet-stream",
+            Some(size_hint),
+            Box::pin(stream::iter([Ok(Bytes::from_static(b"data"))])),
+        )
+    };
+
+    store(4).await.expect("small file should fit");
+    let err = store(u64::MAX).await.expect_err("huge file cannot fit");
+    assert!(
+        matches!(err, StorageError::InsufficientDiskSpace { required, .. } if required == u64::MAX),
+        "{err}"
+    );
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn on_progress_reports_running_byte_count() {
+    let root = temp_root();
+    let written = Arc::new(AtomicU64::new(0));
+    let observed = Arc::clone(&written);
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .on_progress(move |bytes, _total| {
+            observed.store(bytes, Ordering::SeqCst);
+        })
+        .build()
+        .expect("builder should succeed");
+
+    let stored = store_single(&storage, "progress").await.expect("store");
+    assert_eq!(stored.size, 8);
+    assert_eq!(written.load(Ordering::SeqCst), 8);
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn atomic_mode_leaves_no_partial_file_on_stream_error() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::Keep)
+        .atomic(true)
+        .build()
+        .expect("builder should succeed");
+
+    let chunks = stream::iter([
+        Ok(Bytes::from_static(b"partial")),
+        Err(MulterError::IncompleteStream),
+    ]);
+    storage
+        .store("upload", Some("report.txt"), "text/plain", Box::pin(chunks))
+        .await
+        .expect_err("stream error must fail the store");
+
+    assert!(!root.join("report.txt").exists());
+    assert_eq!(directory_entries(&root).await, Vec::<String>::new());
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn atomic_mode_cleans_up_cancelled_stores_and_renames_on_success() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::Keep)
+        .atomic(true)
+        .build()
+        .expect("builder should succeed");
+
+    let (mut tx, rx) = mpsc::channel::<Result<Bytes, MulterError>>(1);
+    tx.send(Ok(Bytes::from_static(b"first chunk")))
+        .await
+        .expect("send chunk");
+    let cancelled = tokio::time::timeout(
+        Duration::from_millis(100),
+        storage.store("upload", Some("slow.txt"), "text/plain", Box::pin(rx)),
+    )
+    .await;
+    assert!(cancelled.is_err(), "store should still be waiting for data");
+    drop(tx);
+    assert_eq!(directory_entries(&root).await, Vec::<String>::new());
+
+    let stored = store_single(&storage, "complete").await.expect("store");
+    let path = stored.path.expect("disk path should be present");
+    assert_eq!(path, root.join("file.txt"));
+    assert_eq!(
+        tokio::fs::read(&path).await.expect("read file"),
+        b"complete"
+    );
+    assert_eq!(directory_entries(&root).await, ["file.txt"]);
+
+    cleanup(root).await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn applies_unix_file_and_directory_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = |path: &std::path::Path| {
+        std::fs::metadata(path)
+            .expect("metadata should read")
+            .permissions()
+            .mode()
+            & 0o777
+    };
+
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .build()
+        .expect("builder should succeed");
+    let stored = store_single(&storage, "default").await.expect("store");
+    let reference = root.join("umask-reference");
+    std::fs::write(&reference, b"").expect("write reference file");
+    assert_eq!(
+        mode(stored.path.as_deref().expect("path")),
+        mode(&reference)
+    );
+    cleanup(root).await;
+
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .subdirectory_by_field(true)
+        .file_permissions(0o600)
+        .dir_permissions(0o700)
+        .build()
+        .expect("builder should succeed");
+    let stored = store_single(&storage, "private").await.expect("store");
+    let path = stored.path.expect("path");
+    assert_eq!(mode(&path), 0o600);
+    assert_eq!(mode(path.parent().expect("field directory")), 0o700);
+    assert_eq!(mode(&root), 0o700);
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn list_stored_files_walks_root_oldest_first() {
+    let root = temp_root();
+    tokio::fs::create_dir_all(root.join("2024/05"))
+        .await
+        .expect("create nested dirs");
+    let files = [
+        ("2024/05/photo.png", "png", 30),
+        ("notes.txt", "hello", 10),
+        ("blob", "??", 20),
+    ];
+    let epoch = std::time::SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    for (relative, contents, offset) in files {
+        let path = root.join(relative);
+        std::fs::write(&path, contents).expect("write file");
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(epoch + Duration::from_secs(offset)))
+            .expect("set mtime");
+    }
+
+    let listed = DiskStorage::list_stored_files(&root)
+        .await
+        .expect("listing should succeed");
+    let summary: Vec<_> = listed
+        .iter()
+        .map(|file| {
+            (
+                file.file_name.as_deref().expect("basename"),
+                file.content_type.essence_str(),
+                file.size,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("notes.txt", "text/plain", 5),
+            ("blob", "application/octet-stream", 2),
+            ("photo.png", "image/png", 3),
+        ]
+    );
+    assert!(listed.iter().all(|file| file.field_name.is_empty()));
+    assert_eq!(listed[2].path, Some(root.join("2024/05/photo.png")));
+
+    cleanup(root).await;
+}
+
+#[tokio::test]
+async fn stored_file_helpers_open_read_and_delete_disk_output() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .build()
+        .expect("builder should succeed");
+    let stored = store_single(&storage, "re-read me")
+        .await
+        .expect("store should succeed");
+
+    let mut contents = String::new();
+    tokio::io::AsyncReadExt::read_to_string(
+        &mut stored.open().await.expect("file should open"),
+        &mut contents,
+    )
+    .await
+    .expect("file should read");
+    assert_eq!(contents, "re-read me");
+    assert_eq!(
+        stored.read_bytes().await.expect("bytes should read"),
+        Bytes::from_static(b"re-read me")
+    );
+
+    stored.delete().await.expect("file should delete");
+    assert!(!exists(stored.path.as_ref().expect("disk path"))
+        .await
+        .expect("existence check"));
+    let err = stored.read_bytes().await.expect_err("deleted file");
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    cleanup(root).await;
+}
+
+async fn directory_entries(root: &std::path::Path) -> Vec<String> {
+    let mut names = list_directory(root)
+        .map_ok(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_owned()
+        })
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("list directory");
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn parse_and_store_removes_files_when_required_field_is_missing() {
+    let root = temp_root();
+    let storage = DiskStorage::builder()
+        .destination(&root)
+        .filename(FilenameStrategy::Random)
+        .build()
+        .expect("builder should succeed");
+    let multer = Multer::builder()
+        .storage(storage)
+        .fields([
+            SelectedField::file("avatar").min_count(1),
+            SelectedField::file("upload"),
+        ])
+        .build()
+        .expect("builder config");
+
+    let body = multipart_body(&[("upload", "file.txt", "text/plain", "hello")]);
+    let err = multer
+        .parse_and_store("BOUND", bytes_stream(body))
+        .await
+        .expect_err("missing avatar must fail");
+
+    assert!(matches!(err, MulterError::RequiredFieldMissing { .. }));
+    let left = std::fs::read_dir(&root).expect("root exists").count();
+    assert_eq!(left, 0, "stored files must be discarded");
+    cleanup(root).await;
+}
+
+async fn store_single(
+    storage: &DiskStorage,
+    payload: &str,
+) -> Result<multigear::StoredFile, MulterError> {
+    let multer = Multer::new(storage.clone());
+    let body = multipart_body(&[("upload", "file.txt", "text/plain", payload)]);
+    let mut multipart =
+        Multipart::new("BOUND", bytes_stream(body)).expect("multipart should initialize");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    multer.store(part).await
+}
+
+#[test]
+fn sanitize_filename_rejects_traversal_and_null_bytes() {
+    let traversal = sanitize_filename("../../etc/passwd");
+    assert!(!traversal.contains(".."));
     assert!(!traversal.contains('/'));
     assert!(!traversal.contains('\\'));
 
@@ -211,6 +1517,32 @@ fn sanitize_filename_rejects_traversal_and_null_bytes() {
     assert!(!nul.contains('?'));
 }
 
+#[test]
+fn filename_strategy_equality_and_hashing() {
+    let custom: FilenameStrategy = FilenameStrategy::Custom(Arc::new(|name| name));
+    let other_custom: FilenameStrategy = FilenameStrategy::Custom(Arc::new(|name| name));
+
+    assert_eq!(FilenameStrategy::Keep, FilenameStrategy::Keep);
+    assert_ne!(FilenameStrategy::Random, FilenameStrategy::Keep);
+    assert_eq!(custom, custom.clone());
+    assert_ne!(custom, other_custom);
+
+    let strategies = HashSet::from([
+        FilenameStrategy::Keep,
+        FilenameStrategy::Random,
+        FilenameStrategy::Keep,
+        custom.clone(),
+        custom.clone(),
+        other_custom,
+    ]);
+    assert_eq!(strategies.len(), 4);
+
+    assert!(FilenameStrategy::Keep.is_keep());
+    assert!(FilenameStrategy::Random.is_random());
+    assert!(custom.is_custom());
+    assert!(!custom.is_keep());
+}
+
 fn temp_root() -> PathBuf {
     std::env::temp_dir().join(format!("multigear-test-{}", Uuid::new_v4()))
 }
@@ -220,20 +1552,21 @@ async fn cleanup(path: PathBuf) {
 }
 
 fn multipart_body(parts: &[(&str, &str, &str, &str)]) -> Vec<u8> {
-    let mut out = Vec::new();
-    for (field, file_name, content_type, body) in parts {
-        out.extend_from_slice(b"--BOUND\r\n");
-        let disposition = format!(
-            "Content-Disposition: form-data; name=\"{field}\"; filename=\"{file_name}\"\r\n"
-        );
-        out.extend_from_slice(disposition.as_bytes());
-        let content_type = format!("Content-Type: {content_type}\r\n\r\n");
-        out.extend_from_slice(content_type.as_bytes());
-        out.extend_from_slice(body.as_bytes());
-        out.extend_from_slice(b"\r\n");
-    }
-    out.extend_from_slice(b"--BOUND--\r\n");
-    out
+    parts
+        .iter()
+        .fold(
+            MultipartBodyBuilder::new(),
+            |builder, (field, file_name, content_type, body)| {
+                builder.file(
+                    field,
+                    file_name,
+                    content_type,
+                    Bytes::copy_from_slice(body.as_bytes()),
+                )
+            },
+        )
+        .build("BOUND")
+        .to_vec()
 }
 
 fn bytes_stream(body: Vec<u8>) -> impl futures::Stream<Item = Result<Bytes, MulterError>> {
@@ -0,0 +1,100 @@
+#![allow(missing_docs)]
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::stream;
+use multigear::{
+    BoxStream, FileMeta, HybridStorage, MemoryStorage, MulterError, StorageEngine, StorageError,
+    StoredFile,
+};
+
+#[derive(Debug, Clone, Copy)]
+struct FailingStorage;
+
+#[async_trait::async_trait]
+impl StorageEngine for FailingStorage {
+    type Output = StoredFile;
+    type Error = StorageError;
+
+    async fn store(
+        &self,
+        _field_name: &str,
+        _file_name: Option<&str>,
+        _content_type: &str,
+        _stream: BoxStream<'_, Result<Bytes, MulterError>>,
+    ) -> Result<Self::Output, Self::Error> {
+        Err(StorageError::new("cache unavailable"))
+    }
+}
+
+#[tokio::test]
+async fn writes_through_to_primary_and_cache() {
+    let storage = HybridStorage::new(MemoryStorage::new(), MemoryStorage::new());
+
+    let stored = storage
+        .store(
+            "logo",
+            Some("logo.png"),
+            "image/png",
+            chunks(&["hel", "lo"]),
+        )
+        .await
+        .expect("store should succeed");
+
+    assert_eq!(stored.size, 5);
+    assert_eq!(
+        storage.primary().get(&stored.storage_key).await,
+        Some(Bytes::from_static(b"hello"))
+    );
+    assert_eq!(storage.cache().len().await, 1);
+}
+
+#[tokio::test]
+async fn primary_store_succeeds_when_cache_fails() {
+    let storage = HybridStorage::new(MemoryStorage::new(), FailingStorage);
+
+    let stored = storage
+        .store("avatar", Some("face.png"), "image/png", chunks(&["face"]))
+        .await
+        .expect("cache failure must not fail the store");
+
+    assert_eq!(
+        storage.primary().get(&stored.storage_key).await,
+        Some(Bytes::from_static(b"face"))
+    );
+}
+
+#[tokio::test]
+async fn cache_predicate_skips_cache_for_rejected_files() {
+    let predicate = Arc::new(|meta: &FileMeta| meta.field_name == "logo");
+    let storage = HybridStorage::new(MemoryStorage::new(), MemoryStorage::new())
+        .with_cache_predicate(predicate);
+
+    storage
+        .store(
+            "document",
+            Some("a.pdf"),
+            "application/pdf",
+            chunks(&["pdf"]),
+        )
+        .await
+        .expect("store should succeed");
+    assert_eq!(storage.primary().len().await, 1);
+    assert!(storage.cache().is_empty().await);
+
+    storage
+        .store("logo", Some("logo.png"), "image/png", chunks(&["png"]))
+        .await
+        .expect("store should succeed");
+    assert_eq!(storage.primary().len().await, 2);
+    assert_eq!(storage.cache().len().await, 1);
+}
+
+fn chunks(parts: &[&'static str]) -> BoxStream<'static, Result<Bytes, MulterError>> {
+    let items = parts
+        .iter()
+        .map(|part| Ok(Bytes::from_static(part.as_bytes())))
+        .collect::<Vec<_>>();
+    Box::pin(stream::iter(items))
+}
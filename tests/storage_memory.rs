@@ -1,8 +1,13 @@
 #![allow(missing_docs)]
 
+use std::sync::{Arc, Mutex};
+
 use bytes::Bytes;
-use futures::stream;
-use multigear::{MemoryStorage, Multer, MulterError, Multipart};
+use futures::{channel::mpsc, stream};
+use multigear::{
+    MemoryStorage, Multer, MulterError, Multipart, MultipartBodyBuilder, StorageEngine,
+    StorageEngineWithProgress, StorageError,
+};
 
 #[tokio::test]
 async fn stores_file_part_and_returns_metadata() {
@@ -72,23 +77,389 @@ async fn memory_storage_conformance_unique_keys_and_payload_integrity() {
         storage.get(&second_meta.storage_key).await,
         Some(Bytes::from_static(b"two"))
     );
+
+    assert_eq!(
+        storage.remove(&first_meta.storage_key).await,
+        Some(Bytes::from_static(b"one"))
+    );
+    storage.clear().await;
+    assert!(storage.is_empty().await);
 }
 
-fn multipart_body(parts: &[(&str, &str, &str, &str)]) -> Vec<u8> {
-    let mut out = Vec::new();
-    for (field, file_name, content_type, body) in parts {
-        out.extend_from_slice(b"--BOUND\r\n");
-        let disposition = format!(
-            "Content-Disposition: form-data; name=\"{field}\"; filename=\"{file_name}\"\r\n"
-        );
-        out.extend_from_slice(disposition.as_bytes());
-        let content_type = format!("Content-Type: {content_type}\r\n\r\n");
-        out.extend_from_slice(content_type.as_bytes());
-        out.extend_from_slice(body.as_bytes());
-        out.extend_from_slice(b"\r\n");
+#[tokio::test]
+async fn get_ref_allows_concurrent_borrowed_reads() {
+    let storage = MemoryStorage::new();
+    let multer = Multer::new(storage.clone());
+
+    let body = multipart_body(&[("avatar", "face.png", "image/png", "hello")]);
+    let mut multipart =
+        Multipart::new("BOUND", bytes_stream(body)).expect("multipart should initialize");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let stored = multer.store(part).await.expect("store should succeed");
+
+    let (first, second) = tokio::join!(
+        storage.get_ref(&stored.storage_key),
+        storage.get_ref(&stored.storage_key)
+    );
+    let first = first.expect("first borrowed read");
+    let second = second.expect("second borrowed read");
+    assert_eq!(&*first, b"hello");
+    assert_eq!(&*second, b"hello");
+    assert!(storage.get_ref("missing").await.is_none());
+}
+
+#[tokio::test]
+async fn clone_on_get_returns_equal_independent_copy() {
+    let storage = MemoryStorage::new().with_clone_on_get(true);
+    let multer = Multer::new(storage.clone());
+
+    let body = multipart_body(&[("avatar", "face.png", "image/png", "hello")]);
+    let mut multipart =
+        Multipart::new("BOUND", bytes_stream(body)).expect("multipart should initialize");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let stored = multer.store(part).await.expect("store should succeed");
+
+    let copied = storage
+        .get(&stored.storage_key)
+        .await
+        .expect("payload should exist");
+    let borrowed = storage
+        .get_ref(&stored.storage_key)
+        .await
+        .expect("payload should exist");
+    assert_eq!(copied, Bytes::from_static(b"hello"));
+    assert_ne!(copied.as_ptr(), borrowed.as_ptr());
+}
+
+#[tokio::test]
+async fn on_progress_reports_bytes_and_content_length_hint() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&events);
+    let storage = MemoryStorage::new().on_progress(move |stored, total| {
+        recorded
+            .lock()
+            .expect("progress lock")
+            .push((stored, total));
+    });
+    let multer = Multer::new(storage);
+
+    let (tx, rx) = mpsc::unbounded::<Result<Bytes, MulterError>>();
+    tx.unbounded_send(Ok(Bytes::from_static(
+        b"--BOUND\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"a.bin\"\r\nContent-Length: 6\r\n\r\nabc",
+    )))
+    .expect("send first chunk");
+    tx.unbounded_send(Ok(Bytes::from_static(b"def\r\n--BOUND--\r\n")))
+        .expect("send second chunk");
+    drop(tx);
+
+    let mut multipart = Multipart::new("BOUND", rx).expect("multipart should initialize");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    let stored = multer.store(part).await.expect("store should succeed");
+
+    assert_eq!(stored.size, 6);
+    let events = events.lock().expect("progress lock").clone();
+    assert!(!events.is_empty());
+    assert!(events.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(events.last(), Some(&(6, Some(6))));
+}
+
+#[tokio::test]
+async fn store_with_progress_wraps_any_storage_engine() {
+    let storage = MemoryStorage::new();
+    let last = Arc::new(Mutex::new(None));
+    let recorded = Arc::clone(&last);
+
+    let chunks = stream::iter([
+        Ok::<Bytes, MulterError>(Bytes::from_static(b"hel")),
+        Ok(Bytes::from_static(b"lo")),
+    ]);
+    let stored = storage
+        .store_with_progress(
+            "upload",
+            Some("a.txt"),
+            "text/plain",
+            None,
+            Box::pin(chunks),
+            move |stored, total| {
+                *recorded.lock().expect("progress lock") = Some((stored, total));
+            },
+        )
+        .await
+        .expect("store should succeed");
+
+    assert_eq!(stored.size, 5);
+    assert_eq!(*last.lock().expect("progress lock"), Some((5, None)));
+}
+
+#[tokio::test]
+async fn capacity_rejects_writes_past_the_limit() {
+    let storage = MemoryStorage::with_capacity(10);
+
+    let first = store_chunks(&storage, &["1234", "56"])
+        .await
+        .expect("first file fits");
+    assert_eq!(storage.used_bytes(), 6);
+
+    let err = store_chunks(&storage, &["abc", "de"])
+        .await
+        .expect_err("second file crosses the limit");
+    assert_eq!(err, StorageError::CapacityExceeded { used: 9, max: 10 });
+    assert_eq!(storage.used_bytes(), 6, "rejected bytes are released");
+    assert_eq!(storage.len().await, 1);
+
+    store_chunks(&storage, &["abcd"])
+        .await
+        .expect("file filling the remaining capacity exactly fits");
+    assert_eq!(storage.used_bytes(), 10);
+    assert!(storage.get(&first.storage_key).await.is_some());
+}
+
+#[tokio::test]
+async fn remove_evicts_entries_and_frees_capacity() {
+    let storage = MemoryStorage::with_capacity(8);
+    let old = store_chunks(&storage, &["old-data"])
+        .await
+        .expect("first file fits");
+    store_chunks(&storage, &["new"])
+        .await
+        .expect_err("storage is full");
+
+    let removed = storage
+        .remove(&old.storage_key)
+        .await
+        .expect("entry should exist");
+    assert_eq!(removed, Bytes::from_static(b"old-data"));
+    assert_eq!(storage.used_bytes(), 0);
+    assert!(storage.remove(&old.storage_key).await.is_none());
+
+    let fresh = store_chunks(&storage, &["new"])
+        .await
+        .expect("freed capacity accepts new file");
+    assert_eq!(
+        storage.get(&fresh.storage_key).await,
+        Some(Bytes::from_static(b"new"))
+    );
+    assert_eq!(storage.used_bytes(), 3);
+}
+
+#[tokio::test]
+async fn global_cap_admits_files_until_stored_total_is_reached() {
+    let storage = MemoryStorage::new().with_global_cap(10);
+    let shared = storage.clone();
+
+    let first = store_chunks(&storage, &["12345", "6"])
+        .await
+        .expect("first file fits");
+    store_chunks(&shared, &["abcd"])
+        .await
+        .expect("file filling the cap exactly fits");
+    assert_eq!(storage.stored_bytes(), 10);
+
+    let err = store_chunks(&shared, &["x"])
+        .await
+        .expect_err("storage is at its cap");
+    assert_eq!(
+        err,
+        StorageError::GlobalCapacityExceeded {
+            stored: 10,
+            incoming: 1,
+            max: 10,
+        }
+    );
+    assert_eq!(storage.len().await, 2);
+
+    let err = storage
+        .store_sized(
+            "upload",
+            Some("big.bin"),
+            "application/octet-stream",
+            Some(64),
+            Box::pin(stream::iter([Ok(Bytes::from_static(b"x"))])),
+        )
+        .await
+        .expect_err("size hint past the cap is rejected up front");
+    assert!(
+        matches!(
+            err,
+            StorageError::GlobalCapacityExceeded { incoming: 64, .. }
+        ),
+        "{err:?}"
+    );
+
+    storage
+        .remove(&first.storage_key)
+        .await
+        .expect("entry should exist");
+    assert_eq!(shared.stored_bytes(), 4);
+    store_chunks(&shared, &["again"])
+        .await
+        .expect("freed room accepts new file");
+    assert_eq!(storage.stored_bytes(), 9);
+
+    storage.clear().await;
+    assert_eq!(storage.stored_bytes(), 0);
+}
+
+#[tokio::test]
+async fn remove_and_clear_update_len() {
+    let storage = MemoryStorage::new();
+    let mut keys = Vec::new();
+    for chunk in ["one", "two", "three"] {
+        let stored = store_chunks(&storage, &[chunk])
+            .await
+            .expect("store should succeed");
+        keys.push(stored.storage_key);
     }
-    out.extend_from_slice(b"--BOUND--\r\n");
-    out
+    assert_eq!(storage.len().await, 3);
+
+    assert!(storage.remove(&keys[1]).await.is_some());
+    assert_eq!(storage.len().await, 2);
+    assert!(storage.remove("missing").await.is_none());
+    assert_eq!(storage.len().await, 2);
+    assert_eq!(storage.used_bytes(), 8);
+
+    storage.clear().await;
+    assert_eq!(storage.len().await, 0);
+    assert!(storage.is_empty().await);
+    assert_eq!(storage.used_bytes(), 0);
+    assert!(storage.get(&keys[0]).await.is_none());
+
+    store_chunks(&storage, &["again"])
+        .await
+        .expect("storage is usable after clear");
+    assert_eq!(storage.len().await, 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn snapshots_stay_consistent_during_concurrent_inserts() {
+    let storage = MemoryStorage::new();
+    let writers: Vec<_> = (0..32)
+        .map(|index| {
+            let storage = storage.clone();
+            tokio::spawn(async move {
+                let payload = Bytes::from(format!("payload-{index}"));
+                storage
+                    .store(
+                        "upload",
+                        Some("file.bin"),
+                        "application/octet-stream",
+                        Box::pin(stream::iter([Ok(payload)])),
+                    )
+                    .await
+                    .expect("store should succeed")
+            })
+        })
+        .collect();
+
+    let mut previous_len = 0;
+    while previous_len < 32 {
+        let (snapshot, keys) =
+            tokio::task::block_in_place(|| (storage.snapshot(), storage.keys_snapshot()));
+        assert!(snapshot.len() >= previous_len, "snapshots only grow");
+        assert!(keys.len() >= snapshot.len());
+        assert!(snapshot
+            .values()
+            .all(|bytes| bytes.starts_with(b"payload-")));
+        previous_len = snapshot.len();
+        tokio::task::yield_now().await;
+    }
+
+    for writer in writers {
+        let stored = writer.await.expect("writer should finish");
+        let snapshot = tokio::task::block_in_place(|| storage.snapshot());
+        assert_eq!(snapshot[&stored.storage_key].len() as u64, stored.size);
+    }
+    let mut keys = tokio::task::block_in_place(|| storage.keys_snapshot());
+    let mut snapshot_keys: Vec<_> = tokio::task::block_in_place(|| storage.snapshot())
+        .into_keys()
+        .collect();
+    keys.sort();
+    snapshot_keys.sort();
+    assert_eq!(keys, snapshot_keys);
+}
+
+#[test]
+fn snapshot_is_callable_outside_async_context() {
+    let storage = MemoryStorage::new();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("runtime should build");
+    let stored = runtime
+        .block_on(store_chunks(&storage, &["sync", " read"]))
+        .expect("store should succeed");
+
+    assert_eq!(
+        storage.keys_snapshot(),
+        std::slice::from_ref(&stored.storage_key)
+    );
+    assert_eq!(
+        storage.snapshot()[&stored.storage_key],
+        Bytes::from_static(b"sync read")
+    );
+}
+
+#[tokio::test]
+async fn stored_file_helpers_fail_without_disk_path() {
+    let storage = MemoryStorage::new();
+    let stored = store_chunks(&storage, &["in memory"])
+        .await
+        .expect("store should succeed");
+    assert!(stored.path.is_none());
+
+    let err = stored.open().await.expect_err("memory output has no path");
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert!(err.to_string().contains(&stored.storage_key), "{err}");
+    assert!(stored.read_bytes().await.is_err());
+    assert!(stored.delete().await.is_err());
+    assert_eq!(storage.len().await, 1);
+}
+
+async fn store_chunks(
+    storage: &MemoryStorage,
+    chunks: &[&'static str],
+) -> Result<multigear::StoredFile, StorageError> {
+    let chunks = chunks
+        .iter()
+        .map(|chunk| Ok(Bytes::from_static(chunk.as_bytes())))
+        .collect::<Vec<Result<Bytes, MulterError>>>();
+    storage
+        .store(
+            "upload",
+            Some("file.bin"),
+            "application/octet-stream",
+            Box::pin(stream::iter(chunks)),
+        )
+        .await
+}
+
+fn multipart_body(parts: &[(&str, &str, &str, &str)]) -> Vec<u8> {
+    parts
+        .iter()
+        .fold(
+            MultipartBodyBuilder::new(),
+            |builder, (field, file_name, content_type, body)| {
+                builder.file(
+                    field,
+                    file_name,
+                    content_type,
+                    Bytes::copy_from_slice(body.as_bytes()),
+                )
+            },
+        )
+        .build("BOUND")
+        .to_vec()
 }
 
 fn bytes_stream(body: Vec<u8>) -> impl futures::Stream<Item = Result<Bytes, MulterError>> {
@@ -0,0 +1,187 @@
+#![allow(missing_docs)]
+#![cfg(feature = "s3")]
+
+//! Tests that talk to a bucket run only when `S3_TEST_BUCKET` is set.
+//!
+//! Credentials come from `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY`; set
+//! `S3_TEST_ENDPOINT` to target MinIO or another S3-compatible service.
+
+use aws_sdk_s3::{
+    config::{BehaviorVersion, Credentials, Region},
+    Client,
+};
+use bytes::Bytes;
+use futures::{channel::mpsc, stream};
+use multigear::{Multer, MulterError, Multipart, MultipartBodyBuilder, S3Storage};
+
+#[test]
+fn builder_requires_client_and_bucket() {
+    let err = S3Storage::builder()
+        .bucket("uploads")
+        .build()
+        .expect_err("client is required");
+    assert!(err.to_string().contains("requires a client"), "{err}");
+
+    let err = S3Storage::builder()
+        .client(offline_client())
+        .bucket("  ")
+        .build()
+        .expect_err("bucket is required");
+    assert!(err.to_string().contains("bucket cannot be empty"), "{err}");
+}
+
+#[test]
+fn builder_rejects_part_size_below_s3_minimum() {
+    let err = S3Storage::builder()
+        .client(offline_client())
+        .bucket("uploads")
+        .part_size(1024)
+        .build()
+        .expect_err("part size below 5 MiB must fail");
+    assert!(
+        err.to_string().contains("part size must be at least"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn streams_small_file_to_bucket() {
+    let Some((client, bucket)) = test_bucket() else {
+        return;
+    };
+    let storage = S3Storage::builder()
+        .client(client.clone())
+        .bucket(&bucket)
+        .key_prefix("multigear-test/")
+        .build()
+        .expect("builder should succeed");
+    let multer = Multer::new(storage);
+
+    let body = multipart_body(&[("upload", "../bad:name?.txt", "text/plain", "hello")]);
+    let mut multipart =
+        Multipart::new("BOUND", bytes_stream(body)).expect("multipart should initialize");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+
+    let stored = multer.store(part).await.expect("store should succeed");
+    assert!(stored.path.is_none());
+    assert_eq!(stored.size, 5);
+    assert!(stored.storage_key.starts_with("multigear-test/"));
+    assert!(!stored.storage_key.contains(".."));
+    assert_eq!(
+        read_object(&client, &bucket, &stored.storage_key).await,
+        b"hello"
+    );
+
+    cleanup(&client, &bucket, &stored.storage_key).await;
+}
+
+#[tokio::test]
+async fn streams_large_file_as_multipart_upload() {
+    let Some((client, bucket)) = test_bucket() else {
+        return;
+    };
+    let storage = S3Storage::builder()
+        .client(client.clone())
+        .bucket(&bucket)
+        .key_prefix("multigear-test/")
+        .build()
+        .expect("builder should succeed");
+    let multer = Multer::new(storage);
+
+    let (tx, rx) = mpsc::unbounded::<Result<Bytes, MulterError>>();
+    tx.unbounded_send(Ok(Bytes::from_static(
+        b"--BOUND\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"big.bin\"\r\n\r\n",
+    )))
+    .expect("send prelude");
+    for _ in 0..176 {
+        tx.unbounded_send(Ok(Bytes::from(vec![b'z'; 64 * 1024])))
+            .expect("send payload chunk");
+    }
+    tx.unbounded_send(Ok(Bytes::from_static(b"\r\n--BOUND--\r\n")))
+        .expect("send trailer");
+    drop(tx);
+
+    let mut multipart = Multipart::new("BOUND", rx).expect("multipart should initialize");
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+
+    let stored = multer.store(part).await.expect("store should succeed");
+    assert_eq!(stored.size, 176 * 64 * 1024);
+    let object = read_object(&client, &bucket, &stored.storage_key).await;
+    assert_eq!(object.len(), 176 * 64 * 1024);
+
+    cleanup(&client, &bucket, &stored.storage_key).await;
+}
+
+fn test_bucket() -> Option<(Client, String)> {
+    let bucket = std::env::var("S3_TEST_BUCKET").ok()?;
+    let access_key = std::env::var("AWS_ACCESS_KEY_ID").unwrap_or_default();
+    let secret_key = std::env::var("AWS_SECRET_ACCESS_KEY").unwrap_or_default();
+    let region = std::env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_owned());
+
+    let mut config = aws_sdk_s3::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new(region))
+        .credentials_provider(Credentials::new(access_key, secret_key, None, None, "env"));
+    if let Ok(endpoint) = std::env::var("S3_TEST_ENDPOINT") {
+        config = config.endpoint_url(endpoint).force_path_style(true);
+    }
+    Some((Client::from_conf(config.build()), bucket))
+}
+
+fn offline_client() -> Client {
+    Client::from_conf(
+        aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .build(),
+    )
+}
+
+async fn read_object(client: &Client, bucket: &str, key: &str) -> Vec<u8> {
+    client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .expect("object should exist")
+        .body
+        .collect()
+        .await
+        .expect("object body should read")
+        .to_vec()
+}
+
+async fn cleanup(client: &Client, bucket: &str, key: &str) {
+    let _ = client.delete_object().bucket(bucket).key(key).send().await;
+}
+
+fn multipart_body(parts: &[(&str, &str, &str, &str)]) -> Vec<u8> {
+    parts
+        .iter()
+        .fold(
+            MultipartBodyBuilder::new(),
+            |builder, (field, file_name, content_type, body)| {
+                builder.file(
+                    field,
+                    file_name,
+                    content_type,
+                    Bytes::copy_from_slice(body.as_bytes()),
+                )
+            },
+        )
+        .build("BOUND")
+        .to_vec()
+}
+
+fn bytes_stream(body: Vec<u8>) -> impl futures::Stream<Item = Result<Bytes, MulterError>> {
+    stream::iter([Ok(Bytes::from(body))])
+}
@@ -0,0 +1,84 @@
+#![allow(missing_docs)]
+#![cfg(feature = "tower")]
+
+use std::{convert::Infallible, sync::Arc};
+
+use bytes::Bytes;
+use http::{header, Request};
+use http_body_util::{BodyExt, Full};
+use multigear::{
+    tower::{MulterLayer, MultipartExtension},
+    MemoryStorage, Multer,
+};
+use tower::{service_fn, Layer, ServiceExt};
+
+async fn describe(request: Request<Full<Bytes>>) -> Result<String, Infallible> {
+    let Some(extension) = request.extensions().get::<MultipartExtension>().cloned() else {
+        let body = request
+            .into_body()
+            .collect()
+            .await
+            .expect("body should read")
+            .to_bytes();
+        return Ok(format!("passthrough: {}", String::from_utf8_lossy(&body)));
+    };
+
+    let mut multipart = extension.take().expect("multipart should be present");
+    assert!(extension.take().is_none(), "multipart is handed out once");
+
+    let mut fields = Vec::new();
+    while let Some(mut part) = multipart.next_part().await.expect("part should parse") {
+        let name = part.field_name().to_owned();
+        let text = part.text().await.expect("body should read");
+        fields.push(format!("{name}={text}"));
+    }
+    Ok(fields.join(","))
+}
+
+fn layer() -> MulterLayer<MemoryStorage> {
+    MulterLayer::new(Arc::new(Multer::new(MemoryStorage::new())))
+}
+
+#[tokio::test]
+async fn layer_attaches_streaming_multipart_extension() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"title\"\r\n",
+        "\r\n",
+        "Holiday\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"photo\"; filename=\"a.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "PNG\r\n",
+        "--BOUND--\r\n"
+    );
+    let request = Request::builder()
+        .method("POST")
+        .header(header::CONTENT_TYPE, "multipart/form-data; boundary=BOUND")
+        .body(Full::new(Bytes::from_static(body.as_bytes())))
+        .expect("request should build");
+
+    let response = layer()
+        .layer(service_fn(describe))
+        .oneshot(request)
+        .await
+        .expect("service should respond");
+    assert_eq!(response, "title=Holiday,photo=PNG");
+}
+
+#[tokio::test]
+async fn layer_forwards_non_multipart_requests_unchanged() {
+    let request = Request::builder()
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Full::new(Bytes::from_static(b"{\"ok\":true}")))
+        .expect("request should build");
+
+    let response = layer()
+        .layer(service_fn(describe))
+        .oneshot(request)
+        .await
+        .expect("service should respond");
+    assert_eq!(response, "passthrough: {\"ok\":true}");
+}
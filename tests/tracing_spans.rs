@@ -0,0 +1,113 @@
+#![allow(missing_docs)]
+#![cfg(feature = "tracing")]
+
+use bytes::Bytes;
+use futures::stream;
+use multigear::{MemoryStorage, Multer, MulterError};
+use tracing_test::traced_test;
+
+const BODY: &str = concat!(
+    "--BOUND\r\n",
+    "Content-Disposition: form-data; name=\"upload\"; filename=\"a.txt\"\r\n",
+    "Content-Type: text/plain\r\n",
+    "\r\n",
+    "hello\r\n",
+    "--BOUND--\r\n"
+);
+
+#[tokio::test]
+#[traced_test]
+async fn emits_spans_with_part_attributes_when_enabled() {
+    let multer = Multer::builder()
+        .storage(MemoryStorage::new())
+        .with_tracing_spans(true)
+        .build()
+        .expect("builder should succeed");
+
+    multer
+        .parse_and_store("BOUND", body_stream())
+        .await
+        .expect("upload should succeed");
+
+    assert!(logs_contain("multer.parse_and_store{boundary=\"BOUND\"}"));
+    assert!(logs_contain(
+        "multer.part{field_name=\"upload\" file_name=\"a.txt\" content_type=\"text/plain\"}"
+    ));
+    assert!(logs_contain(
+        "multer.store{field_name=\"upload\" file_name=\"a.txt\" content_type=\"text/plain\"}"
+    ));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn part_span_scopes_body_reads_and_storage() {
+    let multer = Multer::builder()
+        .storage(MemoryStorage::new())
+        .with_tracing_spans(true)
+        .build()
+        .expect("builder should succeed");
+    let mut multipart = multer
+        .multipart_from_boundary("BOUND", body_stream())
+        .expect("multipart should initialize");
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+    assert_eq!(
+        part.span().metadata().map(|metadata| metadata.name()),
+        Some("multer.part")
+    );
+    multer.store(part).await.expect("store should succeed");
+
+    let part_span =
+        "multer.part{field_name=\"upload\" file_name=\"a.txt\" content_type=\"text/plain\"}";
+    assert!(logs_contain(&format!(
+        "{part_span}: multigear: multer: dispatching part to storage engine"
+    )));
+    assert!(logs_contain(&format!("{part_span}:multer.store{{")));
+    assert!(logs_contain(&format!(
+        "{part_span}: multigear::parser::stream: multipart parser: terminal boundary reached"
+    )));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn omits_spans_by_default() {
+    let multer = Multer::new(MemoryStorage::new());
+
+    multer
+        .parse_and_store("BOUND", body_stream())
+        .await
+        .expect("upload should succeed");
+
+    assert!(logs_contain("multer: storing file part"));
+    assert!(!logs_contain("multer.part"));
+    assert!(!logs_contain("multer.store"));
+    assert!(!logs_contain("multer.parse_and_store"));
+}
+
+fn body_stream() -> impl futures::Stream<Item = Result<Bytes, MulterError>> + Unpin + Send {
+    stream::iter([Ok::<Bytes, MulterError>(Bytes::from_static(
+        BODY.as_bytes(),
+    ))])
+}
+
+#[test]
+#[traced_test]
+fn warns_on_repeated_content_disposition() {
+    let mut headers = http::HeaderMap::new();
+    for value in ["form-data; name=\"first\"", "form-data; name=\"second\""] {
+        headers.append(
+            http::header::CONTENT_DISPOSITION,
+            http::HeaderValue::from_static(value),
+        );
+    }
+
+    let parsed = multigear::parser::headers::parse_part_headers(&headers)
+        .expect("part headers should parse");
+
+    assert_eq!(parsed.field_name, "second");
+    assert!(logs_contain("multiple Content-Disposition headers"));
+}
@@ -0,0 +1,174 @@
+#![allow(missing_docs)]
+#![cfg(feature = "cte")]
+
+use bytes::Bytes;
+use futures::{stream, Stream, TryStreamExt};
+use multigear::{MemoryStorage, Multer, MulterError, Multipart};
+use tokio::io::AsyncReadExt;
+
+fn encoded_body(encoding: &str, content_type: &str, file_name: &str, body: &str) -> Vec<u8> {
+    format!(
+        concat!(
+            "--BOUND\r\n",
+            "Content-Disposition: form-data; name=\"upload\"; filename=\"{}\"\r\n",
+            "Content-Type: {}\r\n",
+            "Content-Transfer-Encoding: {}\r\n",
+            "\r\n",
+            "{}\r\n",
+            "--BOUND--\r\n"
+        ),
+        file_name, content_type, encoding, body
+    )
+    .into_bytes()
+}
+
+/// Splits `body` into `chunk_size`-byte chunks so encoded units straddle chunk boundaries.
+fn chunked_stream(
+    body: Vec<u8>,
+    chunk_size: usize,
+) -> impl Stream<Item = Result<Bytes, MulterError>> + Unpin {
+    let chunks: Vec<_> = body
+        .chunks(chunk_size)
+        .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+        .collect();
+    stream::iter(chunks)
+}
+
+#[tokio::test]
+async fn decodes_base64_part_body_split_across_chunks() {
+    // "Hello, multipart world!" wrapped like an email body.
+    let body = encoded_body(
+        "BASE64",
+        "text/plain",
+        "hello.txt",
+        "SGVsbG8sIG11bHRp\r\ncGFydCB3b3JsZCE=",
+    );
+
+    for chunk_size in [1, 3, 7, 64] {
+        let mut multipart = Multipart::new("BOUND", chunked_stream(body.clone(), chunk_size))
+            .expect("multipart should initialize");
+        let mut part = multipart
+            .next_part()
+            .await
+            .expect("part should parse")
+            .expect("part expected");
+        assert_eq!(
+            part.text().await.expect("body should decode"),
+            "Hello, multipart world!",
+            "chunk size {chunk_size}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn stream_impl_decodes_base64_part_bodies() {
+    let body = encoded_body(
+        "base64",
+        "text/plain",
+        "hello.txt",
+        "SGVsbG8sIG11bHRp\r\ncGFydCB3b3JsZCE=",
+    );
+
+    for chunk_size in [1, 3, 7, 64] {
+        let parts: Vec<_> = Multipart::new("BOUND", chunked_stream(body.clone(), chunk_size))
+            .expect("multipart should initialize")
+            .into_stream()
+            .try_collect()
+            .await
+            .expect("stream should decode");
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            parts[0].text().expect("body should be UTF-8"),
+            "Hello, multipart world!",
+            "chunk size {chunk_size}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn stream_yields_decoded_chunks_incrementally() {
+    let body = encoded_body(
+        "base64",
+        "application/octet-stream",
+        "a.bin",
+        "AAECAwQFBgc=",
+    );
+    let mut multipart =
+        Multipart::new("BOUND", chunked_stream(body, 4)).expect("multipart should initialize");
+    let mut part = multipart
+        .next_part()
+        .await
+        .expect("part should parse")
+        .expect("part expected");
+
+    let chunks: Vec<Bytes> = part
+        .stream()
+        .try_collect()
+        .await
+        .expect("body should decode");
+    assert!(
+        chunks.len() > 1,
+        "body should not be buffered into one chunk"
+    );
+    assert_eq!(chunks.concat(), [0, 1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[tokio::test]
+async fn decodes_quoted_printable_through_reader() {
+    let body = encoded_body(
+        "quoted-printable",
+        "text/plain; charset=utf-8",
+        "note.txt",
+        "caf=C3=A9 =3D tr=\r\nes bien",
+    );
+
+    for chunk_size in [1, 2, 5, 64] {
+        let mut multipart = Multipart::new("BOUND", chunked_stream(body.clone(), chunk_size))
+            .expect("multipart should initialize");
+        let part = multipart
+            .next_part()
+            .await
+            .expect("part should parse")
+            .expect("part expected");
+        let mut text = String::new();
+        part.into_reader()
+            .read_to_string(&mut text)
+            .await
+            .expect("body should decode");
+        assert_eq!(text, "café = tres bien", "chunk size {chunk_size}");
+    }
+}
+
+#[tokio::test]
+async fn parse_and_store_persists_decoded_file_bytes() {
+    let storage = MemoryStorage::new();
+    let multer = Multer::new(storage.clone());
+    let body = encoded_body("base64", "image/png", "dot.png", "iVBORw0KGgo=");
+
+    let processed = multer
+        .parse_and_store("BOUND", chunked_stream(body, 5))
+        .await
+        .expect("request should parse");
+    let stored = &processed.stored_files[0];
+    assert_eq!(stored.size, 8);
+    assert_eq!(
+        storage.get(&stored.storage_key).await.expect("file stored"),
+        Bytes::from_static(b"\x89PNG\r\n\x1a\n")
+    );
+}
+
+#[tokio::test]
+async fn rejects_malformed_encoded_bodies() {
+    for (encoding, payload) in [("base64", "not*base64"), ("quoted-printable", "bad=ZZ")] {
+        let body = encoded_body(encoding, "text/plain", "a.txt", payload);
+        let mut multipart =
+            Multipart::new("BOUND", chunked_stream(body, 64)).expect("multipart should initialize");
+        let mut part = multipart
+            .next_part()
+            .await
+            .expect("part should parse")
+            .expect("part expected");
+        let err = part.bytes().await.expect_err("malformed body must fail");
+        assert!(matches!(err, MulterError::Parse(_)), "{encoding}: {err}");
+    }
+}
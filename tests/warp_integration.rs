@@ -0,0 +1,110 @@
+#![allow(missing_docs)]
+#![cfg(feature = "warp")]
+
+use std::sync::Arc;
+
+use multigear::{
+    warp::{warp_filter, WarpMulterRejection},
+    MemoryStorage, Multer, MulterError,
+};
+
+fn multer() -> Arc<Multer<MemoryStorage>> {
+    Arc::new(Multer::new(MemoryStorage::new()))
+}
+
+#[tokio::test]
+async fn warp_filter_extracts_streaming_multipart() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"avatar\"; filename=\"a.png\"\r\n",
+        "Content-Type: image/png\r\n",
+        "\r\n",
+        "PNG\r\n",
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND--\r\n"
+    );
+    let multer = multer();
+
+    let mut multipart = warp::test::request()
+        .method("POST")
+        .header("content-type", "multipart/form-data; boundary=BOUND")
+        .body(body)
+        .filter(&warp_filter(Arc::clone(&multer)))
+        .await
+        .expect("filter should extract multipart");
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("file part should parse")
+        .expect("file part expected");
+    let stored = multer.store(part).await.expect("store should succeed");
+    assert_eq!(stored.field_name, "avatar");
+    assert_eq!(stored.size, 3);
+
+    let mut note = multipart
+        .next_part()
+        .await
+        .expect("text part should parse")
+        .expect("text part expected");
+    assert_eq!(note.text().await.expect("text should read"), "hello");
+    assert!(multipart
+        .next_part()
+        .await
+        .expect("end should parse")
+        .is_none());
+}
+
+#[tokio::test]
+async fn warp_filter_rejects_missing_or_invalid_content_type() {
+    let missing = warp::test::request()
+        .method("POST")
+        .body("{}")
+        .filter(&warp_filter(multer()))
+        .await
+        .expect_err("missing content type must reject");
+    let rejection = missing
+        .find::<WarpMulterRejection>()
+        .expect("rejection should carry MulterError");
+    assert!(matches!(rejection.0, MulterError::Parse(_)));
+    assert_eq!(rejection.0.http_status(), 400);
+
+    let invalid = warp::test::request()
+        .method("POST")
+        .header("content-type", "application/json")
+        .body("{}")
+        .filter(&warp_filter(multer()))
+        .await
+        .expect_err("non-multipart content type must reject");
+    assert!(invalid.find::<WarpMulterRejection>().is_some());
+}
+
+#[tokio::test]
+async fn warp_filter_ignores_malformed_content_length() {
+    let body = concat!(
+        "--BOUND\r\n",
+        "Content-Disposition: form-data; name=\"note\"\r\n",
+        "\r\n",
+        "hello\r\n",
+        "--BOUND--\r\n"
+    );
+
+    let mut multipart = warp::test::request()
+        .method("POST")
+        .header("content-type", "multipart/form-data; boundary=BOUND")
+        .body(body)
+        .header("content-length", "not-a-number")
+        .filter(&warp_filter(multer()))
+        .await
+        .expect("malformed content length must not reject");
+
+    let part = multipart
+        .next_part()
+        .await
+        .expect("text part should parse")
+        .expect("text part expected");
+    assert_eq!(part.request_content_length(), None);
+}